@@ -1 +1,4 @@
 pub mod http_client;
+pub mod rpc;
+pub mod tracker;
+pub mod transaction;
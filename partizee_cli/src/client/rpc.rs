@@ -0,0 +1,101 @@
+use crate::utils::cryptography::address::ADDRESS_LENGTH;
+
+/// Big-endian byte writer matching the Partisia RPC serialization format
+///
+/// # Fields
+/// * `bytes` - Serialized bytes written so far
+#[derive(Debug, Default, Clone)]
+pub struct RpcWriter {
+    bytes: Vec<u8>,
+}
+
+impl RpcWriter {
+    /// Creates an empty writer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writes a single byte
+    pub fn write_u8(&mut self, value: u8) -> &mut Self {
+        self.bytes.push(value);
+        self
+    }
+
+    /// Writes a big-endian 32 bit signed integer
+    pub fn write_i32(&mut self, value: i32) -> &mut Self {
+        self.bytes.extend_from_slice(&value.to_be_bytes());
+        self
+    }
+
+    /// Writes a big-endian 32 bit unsigned integer
+    pub fn write_u32(&mut self, value: u32) -> &mut Self {
+        self.bytes.extend_from_slice(&value.to_be_bytes());
+        self
+    }
+
+    /// Writes a big-endian 64 bit signed integer
+    pub fn write_i64(&mut self, value: i64) -> &mut Self {
+        self.bytes.extend_from_slice(&value.to_be_bytes());
+        self
+    }
+
+    /// Writes raw bytes without a length prefix
+    pub fn write_bytes(&mut self, value: &[u8]) -> &mut Self {
+        self.bytes.extend_from_slice(value);
+        self
+    }
+
+    /// Writes bytes prefixed with their length as a 32 bit integer
+    pub fn write_dynamic_bytes(&mut self, value: &[u8]) -> &mut Self {
+        self.write_i32(value.len() as i32);
+        self.write_bytes(value)
+    }
+
+    /// Writes a UTF-8 string prefixed with its byte length
+    pub fn write_string(&mut self, value: &str) -> &mut Self {
+        self.write_dynamic_bytes(value.as_bytes())
+    }
+
+    /// Returns the serialized bytes
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// Decodes a 42 character hex address into its 21 raw bytes
+///
+/// # Arguments
+/// * `address` - Hex encoded blockchain address
+///
+/// # Returns
+/// * `Result<[u8; ADDRESS_LENGTH]>` - Raw address bytes
+pub fn address_to_bytes(address: &str) -> Result<[u8; ADDRESS_LENGTH], Box<dyn std::error::Error>> {
+    let decoded: Vec<u8> = hex::decode(address.trim())
+        .map_err(|e| format!("address_to_bytes: Invalid hex in address {}: {}", address, e))?;
+    decoded
+        .try_into()
+        .map_err(|_| format!("address_to_bytes: Address must be 21 bytes: {}", address).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rpc_writer_big_endian() {
+        let mut writer = RpcWriter::new();
+        writer.write_u8(1).write_i32(2).write_i64(3).write_string("ab");
+        assert_eq!(
+            hex::encode(writer.into_bytes()),
+            "01000000020000000000000003000000026162"
+        );
+    }
+
+    #[test]
+    fn test_address_to_bytes() {
+        let bytes = address_to_bytes("00d277aa1bf5702ab9fc690b04bd68b5a981095530").unwrap();
+        assert_eq!(bytes[0], 0x00);
+        assert_eq!(bytes[20], 0x30);
+        assert!(address_to_bytes("00d277").is_err());
+    }
+}
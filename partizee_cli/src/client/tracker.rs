@@ -0,0 +1,339 @@
+use crate::client::transaction::{now_millis, ExecutedTransaction, SentTransaction, TransactionClient};
+use crate::utils::constants::TRANSACTION_POLL_INTERVAL_MS;
+use std::time::Duration;
+
+/// Transaction or spawned event in a trace
+///
+/// # Fields
+/// * `depth` - 0 for the traced transaction, 1 for the events it spawned and so on
+/// * `transaction_hash` - Hex encoded identifier of the transaction or event
+/// * `shard` - Shard the transaction was routed to, if known
+/// * `executed` - Execution outcome, None while the transaction is pending
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TracedTransaction {
+    pub depth: usize,
+    pub transaction_hash: String,
+    pub shard: Option<String>,
+    pub executed: Option<ExecutedTransaction>,
+}
+
+/// Transaction and every event it spawned, in execution order
+///
+/// # Fields
+/// * `transactions` - Traced transaction followed by its events, depth first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionTrace {
+    pub transactions: Vec<TracedTransaction>,
+}
+
+/// Follows a transaction and the events it spawns across shards
+///
+/// # Fields
+/// * `client` - Client of the transaction's network
+/// * `timeout_ms` - Milliseconds to wait for the whole trace before giving up
+pub struct TransactionTracker<'a> {
+    client: &'a TransactionClient,
+    timeout_ms: i64,
+}
+
+/// Finds the transaction hash in the output of a command that sent a transaction
+///
+/// # Arguments
+/// * `output` - Command output
+///
+/// # Returns
+/// * `Option<String>` - First 32 byte hex string in the output, lowercased
+pub fn transaction_hash_in_output(output: &str) -> Option<String> {
+    output
+        .split(|c: char| !c.is_ascii_hexdigit())
+        .find(|word| word.len() == 64)
+        .map(|word| word.to_lowercase())
+}
+
+impl TracedTransaction {
+    /// Whether the transaction has been executed in a finalized block
+    pub fn is_final(&self) -> bool {
+        self.executed
+            .as_ref()
+            .map(|executed| executed.finalized)
+            .unwrap_or(false)
+    }
+}
+
+impl TransactionTrace {
+    /// Whether every transaction in the trace has been executed and finalized
+    pub fn is_final(&self) -> bool {
+        self.transactions.iter().all(|traced| traced.is_final())
+    }
+
+    /// Describes the first failed transaction or event
+    ///
+    /// # Returns
+    /// * `Option<String>` - Failure reason, None if nothing has failed so far
+    pub fn failure(&self) -> Option<String> {
+        let root: &str = self
+            .transactions
+            .first()
+            .map(|traced| traced.transaction_hash.as_str())
+            .unwrap_or_default();
+        self.transactions.iter().find_map(|traced| {
+            let executed: &ExecutedTransaction = traced.executed.as_ref()?;
+            if executed.succeeded {
+                return None;
+            }
+            let reason: &str = executed.failure.as_deref().unwrap_or("no reason was reported");
+            Some(match traced.depth {
+                0 => format!(
+                    "Transaction {} failed on {}: {}",
+                    executed.transaction_hash, executed.shard, reason
+                ),
+                _ => format!(
+                    "Event {} spawned by transaction {} failed on {}: {}",
+                    executed.transaction_hash, root, executed.shard, reason
+                ),
+            })
+        })
+    }
+
+    /// Fails with the reason of the first failed transaction or event
+    ///
+    /// # Returns
+    /// * `Result<()>` - Ok if nothing in the trace failed
+    pub fn ensure_succeeded(&self) -> Result<(), Box<dyn std::error::Error>> {
+        match self.failure() {
+            Some(failure) => Err(failure.into()),
+            None => Ok(()),
+        }
+    }
+
+    /// Renders the trace as an indented tree, one line per transaction
+    ///
+    /// # Returns
+    /// * `String` - Printable trace
+    pub fn render(&self) -> String {
+        let mut lines: Vec<String> = Vec::new();
+        for traced in self.transactions.iter() {
+            let indent: String = "  ".repeat(traced.depth);
+            let line: String = match traced.executed.as_ref() {
+                None => format!(
+                    "⏳ {} pending on {}",
+                    traced.transaction_hash,
+                    traced.shard.as_deref().unwrap_or("an unknown shard")
+                ),
+                Some(executed) => {
+                    let status: String = match (executed.succeeded, executed.finalized) {
+                        (true, true) => "✅".to_string(),
+                        (true, false) => "⏳ executed, not yet finalized,".to_string(),
+                        (false, _) => "❌".to_string(),
+                    };
+                    let mut line: String = format!(
+                        "{} {} on {} in block {}",
+                        status,
+                        executed.transaction_hash,
+                        executed.shard,
+                        executed.block.as_deref().unwrap_or("unknown")
+                    );
+                    if let Some(failure) = executed.failure.as_ref() {
+                        line.push_str(&format!(": {}", failure));
+                    }
+                    line
+                }
+            };
+            lines.push(format!("{}{}", indent, line));
+        }
+        lines.join("\n")
+    }
+}
+
+impl<'a> TransactionTracker<'a> {
+    /// Creates a tracker
+    ///
+    /// # Arguments
+    /// * `client` - Client of the transaction's network
+    /// * `timeout_ms` - Milliseconds to wait for the whole trace before giving up
+    ///
+    /// # Returns
+    /// * `TransactionTracker` - New tracker
+    pub fn new(client: &'a TransactionClient, timeout_ms: i64) -> Self {
+        Self { client, timeout_ms }
+    }
+
+    /// Waits until the transaction and every event it spawns are executed and finalized
+    ///
+    /// A failed transaction or event does not end the wait early for its siblings, check
+    /// the result with `ensure_succeeded`.
+    ///
+    /// # Arguments
+    /// * `sent` - Transaction to track
+    ///
+    /// # Returns
+    /// * `Result<TransactionTrace>` - Complete trace, Error if it is not final within the timeout
+    pub async fn wait(&self, sent: &SentTransaction) -> Result<TransactionTrace, Box<dyn std::error::Error>> {
+        self.trace(sent, Some(now_millis() + self.timeout_ms)).await
+    }
+
+    /// Looks up the current status of the transaction and its events without waiting
+    ///
+    /// # Arguments
+    /// * `sent` - Transaction to look up
+    ///
+    /// # Returns
+    /// * `Result<TransactionTrace>` - Trace, with pending transactions that have not been executed
+    pub async fn status(&self, sent: &SentTransaction) -> Result<TransactionTrace, Box<dyn std::error::Error>> {
+        self.trace(sent, None).await
+    }
+
+    async fn trace(
+        &self,
+        sent: &SentTransaction,
+        deadline: Option<i64>,
+    ) -> Result<TransactionTrace, Box<dyn std::error::Error>> {
+        let root: &str = &sent.transaction_hash;
+        let mut transactions: Vec<TracedTransaction> = Vec::new();
+        // depth first, so events are listed directly below the transaction that spawned them
+        let mut pending: Vec<(usize, SentTransaction)> = vec![(0, sent.clone())];
+        while let Some((depth, next)) = pending.pop() {
+            let executed: Option<ExecutedTransaction> = loop {
+                let executed: Option<ExecutedTransaction> = self
+                    .client
+                    .get_executed_transaction(&next.transaction_hash, next.destination_shard.as_deref())
+                    .await?;
+                let is_final: bool = executed.as_ref().map(|executed| executed.finalized).unwrap_or(false);
+                let deadline: i64 = match deadline {
+                    Some(deadline) if !is_final => deadline,
+                    _ => break executed,
+                };
+                if now_millis() >= deadline {
+                    let what: String = match depth {
+                        0 => format!("Transaction {}", root),
+                        _ => format!("Event {} spawned by transaction {}", next.transaction_hash, root),
+                    };
+                    let state: &str = match executed {
+                        Some(_) => "finalized",
+                        None => "executed",
+                    };
+                    return Err(format!(
+                        "{} was not {} on {} within {} seconds",
+                        what,
+                        state,
+                        next.destination_shard.as_deref().unwrap_or("any shard"),
+                        self.timeout_ms / 1000
+                    )
+                    .into());
+                }
+                tokio::time::sleep(Duration::from_millis(TRANSACTION_POLL_INTERVAL_MS)).await;
+            };
+            if let Some(executed) = executed.as_ref() {
+                for event in executed.events.iter().rev() {
+                    pending.push((depth + 1, event.clone()));
+                }
+            }
+            transactions.push(TracedTransaction {
+                depth,
+                transaction_hash: next.transaction_hash.clone(),
+                shard: executed
+                    .as_ref()
+                    .map(|executed| executed.shard.clone())
+                    .or(next.destination_shard),
+                executed,
+            });
+        }
+        Ok(TransactionTrace { transactions })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::transaction::tests::start_mock_node;
+
+    fn sent(hash: &str, shard: &str) -> SentTransaction {
+        SentTransaction {
+            transaction_hash: hash.repeat(32),
+            destination_shard: Some(shard.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_transaction_hash_in_output() {
+        let output = format!("Minting gas for 00d277aa1bf5702ab9fc690b04bd68b5a981095530\nSent transaction: {}\n", "AB".repeat(32));
+        assert_eq!(transaction_hash_in_output(&output), Some("ab".repeat(32)));
+        assert_eq!(transaction_hash_in_output("Minted gas"), None);
+    }
+
+    #[tokio::test]
+    async fn test_wait_follows_events_across_shards() {
+        let (endpoint, handle) = start_mock_node(vec![
+            format!(
+                r#"{{"executionStatus": {{"blockId": "b0", "success": true, "finalized": true,
+                    "events": [{{"identifier": "{}", "destinationShardId": "Shard2"}}]}}}}"#,
+                "bb".repeat(32)
+            ),
+            r#"{"executionStatus": {"blockId": "b1", "success": true, "finalized": false}}"#.to_string(),
+            r#"{"executionStatus": {"blockId": "b1", "success": false, "finalized": true,
+                "failure": {"errorMessage": "counter overflow"}}}"#
+                .to_string(),
+        ]);
+        let client = TransactionClient::new(&endpoint, "test chain");
+        let trace = TransactionTracker::new(&client, 10_000)
+            .wait(&sent("aa", "Shard1"))
+            .await
+            .unwrap();
+
+        assert_eq!(trace.transactions.len(), 2);
+        assert_eq!(trace.transactions[1].depth, 1);
+        assert_eq!(trace.transactions[1].shard.as_deref(), Some("Shard2"));
+        assert!(trace.is_final());
+        assert_eq!(
+            trace.failure().unwrap(),
+            format!(
+                "Event {} spawned by transaction {} failed on Shard2: counter overflow",
+                "bb".repeat(32),
+                "aa".repeat(32)
+            )
+        );
+        assert!(trace.ensure_succeeded().is_err());
+        assert!(trace.render().contains("\n  ❌"));
+
+        let requests = handle.join().unwrap();
+        assert!(requests[0].starts_with(&format!("GET /chain/shards/Shard1/transactions/{}", "aa".repeat(32))));
+        assert!(requests[1].starts_with(&format!("GET /chain/shards/Shard2/transactions/{}", "bb".repeat(32))));
+    }
+
+    #[tokio::test]
+    async fn test_wait_times_out() {
+        let (endpoint, _handle) = start_mock_node(vec![
+            r#"{"executionSucceeded": true, "finalized": false}"#.to_string(),
+        ]);
+        let client = TransactionClient::new(&endpoint, "test chain");
+        let error = TransactionTracker::new(&client, 0)
+            .wait(&sent("aa", "Shard0"))
+            .await
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            error,
+            format!("Transaction {} was not finalized on Shard0 within 0 seconds", "aa".repeat(32))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_status_does_not_wait() {
+        let (endpoint, _handle) = start_mock_node(vec![
+            r#"{"block": "ab", "executionSucceeded": false, "failureCause": {"errorMessage": "out of gas"}}"#
+                .to_string(),
+        ]);
+        let client = TransactionClient::new(&endpoint, "test chain");
+        let trace = TransactionTracker::new(&client, 0)
+            .status(&sent("cd", "Shard1"))
+            .await
+            .unwrap();
+        let executed = trace.transactions[0].executed.as_ref().unwrap();
+        assert_eq!(executed.block.as_deref(), Some("ab"));
+        assert!(executed.finalized);
+        assert_eq!(
+            trace.failure().unwrap(),
+            format!("Transaction {} failed on Shard1: out of gas", "cd".repeat(32))
+        );
+    }
+}
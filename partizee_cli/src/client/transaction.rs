@@ -0,0 +1,1089 @@
+use crate::client::http_client::HttpClient;
+use crate::client::rpc::{address_to_bytes, RpcWriter};
+use crate::utils::artifact::{ContractArtifact, ContractKind};
+use crate::utils::constants::{
+    PUB_DEPLOY_ADDRESS, SHARDS, TRANSACTION_VALIDITY_MS, ZK_DEPLOY_ADDRESS,
+};
+use crate::utils::cryptography::address::{address_from_secret_key, ADDRESS_LENGTH};
+use crate::utils::state::AvlTrees;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use k256::ecdsa::SigningKey;
+use k256::SecretKey;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Invocation byte of the public deploy contract's deploy action
+pub const DEPLOY_CONTRACT_INVOCATION: u8 = 0x04;
+/// Invocation byte of the public deploy contract's upgrade action
+pub const UPGRADE_CONTRACT_INVOCATION: u8 = 0x05;
+/// Binder used for public WASM contracts
+pub const PUB_BINDER_ID: i32 = 11;
+/// Invocation byte of the zero-knowledge deploy contract's deploy action
+pub const ZK_DEPLOY_CONTRACT_INVOCATION: u8 = 0x02;
+/// MPC tokens, in the smallest unit, the ZK nodes allocated to a contract must have staked
+pub const ZK_REQUIRED_STAKES: i64 = 20_000_000;
+/// Address type prefix of public contracts
+pub const PUBLIC_CONTRACT_PREFIX: u8 = 0x02;
+/// Address type prefix of zero-knowledge contracts
+pub const ZK_CONTRACT_PREFIX: u8 = 0x03;
+/// Invocation byte routing a transaction to a zero-knowledge contract's public actions
+pub const ZK_OPEN_INVOCATION: u8 = 0x09;
+/// Length of a serialized recoverable signature
+pub const SIGNATURE_LENGTH: usize = 65;
+
+/// Unsigned Partisia transaction
+///
+/// # Fields
+/// * `nonce` - Account nonce of the sender
+/// * `valid_to_time` - Unix time in milliseconds after which the transaction is rejected
+/// * `gas_cost` - Gas the sender is willing to pay
+/// * `address` - Contract the transaction interacts with
+/// * `rpc` - Serialized payload sent to the contract
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transaction {
+    pub nonce: i64,
+    pub valid_to_time: i64,
+    pub gas_cost: i64,
+    pub address: [u8; ADDRESS_LENGTH],
+    pub rpc: Vec<u8>,
+}
+
+/// Transaction together with the sender's recoverable signature
+///
+/// # Fields
+/// * `signature` - Recovery id followed by r and s
+/// * `transaction` - Signed transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedTransaction {
+    pub signature: [u8; SIGNATURE_LENGTH],
+    pub transaction: Transaction,
+}
+
+/// Pointer to a transaction, as returned when submitting it or spawned as an event
+///
+/// # Fields
+/// * `transaction_hash` - Hex encoded identifier of the transaction
+/// * `destination_shard` - Shard the transaction was routed to, if reported by the node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SentTransaction {
+    pub transaction_hash: String,
+    pub destination_shard: Option<String>,
+}
+
+/// Result of a contract deployment
+///
+/// # Fields
+/// * `transaction_hash` - Hex encoded identifier of the deploy transaction
+/// * `contract_address` - Address the contract is deployed at
+/// * `destination_shard` - Shard the deploy transaction was routed to, if reported by the node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractDeployment {
+    pub transaction_hash: String,
+    pub contract_address: String,
+    pub destination_shard: Option<String>,
+}
+
+/// Transaction as executed by a shard
+///
+/// # Fields
+/// * `transaction_hash` - Hex encoded identifier of the transaction
+/// * `shard` - Shard the transaction was executed on
+/// * `block` - Hash of the block the transaction was included in
+/// * `succeeded` - Whether the execution succeeded
+/// * `finalized` - Whether the block the transaction is in has been finalized
+/// * `failure` - Error message of a failed execution
+/// * `events` - Events the transaction spawned, possibly on other shards
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutedTransaction {
+    pub transaction_hash: String,
+    pub shard: String,
+    pub block: Option<String>,
+    pub succeeded: bool,
+    pub finalized: bool,
+    pub failure: Option<String>,
+    pub events: Vec<SentTransaction>,
+}
+
+/// Builds, signs and submits transactions against a single node
+///
+/// # Fields
+/// * `http` - Client used for the REST calls
+/// * `endpoint` - Base url of the node
+/// * `chain_id` - Chain id the signatures are bound to
+pub struct TransactionClient {
+    http: HttpClient,
+    endpoint: String,
+    chain_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AccountResponse {
+    nonce: i64,
+}
+
+#[derive(Debug, Serialize)]
+struct SubmitTransactionRequest {
+    payload: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SubmitTransactionResponse {
+    transaction_pointer: Option<TransactionPointer>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TransactionPointer {
+    identifier: String,
+    destination_shard_id: Option<String>,
+}
+
+impl ExecutedTransaction {
+    /// Reads a transaction returned by `/chain/shards/<shard>/transactions/<hash>`
+    ///
+    /// # Arguments
+    /// * `transaction_hash` - Hex encoded transaction identifier
+    /// * `shard` - Shard the transaction was fetched from
+    /// * `executed` - Transaction document
+    ///
+    /// # Returns
+    /// * `ExecutedTransaction` - Execution outcome
+    pub fn from_json(transaction_hash: &str, shard: &str, executed: &Value) -> Self {
+        // newer nodes nest the outcome in `executionStatus`
+        let status: &Value = executed.get("executionStatus").unwrap_or(executed);
+        let events: Vec<SentTransaction> = status
+            .get("events")
+            .and_then(|events| events.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|event| {
+                Some(SentTransaction {
+                    transaction_hash: event.get("identifier")?.as_str()?.to_string(),
+                    destination_shard: event
+                        .get("destinationShardId")
+                        .or_else(|| event.get("destinationShard"))
+                        .and_then(|shard| shard.as_str())
+                        .map(|shard| shard.to_string()),
+                })
+            })
+            .collect();
+        Self {
+            transaction_hash: transaction_hash.to_string(),
+            shard: shard.to_string(),
+            block: status
+                .get("blockId")
+                .or_else(|| executed.get("block"))
+                .and_then(|block| block.as_str())
+                .map(|block| block.to_string()),
+            succeeded: status
+                .get("success")
+                .or_else(|| executed.get("executionSucceeded"))
+                .and_then(|succeeded| succeeded.as_bool())
+                .unwrap_or(false),
+            // nodes that do not report finality only serve finalized blocks
+            finalized: status
+                .get("finalized")
+                .and_then(|finalized| finalized.as_bool())
+                .unwrap_or(true),
+            failure: status
+                .pointer("/failure/errorMessage")
+                .or_else(|| executed.pointer("/failureCause/errorMessage"))
+                .and_then(|message| message.as_str())
+                .map(|message| message.to_string()),
+            events,
+        }
+    }
+}
+
+impl Transaction {
+    /// Serializes the transaction in the format the signature is computed over
+    ///
+    /// # Returns
+    /// * `Vec<u8>` - Serialized transaction
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut writer = RpcWriter::new();
+        writer
+            .write_i64(self.nonce)
+            .write_i64(self.valid_to_time)
+            .write_i64(self.gas_cost)
+            .write_bytes(&self.address)
+            .write_dynamic_bytes(&self.rpc);
+        writer.into_bytes()
+    }
+
+    /// Signs the transaction for a specific chain
+    ///
+    /// # Arguments
+    /// * `secret_key` - Key of the sender
+    /// * `chain_id` - Chain the transaction is valid on
+    ///
+    /// # Returns
+    /// * `Result<SignedTransaction>` - Signed transaction
+    pub fn sign(
+        &self,
+        secret_key: &SecretKey,
+        chain_id: &str,
+    ) -> Result<SignedTransaction, Box<dyn std::error::Error>> {
+        let mut writer = RpcWriter::new();
+        writer.write_bytes(&self.serialize()).write_string(chain_id);
+        let message_hash = Sha256::digest(writer.into_bytes());
+
+        let signing_key: SigningKey = SigningKey::from(secret_key);
+        let (signature, recovery_id) = signing_key.sign_prehash_recoverable(&message_hash)?;
+
+        let mut signature_bytes = [0u8; SIGNATURE_LENGTH];
+        signature_bytes[0] = recovery_id.to_byte();
+        signature_bytes[1..].copy_from_slice(&signature.to_bytes());
+        Ok(SignedTransaction {
+            signature: signature_bytes,
+            transaction: self.clone(),
+        })
+    }
+}
+
+impl SignedTransaction {
+    /// Serializes the signed transaction as sent to the node
+    ///
+    /// # Returns
+    /// * `Vec<u8>` - Serialized signed transaction
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = self.signature.to_vec();
+        bytes.extend(self.transaction.serialize());
+        bytes
+    }
+
+    /// Computes the identifier the chain assigns to the transaction
+    ///
+    /// # Arguments
+    /// * `chain_id` - Chain the transaction was signed for
+    ///
+    /// # Returns
+    /// * `[u8; 32]` - Transaction hash
+    pub fn identifier(&self, chain_id: &str) -> [u8; 32] {
+        let mut writer = RpcWriter::new();
+        writer.write_bytes(&self.serialize()).write_string(chain_id);
+        Sha256::digest(writer.into_bytes()).into()
+    }
+}
+
+/// Builds the RPC for deploying a public contract through the deploy contract
+///
+/// # Arguments
+/// * `artifact` - Contract code and ABI
+/// * `init_rpc` - Serialized call to the contract's init function
+///
+/// # Returns
+/// * `Vec<u8>` - Serialized deploy RPC
+pub fn deploy_contract_rpc(artifact: &ContractArtifact, init_rpc: &[u8]) -> Vec<u8> {
+    let mut writer = RpcWriter::new();
+    writer
+        .write_u8(DEPLOY_CONTRACT_INVOCATION)
+        .write_dynamic_bytes(&artifact.contract_bytes)
+        .write_dynamic_bytes(&artifact.abi_bytes)
+        .write_dynamic_bytes(init_rpc)
+        .write_i32(PUB_BINDER_ID);
+    writer.into_bytes()
+}
+
+/// Serializes the RPC upgrading a public contract to new code
+///
+/// # Arguments
+/// * `address` - Contract to upgrade
+/// * `artifact` - New contract code and ABI
+/// * `upgrade_rpc` - Serialized arguments of the new code's `#[upgrade]` function
+///
+/// # Returns
+/// * `Vec<u8>` - Serialized upgrade RPC
+pub fn upgrade_contract_rpc(
+    address: &[u8; ADDRESS_LENGTH],
+    artifact: &ContractArtifact,
+    upgrade_rpc: &[u8],
+) -> Vec<u8> {
+    let mut writer = RpcWriter::new();
+    writer
+        .write_u8(UPGRADE_CONTRACT_INVOCATION)
+        .write_bytes(address)
+        .write_dynamic_bytes(&artifact.contract_bytes)
+        .write_dynamic_bytes(&artifact.abi_bytes)
+        .write_dynamic_bytes(upgrade_rpc);
+    writer.into_bytes()
+}
+
+/// Builds the RPC for deploying a zero-knowledge contract through the ZK deploy contract
+///
+/// ZK nodes from any jurisdiction may be allocated to the contract.
+///
+/// # Arguments
+/// * `artifact` - Contract ZK WASM code and ABI
+/// * `init_rpc` - Serialized call to the contract's init function
+///
+/// # Returns
+/// * `Vec<u8>` - Serialized deploy RPC
+pub fn deploy_zk_contract_rpc(artifact: &ContractArtifact, init_rpc: &[u8]) -> Vec<u8> {
+    let mut writer = RpcWriter::new();
+    writer
+        .write_u8(ZK_DEPLOY_CONTRACT_INVOCATION)
+        .write_dynamic_bytes(&artifact.contract_bytes)
+        .write_dynamic_bytes(init_rpc)
+        .write_dynamic_bytes(&artifact.abi_bytes)
+        .write_i64(ZK_REQUIRED_STAKES)
+        // no restriction on the jurisdictions of the allocated nodes
+        .write_i32(0);
+    writer.into_bytes()
+}
+
+/// Deploy contract and RPC that deploy an artifact of either kind
+///
+/// # Arguments
+/// * `artifact` - Contract code and ABI
+/// * `init_rpc` - Serialized call to the contract's init function
+///
+/// # Returns
+/// * `Result<([u8; ADDRESS_LENGTH], Vec<u8>)>` - Address of the deploy contract and the RPC to send it
+pub fn deploy_rpc(
+    artifact: &ContractArtifact,
+    init_rpc: &[u8],
+) -> Result<([u8; ADDRESS_LENGTH], Vec<u8>), Box<dyn std::error::Error>> {
+    Ok(match artifact.kind {
+        ContractKind::Public => (
+            address_to_bytes(PUB_DEPLOY_ADDRESS)?,
+            deploy_contract_rpc(artifact, init_rpc),
+        ),
+        ContractKind::ZeroKnowledge => (
+            address_to_bytes(ZK_DEPLOY_ADDRESS)?,
+            deploy_zk_contract_rpc(artifact, init_rpc),
+        ),
+    })
+}
+
+/// Computes the address a contract is deployed at
+///
+/// # Arguments
+/// * `kind` - Kind of the deployed contract, which decides the address prefix
+/// * `transaction_hash` - Hash of the deploy transaction
+///
+/// # Returns
+/// * `String` - Hex encoded contract address
+pub fn contract_address(kind: ContractKind, transaction_hash: &[u8; 32]) -> String {
+    let mut address = [0u8; ADDRESS_LENGTH];
+    address[0] = match kind {
+        ContractKind::Public => PUBLIC_CONTRACT_PREFIX,
+        ContractKind::ZeroKnowledge => ZK_CONTRACT_PREFIX,
+    };
+    address[1..].copy_from_slice(&transaction_hash[32 - (ADDRESS_LENGTH - 1)..]);
+    hex::encode(address)
+}
+
+/// Shard an address is stored on
+///
+/// # Arguments
+/// * `address` - Raw address
+///
+/// # Returns
+/// * `&str` - Name of the shard
+pub fn shard_for_address(address: &[u8; ADDRESS_LENGTH]) -> &'static str {
+    let suffix: [u8; 4] = [address[17], address[18], address[19], address[20]];
+    let index: usize = i32::from_be_bytes(suffix).unsigned_abs() as usize % SHARDS.len();
+    SHARDS[index]
+}
+
+/// Extracts the serialized state and AVL trees from a contract document
+///
+/// Public contracts report their state under `state`, zero-knowledge contracts under
+/// `openState.openState`. Byte fields are base64, possibly wrapped in a `data` object.
+///
+/// # Arguments
+/// * `serialized_contract` - `serializedContract` of a contract document
+///
+/// # Returns
+/// * `Option<(Vec<u8>, AvlTrees)>` - State bytes and AVL tree entries
+pub fn contract_state_from_json(serialized_contract: &Value) -> Option<(Vec<u8>, AvlTrees)> {
+    let state: Vec<u8> = base64_bytes(serialized_contract)
+        .or_else(|| serialized_contract.get("state").and_then(base64_bytes))
+        .or_else(|| {
+            serialized_contract
+                .pointer("/openState/openState")
+                .and_then(base64_bytes)
+        })?;
+    let mut avl_trees: AvlTrees = AvlTrees::new();
+    let trees: Option<&Vec<Value>> = serialized_contract
+        .get("avlTrees")
+        .or_else(|| serialized_contract.pointer("/openState/avlTrees"))
+        .and_then(|trees| trees.as_array());
+    for tree in trees.into_iter().flatten() {
+        let (Some(tree_id), Some(entries)) = (
+            tree.get("key").and_then(|key| key.as_i64()),
+            tree.pointer("/value/avlTree").and_then(|entries| entries.as_array()),
+        ) else {
+            continue;
+        };
+        let entries: Vec<(Vec<u8>, Vec<u8>)> = entries
+            .iter()
+            .filter_map(|entry| {
+                Some((
+                    base64_bytes(entry.get("key")?)?,
+                    base64_bytes(entry.get("value")?)?,
+                ))
+            })
+            .collect();
+        avl_trees.insert(tree_id as i32, entries);
+    }
+    Some((state, avl_trees))
+}
+
+/// Extracts the code and ABI hashes of a deployed contract
+///
+/// The code hash is the `contractIdentifier` reported by the node, or computed from the
+/// base64 `code` for nodes that return the code itself. The ABI hash is computed from `abi`.
+///
+/// # Arguments
+/// * `contract` - Contract document returned by `/chain/contracts/<address>`
+///
+/// # Returns
+/// * `(Option<[u8; 32]>, Option<[u8; 32]>)` - Code hash and ABI hash, None if not reported
+pub fn deployed_code_hashes(contract: &Value) -> (Option<[u8; 32]>, Option<[u8; 32]>) {
+    let contract_hash: Option<[u8; 32]> = contract
+        .get("contractIdentifier")
+        .and_then(|identifier| identifier.as_str())
+        .and_then(|identifier| hex::decode(identifier).ok())
+        .and_then(|identifier| identifier.try_into().ok())
+        .or_else(|| {
+            let code: Vec<u8> = base64_bytes(contract.get("code")?)?;
+            Some(Sha256::digest(code).into())
+        });
+    let abi_hash: Option<[u8; 32]> = contract
+        .get("abi")
+        .and_then(base64_bytes)
+        .map(|abi| Sha256::digest(abi).into());
+    (contract_hash, abi_hash)
+}
+
+/// Reads base64 bytes given as a string or wrapped in `data` objects
+fn base64_bytes(value: &Value) -> Option<Vec<u8>> {
+    match value {
+        Value::String(text) => STANDARD.decode(text).ok(),
+        Value::Object(_) => base64_bytes(value.get("data")?),
+        _ => None,
+    }
+}
+
+/// Converts an account's coin balances to gas using the chain's conversion rates
+///
+/// # Arguments
+/// * `account` - Account returned by `/chain/accounts/<address>`
+/// * `global` - Account plugin state returned by `/chain/accountPlugin/global`
+///
+/// # Returns
+/// * `Option<i64>` - Gas the coins are worth, None if either document is malformed
+pub fn gas_from_coins(account: &Value, global: &Value) -> Option<i64> {
+    let balances: &Vec<Value> = account.pointer("/account/accountCoins")?.as_array()?;
+    let rates: &Vec<Value> = global.pointer("/coins/coins")?.as_array()?;
+    let mut gas: i128 = 0;
+    for (coin, rate) in balances.iter().zip(rates) {
+        let balance: i128 = json_integer(coin.get("balance")?)?;
+        let numerator: i128 = json_integer(rate.pointer("/conversionRate/numerator")?)?;
+        let denominator: i128 = json_integer(rate.pointer("/conversionRate/denominator")?)?;
+        if denominator != 0 {
+            gas += balance * numerator / denominator;
+        }
+    }
+    Some(gas.min(i64::MAX as i128) as i64)
+}
+
+/// Reads an integer the node encodes either as a JSON number or a string
+fn json_integer(value: &Value) -> Option<i128> {
+    match value {
+        Value::Number(number) => number.as_i64().map(|number| number as i128),
+        Value::String(text) => text.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Current unix time in milliseconds
+pub(crate) fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+impl TransactionClient {
+    /// Creates a client for a node
+    ///
+    /// # Arguments
+    /// * `endpoint` - Base url of the node
+    /// * `chain_id` - Chain id transactions are signed for
+    ///
+    /// # Returns
+    /// * `TransactionClient` - New client
+    pub fn new(endpoint: &str, chain_id: &str) -> Self {
+        Self {
+            http: HttpClient::default(),
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            chain_id: chain_id.to_string(),
+        }
+    }
+
+    /// Chain id the client signs for
+    pub fn chain_id(&self) -> &str {
+        &self.chain_id
+    }
+
+    /// Fetches the next nonce of an account
+    ///
+    /// # Arguments
+    /// * `address` - Hex encoded account address
+    ///
+    /// # Returns
+    /// * `Result<i64>` - Nonce to use for the next transaction
+    pub async fn get_nonce(&self, address: &str) -> Result<i64, Box<dyn std::error::Error>> {
+        let url: String = format!("{}/chain/accounts/{}", self.endpoint, address);
+        let account: Option<AccountResponse> = self.http.get_request(&url).await?;
+        account
+            .map(|account| account.nonce)
+            .ok_or_else(|| format!("Account {} was not found on {}", address, self.endpoint).into())
+    }
+
+    /// Fetches the gas an account can pay for, converted from its BYOC coin balances
+    ///
+    /// # Arguments
+    /// * `address` - Hex encoded account address
+    ///
+    /// # Returns
+    /// * `Result<i64>` - Gas the account's coins convert to
+    pub async fn get_gas_balance(&self, address: &str) -> Result<i64, Box<dyn std::error::Error>> {
+        let account_url: String = format!("{}/chain/accounts/{}", self.endpoint, address);
+        let account: Value = self
+            .http
+            .get_request(&account_url)
+            .await?
+            .ok_or_else(|| format!("Account {} was not found on {}", address, self.endpoint))?;
+        let global_url: String = format!("{}/chain/accountPlugin/global", self.endpoint);
+        let global: Value = self
+            .http
+            .get_request(&global_url)
+            .await?
+            .ok_or_else(|| format!("Coin conversion rates are not available on {}", self.endpoint))?;
+        gas_from_coins(&account, &global)
+            .ok_or_else(|| format!("Unexpected account format returned by {}", self.endpoint).into())
+    }
+
+    /// Submits a signed transaction
+    ///
+    /// # Arguments
+    /// * `signed` - Signed transaction
+    ///
+    /// # Returns
+    /// * `Result<SentTransaction>` - Hash and destination shard of the transaction
+    pub async fn send(
+        &self,
+        signed: &SignedTransaction,
+    ) -> Result<SentTransaction, Box<dyn std::error::Error>> {
+        let url: String = format!("{}/chain/transactions", self.endpoint);
+        let request = SubmitTransactionRequest {
+            payload: STANDARD.encode(signed.serialize()),
+        };
+        let response: Option<SubmitTransactionResponse> =
+            self.http.put_request(&url, &request).await?;
+        let response: SubmitTransactionResponse =
+            response.ok_or_else(|| format!("Node {} rejected the transaction", self.endpoint))?;
+
+        let transaction_hash: String = hex::encode(signed.identifier(&self.chain_id));
+        let destination_shard: Option<String> = match response.transaction_pointer {
+            Some(pointer) => {
+                if pointer.identifier != transaction_hash {
+                    return Err(format!(
+                        "Node reported transaction {} but {} was signed",
+                        pointer.identifier, transaction_hash
+                    )
+                    .into());
+                }
+                pointer.destination_shard_id
+            }
+            None => None,
+        };
+        Ok(SentTransaction {
+            transaction_hash,
+            destination_shard,
+        })
+    }
+
+    /// Fetches the chain description of the node
+    ///
+    /// # Returns
+    /// * `Result<Value>` - Chain as returned by `/chain`, including its `chainId`
+    pub async fn get_chain(&self) -> Result<Value, Box<dyn std::error::Error>> {
+        let url: String = format!("{}/chain", self.endpoint);
+        self.http
+            .get_request(&url)
+            .await?
+            .ok_or_else(|| format!("{} did not describe its chain", self.endpoint).into())
+    }
+
+    /// Fetches a deployed contract
+    ///
+    /// # Arguments
+    /// * `address` - Hex encoded contract address
+    ///
+    /// # Returns
+    /// * `Result<Value>` - Contract as returned by `/chain/contracts/<address>`
+    pub async fn get_contract(&self, address: &str) -> Result<Value, Box<dyn std::error::Error>> {
+        let url: String = format!("{}/chain/contracts/{}", self.endpoint, address);
+        self.http
+            .get_request(&url)
+            .await?
+            .ok_or_else(|| format!("Contract {} was not found on {}", address, self.endpoint).into())
+    }
+
+    /// Fetches the serialized state of a contract from its shard
+    ///
+    /// # Arguments
+    /// * `address` - Hex encoded contract address
+    /// * `block_time` - Read the state as of this block instead of the latest block
+    ///
+    /// # Returns
+    /// * `Result<(Vec<u8>, AvlTrees)>` - State bytes and AVL tree entries
+    pub async fn get_contract_state(
+        &self,
+        address: &str,
+        block_time: Option<i64>,
+    ) -> Result<(Vec<u8>, AvlTrees), Box<dyn std::error::Error>> {
+        let shard: &str = shard_for_address(&address_to_bytes(address)?);
+        let mut url: String = format!(
+            "{}/shards/{}/blockchain/contracts/{}?requireContractState=true",
+            self.endpoint, shard, address
+        );
+        if let Some(block_time) = block_time {
+            url.push_str(&format!("&blockTime={}", block_time));
+        }
+        let contract: Value = self.http.get_request(&url).await?.ok_or_else(|| match block_time {
+            Some(block_time) => format!("Contract {} was not found on {} at block {}", address, shard, block_time),
+            None => format!("Contract {} was not found on {}", address, shard),
+        })?;
+        contract
+            .get("serializedContract")
+            .and_then(contract_state_from_json)
+            .ok_or_else(|| format!("{} did not report the state of {}", self.endpoint, address).into())
+    }
+
+    /// Fetches the hash of the binder a deployed contract runs on
+    ///
+    /// # Arguments
+    /// * `address` - Hex encoded contract address
+    ///
+    /// # Returns
+    /// * `Result<[u8; 32]>` - Binder hash
+    pub async fn get_binder_hash(&self, address: &str) -> Result<[u8; 32], Box<dyn std::error::Error>> {
+        let contract: Value = self.get_contract(address).await?;
+        let jar_hash: &str = contract
+            .get("jarHash")
+            .and_then(|jar_hash| jar_hash.as_str())
+            .ok_or_else(|| format!("{} did not report the binder of {}", self.endpoint, address))?;
+        hex::decode(jar_hash)?
+            .try_into()
+            .map_err(|_| format!("Binder hash of {} must be 32 bytes", address).into())
+    }
+
+    /// Fetches the binary ABI a contract was deployed with
+    ///
+    /// # Arguments
+    /// * `address` - Hex encoded contract address
+    ///
+    /// # Returns
+    /// * `Result<Vec<u8>>` - ABI bytes
+    pub async fn get_contract_abi(&self, address: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let contract: Value = self.get_contract(address).await?;
+        let abi: &str = contract
+            .get("abi")
+            .and_then(|abi| abi.as_str())
+            .ok_or_else(|| format!("{} did not report the ABI of {}", self.endpoint, address))?;
+        Ok(STANDARD.decode(abi)?)
+    }
+
+    /// Fetches a transaction once it has been executed
+    ///
+    /// # Arguments
+    /// * `transaction_hash` - Hex encoded transaction identifier
+    /// * `shard` - Shard the transaction was routed to, every shard is searched if unknown
+    ///
+    /// # Returns
+    /// * `Result<Option<ExecutedTransaction>>` - Executed transaction, None if not yet included
+    pub async fn get_executed_transaction(
+        &self,
+        transaction_hash: &str,
+        shard: Option<&str>,
+    ) -> Result<Option<ExecutedTransaction>, Box<dyn std::error::Error>> {
+        let shards: Vec<&str> = match shard {
+            Some(shard) => vec![shard],
+            None => SHARDS.to_vec(),
+        };
+        for shard in shards {
+            let url: String = format!(
+                "{}/chain/shards/{}/transactions/{}",
+                self.endpoint, shard, transaction_hash
+            );
+            let executed: Option<Value> = self.http.get_request(&url).await?;
+            if let Some(executed) = executed {
+                return Ok(Some(ExecutedTransaction::from_json(transaction_hash, shard, &executed)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Builds, signs and submits a transaction from the key's account
+    ///
+    /// # Arguments
+    /// * `secret_key` - Key of the sender
+    /// * `address` - Contract to interact with
+    /// * `rpc` - Payload for the contract
+    /// * `gas_cost` - Gas to pay for the transaction
+    ///
+    /// # Returns
+    /// * `Result<SentTransaction>` - Hash and destination shard of the transaction
+    pub async fn sign_and_send(
+        &self,
+        secret_key: &SecretKey,
+        address: [u8; ADDRESS_LENGTH],
+        rpc: Vec<u8>,
+        gas_cost: i64,
+    ) -> Result<SentTransaction, Box<dyn std::error::Error>> {
+        let mut sent: Vec<SentTransaction> = self
+            .sign_and_send_all(secret_key, vec![(address, rpc, gas_cost)])
+            .await?;
+        sent.pop().ok_or_else(|| "No transaction was sent".into())
+    }
+
+    /// Signs and submits several transactions from the key's account with consecutive nonces
+    ///
+    /// The nonce is fetched once, so later transactions do not wait for earlier ones to be
+    /// included before they can be signed.
+    ///
+    /// # Arguments
+    /// * `secret_key` - Key of the sender
+    /// * `calls` - Contract, payload and gas of each transaction, in sending order
+    ///
+    /// # Returns
+    /// * `Result<Vec<SentTransaction>>` - Hash and destination shard of each transaction
+    pub async fn sign_and_send_all(
+        &self,
+        secret_key: &SecretKey,
+        calls: Vec<([u8; ADDRESS_LENGTH], Vec<u8>, i64)>,
+    ) -> Result<Vec<SentTransaction>, Box<dyn std::error::Error>> {
+        let sender: String = hex::encode(address_from_secret_key(secret_key));
+        let first_nonce: i64 = self.get_nonce(&sender).await?;
+        let valid_to_time: i64 = now_millis() + TRANSACTION_VALIDITY_MS;
+        let mut sent: Vec<SentTransaction> = Vec::new();
+        for (nonce, (address, rpc, gas_cost)) in (first_nonce..).zip(calls) {
+            let transaction = Transaction {
+                nonce,
+                valid_to_time,
+                gas_cost,
+                address,
+                rpc,
+            };
+            let signed: SignedTransaction = transaction.sign(secret_key, &self.chain_id)?;
+            sent.push(self.send(&signed).await?);
+        }
+        Ok(sent)
+    }
+
+    /// Deploys a contract artifact
+    ///
+    /// # Arguments
+    /// * `secret_key` - Key of the deployer
+    /// * `artifact` - Contract code and ABI
+    /// * `init_rpc` - Serialized call to the contract's init function
+    /// * `gas_cost` - Gas to pay for the deployment
+    ///
+    /// # Returns
+    /// * `Result<ContractDeployment>` - Transaction hash and contract address
+    pub async fn deploy_contract(
+        &self,
+        secret_key: &SecretKey,
+        artifact: &ContractArtifact,
+        init_rpc: &[u8],
+        gas_cost: i64,
+    ) -> Result<ContractDeployment, Box<dyn std::error::Error>> {
+        let (deploy_address, rpc): ([u8; ADDRESS_LENGTH], Vec<u8>) = deploy_rpc(artifact, init_rpc)?;
+        let sent: SentTransaction = self
+            .sign_and_send(secret_key, deploy_address, rpc, gas_cost)
+            .await?;
+        let hash_bytes: [u8; 32] = hex::decode(&sent.transaction_hash)?
+            .try_into()
+            .map_err(|_| "Transaction hash must be 32 bytes")?;
+        Ok(ContractDeployment {
+            contract_address: contract_address(artifact.kind, &hash_bytes),
+            transaction_hash: sent.transaction_hash,
+            destination_shard: sent.destination_shard,
+        })
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::utils::cryptography::address::secret_key_from_hex;
+    use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    const TEST_PRIVATE_KEY: &str =
+        "9c1a15a50a4f978f0085bd747b9da360cc0fbf5f1d0744e040873aeba46b37b0";
+
+    /// Starts a mock node answering each request in order with the given JSON bodies
+    ///
+    /// # Returns
+    /// * `(String, thread::JoinHandle<Vec<String>>)` - Endpoint and handle yielding the raw requests
+    pub fn start_mock_node(responses: Vec<String>) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut requests: Vec<String> = Vec::new();
+            for body in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request: Vec<u8> = Vec::new();
+                let mut buffer = [0u8; 4096];
+                loop {
+                    let read = stream.read(&mut buffer).unwrap();
+                    request.extend_from_slice(&buffer[..read]);
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    if let Some(header_end) = text.find("\r\n\r\n") {
+                        let content_length: usize = text[..header_end]
+                            .lines()
+                            .find_map(|line| {
+                                line.to_lowercase()
+                                    .strip_prefix("content-length:")
+                                    .map(|value| value.trim().parse().unwrap_or(0))
+                            })
+                            .unwrap_or(0);
+                        if request.len() >= header_end + 4 + content_length {
+                            break;
+                        }
+                    }
+                    if read == 0 {
+                        break;
+                    }
+                }
+                requests.push(String::from_utf8_lossy(&request).to_string());
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+        (endpoint, handle)
+    }
+
+    fn test_transaction() -> Transaction {
+        Transaction {
+            nonce: 1,
+            valid_to_time: 2,
+            gas_cost: 3,
+            address: address_to_bytes(PUB_DEPLOY_ADDRESS).unwrap(),
+            rpc: vec![0xab],
+        }
+    }
+
+    #[test]
+    fn test_serialize_transaction() {
+        assert_eq!(
+            hex::encode(test_transaction().serialize()),
+            format!(
+                "000000000000000100000000000000020000000000000003{}00000001ab",
+                PUB_DEPLOY_ADDRESS
+            )
+        );
+    }
+
+    #[test]
+    fn test_signature_recovers_sender() {
+        let secret_key = secret_key_from_hex(TEST_PRIVATE_KEY).unwrap();
+        let signed = test_transaction().sign(&secret_key, "test chain").unwrap();
+        assert_eq!(signed.serialize().len(), SIGNATURE_LENGTH + 50);
+
+        let mut writer = RpcWriter::new();
+        writer
+            .write_bytes(&signed.transaction.serialize())
+            .write_string("test chain");
+        let message_hash = Sha256::digest(writer.into_bytes());
+        let signature = Signature::from_slice(&signed.signature[1..]).unwrap();
+        let recovery_id = RecoveryId::from_byte(signed.signature[0]).unwrap();
+        let recovered =
+            VerifyingKey::recover_from_prehash(&message_hash, &signature, recovery_id).unwrap();
+        assert_eq!(&recovered, SigningKey::from(&secret_key).verifying_key());
+    }
+
+    #[test]
+    fn test_upgrade_contract_rpc() {
+        let artifact = ContractArtifact {
+            kind: ContractKind::Public,
+            contract_bytes: vec![1, 2],
+            abi_bytes: vec![3],
+        };
+        let address = address_to_bytes("02aa00000000000000000000000000000000000000").unwrap();
+        let rpc = upgrade_contract_rpc(&address, &artifact, &[]);
+        assert_eq!(
+            hex::encode(rpc),
+            "0502aa00000000000000000000000000000000000000000000020102000000010300000000"
+        );
+    }
+
+    #[test]
+    fn test_shard_for_address() {
+        let mut address = [0u8; ADDRESS_LENGTH];
+        assert_eq!(shard_for_address(&address), "Shard0");
+        address[20] = 4;
+        assert_eq!(shard_for_address(&address), "Shard1");
+        // the suffix is read as a signed integer and its absolute value used
+        address[17..].copy_from_slice(&(-5i32).to_be_bytes());
+        assert_eq!(shard_for_address(&address), "Shard2");
+    }
+
+    #[test]
+    fn test_contract_state_from_json() {
+        let serialized_contract: Value = serde_json::json!({
+            "state": {"data": STANDARD.encode([7, 0, 0, 0])},
+            "avlTrees": [{"key": 0, "value": {"avlTree": [
+                {"key": {"data": {"data": STANDARD.encode([1])}}, "value": {"data": STANDARD.encode([2])}}
+            ]}}]
+        });
+        let (state, avl_trees) = contract_state_from_json(&serialized_contract).unwrap();
+        assert_eq!(state, vec![7, 0, 0, 0]);
+        assert_eq!(avl_trees[&0], vec![(vec![1], vec![2])]);
+        assert!(contract_state_from_json(&serde_json::json!({})).is_none());
+    }
+
+    #[test]
+    fn test_deployed_code_hashes() {
+        let contract: Value = serde_json::json!({
+            "contractIdentifier": "11".repeat(32),
+            "abi": STANDARD.encode(b"abi"),
+        });
+        let (contract_hash, abi_hash) = deployed_code_hashes(&contract);
+        assert_eq!(contract_hash, Some([0x11; 32]));
+        assert_eq!(abi_hash, Some(Sha256::digest(b"abi").into()));
+
+        let (contract_hash, abi_hash) =
+            deployed_code_hashes(&serde_json::json!({"code": STANDARD.encode(b"wasm")}));
+        assert_eq!(contract_hash, Some(Sha256::digest(b"wasm").into()));
+        assert!(abi_hash.is_none());
+    }
+
+    #[test]
+    fn test_public_contract_address() {
+        let address = contract_address(ContractKind::Public, &[0xaa; 32]);
+        assert_eq!(address, format!("02{}", "aa".repeat(20)));
+        let zk_address = contract_address(ContractKind::ZeroKnowledge, &[0xaa; 32]);
+        assert_eq!(zk_address, format!("03{}", "aa".repeat(20)));
+    }
+
+    #[test]
+    fn test_deploy_rpc_by_kind() {
+        let artifact = ContractArtifact {
+            kind: ContractKind::ZeroKnowledge,
+            contract_bytes: vec![1, 2],
+            abi_bytes: vec![3],
+        };
+        let (address, rpc) = deploy_rpc(&artifact, &[0xff]).unwrap();
+        assert_eq!(hex::encode(address), ZK_DEPLOY_ADDRESS);
+        assert_eq!(
+            hex::encode(rpc),
+            format!("0200000002010200000001ff0000000103{:016x}00000000", ZK_REQUIRED_STAKES)
+        );
+
+        let public = ContractArtifact {
+            kind: ContractKind::Public,
+            ..artifact
+        };
+        let (address, rpc) = deploy_rpc(&public, &[0xff]).unwrap();
+        assert_eq!(hex::encode(address), PUB_DEPLOY_ADDRESS);
+        assert_eq!(rpc, deploy_contract_rpc(&public, &[0xff]));
+    }
+
+    #[tokio::test]
+    async fn test_gas_balance() {
+        let (endpoint, handle) = start_mock_node(vec![
+            r#"{"nonce": 1, "account": {"accountCoins": [{"balance": "1000"}, {"balance": 4}]}}"#
+                .to_string(),
+            r#"{"coins": {"coins": [
+                {"symbol": "TEST_COIN", "conversionRate": {"numerator": "1", "denominator": "1"}},
+                {"symbol": "ETH", "conversionRate": {"numerator": 5, "denominator": 2}}
+            ]}}"#
+                .to_string(),
+        ]);
+        let client = TransactionClient::new(&endpoint, "test chain");
+        let gas = client
+            .get_gas_balance("00d277aa1bf5702ab9fc690b04bd68b5a981095530")
+            .await
+            .unwrap();
+        assert_eq!(gas, 1010);
+        let requests = handle.join().unwrap();
+        assert!(requests[1].starts_with("GET /chain/accountPlugin/global"));
+    }
+
+    #[tokio::test]
+    async fn test_deploy_against_mock_node() {
+        let secret_key = secret_key_from_hex(TEST_PRIVATE_KEY).unwrap();
+        let artifact = ContractArtifact {
+            kind: ContractKind::Public,
+            contract_bytes: vec![1, 2, 3],
+            abi_bytes: vec![4, 5],
+        };
+        // the identifier depends on the valid-to time, so the node omits the pointer
+        let (endpoint, handle) = start_mock_node(vec![
+            r#"{"nonce": 7}"#.to_string(),
+            r#"{}"#.to_string(),
+        ]);
+        let client = TransactionClient::new(&endpoint, "test chain");
+        let deployment = client
+            .deploy_contract(&secret_key, &artifact, &[0x01], 1000)
+            .await
+            .unwrap();
+
+        assert_eq!(deployment.transaction_hash.len(), 64);
+        assert!(deployment.contract_address.starts_with("02"));
+        assert_eq!(
+            deployment.contract_address[2..],
+            deployment.transaction_hash[24..]
+        );
+        let requests = handle.join().unwrap();
+        assert!(requests[0].starts_with("GET /chain/accounts/00d277aa1bf5702ab9fc690b04bd68b5a981095530"));
+        assert!(requests[1].starts_with("PUT /chain/transactions"));
+        assert!(requests[1].contains("\"payload\""));
+    }
+
+    #[tokio::test]
+    async fn test_sign_and_send_all_uses_consecutive_nonces() {
+        let secret_key = secret_key_from_hex(TEST_PRIVATE_KEY).unwrap();
+        let (endpoint, handle) = start_mock_node(vec![
+            r#"{"nonce": 7}"#.to_string(),
+            r#"{}"#.to_string(),
+            r#"{}"#.to_string(),
+        ]);
+        let client = TransactionClient::new(&endpoint, "test chain");
+        let address = address_to_bytes(PUB_DEPLOY_ADDRESS).unwrap();
+        let sent = client
+            .sign_and_send_all(&secret_key, vec![(address, vec![1], 10), (address, vec![2], 10)])
+            .await
+            .unwrap();
+        assert_eq!(sent.len(), 2);
+
+        let requests = handle.join().unwrap();
+        let nonces: Vec<i64> = requests[1..]
+            .iter()
+            .map(|request| {
+                let body: Value = serde_json::from_str(request.split("\r\n\r\n").nth(1).unwrap()).unwrap();
+                let payload = STANDARD.decode(body["payload"].as_str().unwrap()).unwrap();
+                i64::from_be_bytes(payload[SIGNATURE_LENGTH..SIGNATURE_LENGTH + 8].try_into().unwrap())
+            })
+            .collect();
+        assert_eq!(nonces, vec![7, 8]);
+    }
+}
@@ -0,0 +1,246 @@
+use crate::client::rpc::address_to_bytes;
+use crate::client::tracker::{TransactionTrace, TransactionTracker};
+use crate::client::transaction::{
+    SentTransaction, TransactionClient, ZK_CONTRACT_PREFIX, ZK_OPEN_INVOCATION,
+};
+use crate::commands::deploy::{find_contract_abi, Deployment};
+use crate::commands::deployments::DeploymentHistory;
+use crate::commands::upgrade::resolve_deployment;
+use crate::commands::user_profile::Profile;
+use crate::utils::abi::{ContractAbi, FnAbi, FnKind};
+use crate::utils::constants::{DEFAULT_ACTION_GAS, DEFAULT_NETWORK, TRANSACTION_VALIDITY_MS};
+use crate::utils::context::ProjectContext;
+use crate::utils::cryptography::address::{secret_key_from_hex, ADDRESS_LENGTH};
+use crate::utils::mainnet::MainnetGuard;
+use crate::utils::utils::{block_on, get_chain_id, get_rpc_endpoint, load_account_from_pk_file};
+use k256::SecretKey;
+use std::path::PathBuf;
+
+/// Configuration for invoking a contract action
+///
+/// # Fields
+/// * `contract` - Name or address of the deployed contract
+/// * `action` - Name of the action to invoke
+/// * `args` - One string per action argument
+/// * `network` - Network of the contract, defaults to the network it was recorded on
+/// * `path_to_pk` - Path to private key file
+/// * `gas_limit` - Gas limit of the transaction
+/// * `wait` - Wait until the transaction and its events have been executed and finalized
+/// * `confirm_mainnet` - Confirms a mainnet transaction without asking
+/// * `context` - Project the contract's deployment is looked up in
+#[derive(Debug, Clone)]
+pub struct CallConfigs {
+    pub contract: String,
+    pub action: String,
+    pub args: Vec<String>,
+    pub network: Option<String>,
+    pub path_to_pk: PathBuf,
+    pub gas_limit: Option<i64>,
+    pub wait: bool,
+    pub confirm_mainnet: bool,
+    pub context: ProjectContext,
+}
+
+/// Action invocation signed by the given profile
+///
+/// # Fields
+/// * `configs` - Call configuration
+/// * `address` - Address of the contract
+/// * `deployment` - Recorded deployment of the contract, if any
+/// * `network` - Network the contract is deployed on
+/// * `account` - Account sending the transaction
+#[derive(Debug, Clone)]
+pub struct ContractCall {
+    configs: CallConfigs,
+    address: String,
+    deployment: Option<Deployment>,
+    network: String,
+    account: Profile,
+}
+
+impl ContractCall {
+    /// Resolves the contract and loads the calling account
+    ///
+    /// # Arguments
+    /// * `configs` - Call configuration
+    ///
+    /// # Returns
+    /// * `Result<ContractCall>` - Call ready to be sent
+    pub fn new(configs: CallConfigs) -> Result<Self, Box<dyn std::error::Error>> {
+        let (address, deployment, network) =
+            resolve_contract(&configs.context, &configs.contract, configs.network.as_deref())?;
+        let account: Profile = load_account_from_pk_file(&configs.path_to_pk, &network)?;
+        Ok(Self {
+            configs,
+            address,
+            deployment,
+            network,
+            account,
+        })
+    }
+
+    /// Encodes and sends the action
+    ///
+    /// # Returns
+    /// * `Result<SentTransaction>` - Hash and destination shard of the transaction
+    pub fn call(&self) -> Result<SentTransaction, Box<dyn std::error::Error>> {
+        let client: TransactionClient =
+            TransactionClient::new(&get_rpc_endpoint(&self.network)?, &get_chain_id(&self.network)?);
+        let abi: ContractAbi = load_contract_abi(&client, &self.configs.context, &self.address, self.deployment.as_ref())?;
+        let address: [u8; ADDRESS_LENGTH] = address_to_bytes(&self.address)?;
+        let rpc: Vec<u8> = action_rpc(&abi, &self.configs.action, &self.configs.args, &address)?;
+        let secret_key: SecretKey = secret_key_from_hex(&self.account.private_key)?;
+
+        println!(
+            "Calling {} on {} at {} on {}.",
+            self.configs.action, self.configs.contract, self.address, self.network
+        );
+        let gas_limit: i64 = self.configs.gas_limit.unwrap_or(DEFAULT_ACTION_GAS);
+        // an action sends no code, so there is no artifact to check against a verified deployment
+        let project_root: PathBuf = self.configs.context.search_root();
+        MainnetGuard {
+            network: &self.network,
+            confirmed: self.configs.confirm_mainnet,
+            account: &self.account,
+            estimated_gas: gas_limit,
+            artifacts: Vec::new(),
+        }
+        .enforce(&project_root)?;
+        let sent: SentTransaction =
+            block_on(client.sign_and_send(&secret_key, address, rpc, gas_limit))??;
+        println!("Sent transaction {}", sent.transaction_hash);
+
+        if self.configs.wait {
+            let tracker: TransactionTracker = TransactionTracker::new(&client, TRANSACTION_VALIDITY_MS);
+            let trace: TransactionTrace = block_on(tracker.wait(&sent))??;
+            println!("{}", trace.render());
+            trace.ensure_succeeded()?;
+        }
+        Ok(sent)
+    }
+}
+
+/// Looks up a contract in the project's deployment history
+///
+/// Contracts are found by name or address. Addresses of contracts deployed outside the
+/// project are used as given.
+///
+/// # Arguments
+/// * `context` - Project whose deployment history is searched
+/// * `contract` - Contract name or address
+/// * `network` - Network of the contract, defaults to the network it was recorded on
+///
+/// # Returns
+/// * `Result<(String, Option<Deployment>, String)>` - Address, recorded deployment and network
+pub fn resolve_contract(
+    context: &ProjectContext,
+    contract: &str,
+    network: Option<&str>,
+) -> Result<(String, Option<Deployment>, String), Box<dyn std::error::Error>> {
+    let history: DeploymentHistory = DeploymentHistory::for_project(context).unwrap_or_default();
+    let deployment: Option<Deployment> = match resolve_deployment(&history, contract, network) {
+        Ok(deployment) => Some(deployment),
+        Err(e) if address_to_bytes(contract).is_err() => return Err(e),
+        Err(_) => None,
+    };
+    let address: String = deployment
+        .as_ref()
+        .map(|deployment| deployment.address.clone())
+        .unwrap_or_else(|| contract.to_lowercase());
+    let network: String = network
+        .map(|network| network.to_string())
+        .or_else(|| deployment.as_ref().map(|deployment| deployment.network.clone()))
+        .filter(|network| !network.is_empty())
+        .unwrap_or(DEFAULT_NETWORK.to_string());
+    Ok((address, deployment, network))
+}
+
+/// Loads the ABI of the code at an address
+///
+/// The project's build output is used for recorded deployments, otherwise the ABI
+/// the contract was deployed with is fetched from the chain.
+///
+/// # Arguments
+/// * `client` - Client of the contract's network
+/// * `context` - Project whose build output is searched
+/// * `address` - Contract address
+/// * `deployment` - Recorded deployment of the contract, if any
+///
+/// # Returns
+/// * `Result<ContractAbi>` - Parsed ABI
+pub fn load_contract_abi(
+    client: &TransactionClient,
+    context: &ProjectContext,
+    address: &str,
+    deployment: Option<&Deployment>,
+) -> Result<ContractAbi, Box<dyn std::error::Error>> {
+    let local_abi: Option<ContractAbi> = deployment.and_then(|deployment| {
+        find_contract_abi(context, deployment.code_version.as_deref().unwrap_or(&deployment.name))
+    });
+    match local_abi {
+        Some(abi) => Ok(abi),
+        None => {
+            let abi_bytes: Vec<u8> = block_on(client.get_contract_abi(address))??;
+            ContractAbi::from_bytes(&abi_bytes)
+        }
+    }
+}
+
+/// Serializes an action invocation
+///
+/// # Arguments
+/// * `abi` - ABI of the contract
+/// * `action` - Name of the action
+/// * `args` - One string per action argument
+/// * `address` - Contract address, zero-knowledge contracts take an extra invocation byte
+///
+/// # Returns
+/// * `Result<Vec<u8>>` - Transaction payload
+pub fn action_rpc(
+    abi: &ContractAbi,
+    action: &str,
+    args: &[String],
+    address: &[u8; ADDRESS_LENGTH],
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let function: &FnAbi = abi
+        .function(action)
+        .filter(|function| function.kind == FnKind::Action)
+        .ok_or_else(|| {
+            let actions: Vec<&str> = abi
+                .functions
+                .iter()
+                .filter(|function| function.kind == FnKind::Action)
+                .map(|function| function.name.as_str())
+                .collect();
+            format!("Unknown action {}, the contract has: {}", action, actions.join(", "))
+        })?;
+    let mut rpc: Vec<u8> = Vec::new();
+    if address[0] == ZK_CONTRACT_PREFIX {
+        rpc.push(ZK_OPEN_INVOCATION);
+    }
+    rpc.extend(abi.encode_call(function, args)?);
+    Ok(rpc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::abi::tests::counter_abi_bytes;
+
+    #[test]
+    fn test_action_rpc() {
+        let abi = ContractAbi::from_bytes(&counter_abi_bytes()).unwrap();
+        let public = address_to_bytes("02aa00000000000000000000000000000000000000").unwrap();
+        let zk = address_to_bytes("03aa00000000000000000000000000000000000000").unwrap();
+        let args: Vec<String> = vec!["5".to_string(), "ab".to_string()];
+
+        let rpc = action_rpc(&abi, "increment", &args, &public).unwrap();
+        assert_eq!(hex::encode(&rpc), "010000000500000001ab");
+        let zk_rpc = action_rpc(&abi, "increment", &args, &zk).unwrap();
+        assert_eq!(zk_rpc[0], ZK_OPEN_INVOCATION);
+        assert_eq!(zk_rpc[1..], rpc[..]);
+
+        let error = action_rpc(&abi, "initialize", &[], &public).unwrap_err().to_string();
+        assert!(error.contains("increment, configure"), "unexpected error: {}", error);
+    }
+}
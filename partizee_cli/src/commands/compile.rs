@@ -1,13 +1,47 @@
-use std::path::PathBuf;
-use std::process::{Command, Output};
+use crate::commands::deploy::{
+    order_deploy_groups, DeployConfigs, Deployer, DeploymentWithProfile, RunAddresses,
+};
+use crate::utils::build_cache::{source_hash, workspace_fingerprint, BuildCache};
+use crate::utils::build_info::{BuildInfo, BUILD_INFO_FILE};
+use crate::utils::build_report::{count_actions, ArtifactSizes, BuildReport, ContractReport};
+use crate::utils::constants::{DEFAULT_NETWORK, WATCH_DEBOUNCE_MS, WATCH_POLL_INTERVAL_MS};
+use crate::utils::context::ProjectContext;
+use crate::utils::diagnostics::{group_by_crate, parse_cargo_message, Diagnostic, CARGO_MESSAGE_FORMAT};
+use crate::utils::fs_nav::find_workspace_root;
+use crate::utils::manifest::DeployManifest;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+use walkdir::{DirEntry, WalkDir};
+
+/// Directory holding the contract crates, relative to the workspace root
+pub const CONTRACTS_DIR: &str = "rust/contracts";
+/// Directory cargo pbc writes compiled contracts to, relative to the workspace root
+pub const RELEASE_DIR: &str = "target/wasm32-unknown-unknown/release";
+/// Compiler flag that skips the ZK computation of zero-knowledge contracts
+pub const NO_ZK_FLAG: &str = "--no-zk";
+/// Directory holding a separate cargo target directory per contract built in parallel
+pub const JOBS_TARGET_DIR: &str = "target/partizee/jobs";
+/// Extensions of the artifacts cargo pbc writes for a contract
+const ARTIFACT_EXTENSIONS: [&str; 4] = ["pbc", "wasm", "abi", "zkwa"];
 
 /// Configuration for compiling Partisia Blockchain contracts
 /// 
 /// # Fields
-/// * `files` - Optional list of specific contract files to compile
-/// * `path` - Optional path to workspace directory
+/// * `files` - Optional list of specific contract files to compile, relative to the project directory
+/// * `path` - Optional path to workspace directory, relative to the project directory
 /// * `build_args` - Optional build arguments for cargo
 /// * `additional_args` - Optional additional arguments for the compiler
+/// * `force` - Rebuild every contract, ignoring the build cache
+/// * `jobs` - Number of contracts built at the same time, defaults to the available parallelism
+/// * `json` - Print the build report as JSON instead of a table
+/// * `context` - Project directory the paths are resolved against
 #[derive(Debug)]
 pub struct ProjectCompiler {
     // extra files to include
@@ -15,11 +49,15 @@ pub struct ProjectCompiler {
     pub path: Option<String>,
     pub build_args: Option<Vec<String>>,
     pub additional_args: Option<Vec<String>>,
+    pub force: bool,
+    pub jobs: Option<usize>,
+    pub json: bool,
+    pub context: ProjectContext,
 }
 
 impl Default for ProjectCompiler {
     /// Creates a new ProjectCompiler with default settings
-    /// All fields are set to None
+    /// All fields are set to None, the project is searched from the current directory
     #[inline]
     fn default() -> Self {
         let compile_args: ProjectCompiler = ProjectCompiler {
@@ -27,6 +65,10 @@ impl Default for ProjectCompiler {
             path: None,
             build_args: None,
             additional_args: None,
+            force: false,
+            jobs: None,
+            json: false,
+            context: ProjectContext::default(),
         };
         Self::new(compile_args)
     }
@@ -47,6 +89,10 @@ impl ProjectCompiler {
             path: compile_args.path,
             build_args: compile_args.build_args,
             additional_args: compile_args.additional_args,
+            force: compile_args.force,
+            jobs: compile_args.jobs,
+            json: compile_args.json,
+            context: compile_args.context,
         }
     }
 
@@ -55,10 +101,15 @@ impl ProjectCompiler {
     /// If no specific files are provided, compiles all contracts in the workspace
     /// Uses release mode by default and applies any specified build/additional arguments
     /// 
+    /// Contracts whose sources, workspace dependencies and build arguments are unchanged
+    /// since their last successful build are skipped, unless `force` is set.
+    /// The toolchain, sources and artifact hashes of the rebuilt contracts are recorded
+    /// in build-info.json in the release directory.
+    /// 
     /// # Returns
-    /// * `Result<(), Box<dyn std::error::Error>>` - Ok if compilation succeeds, Error otherwise
-    pub fn compile_contracts(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let mut output: Output;
+    /// * `Result<BuildReport>` - Report of the compiled contracts, Error if a contract failed to build or an artifact exceeds its size budget
+    pub fn compile_contracts(&self) -> Result<BuildReport, Box<dyn std::error::Error>> {
+        let started: Instant = Instant::now();
         let mut args = vec![
             String::from("pbc"),
             String::from("build"),
@@ -69,51 +120,777 @@ impl ProjectCompiler {
         extend_args(&mut args, self.build_args.as_ref());
         extend_args(&mut args, self.additional_args.as_ref());
 
-        if self.path.is_some() {
-            let path_arg: String = self.path.as_ref().unwrap().to_string();
-            if !PathBuf::from(&path_arg).is_dir() {
-                return Err("Path is not a directory".into());
+        let project_dir: PathBuf = match self.path.as_ref() {
+            Some(path) => {
+                let project_dir: PathBuf = self.context.resolve(Path::new(path));
+                if !project_dir.is_dir() {
+                    return Err(format!("Path {} is not a directory", project_dir.display()).into());
+                }
+                project_dir
+            }
+            None => self.context.project_dir.clone(),
+        };
+
+        let project_root: PathBuf = find_workspace_root(&project_dir).unwrap_or(project_dir);
+        let targets: Vec<(String, String, Option<ContractCrate>)> = match self.files.as_ref() {
+            None => find_contract_crates(&project_root)
+                .into_iter()
+                .map(|contract| (contract.name.clone(), contract.manifest_path.display().to_string(), Some(contract)))
+                .collect(),
+            Some(files) => files
+                .iter()
+                .map(|file| {
+                    let manifest_path: PathBuf = self.context.resolve(Path::new(file));
+                    (file.clone(), manifest_path.display().to_string(), read_contract_crate(&manifest_path))
+                })
+                .collect(),
+        };
+
+        // contracts are only skipped if they can be hashed and their artifacts are still there
+        let fingerprint: Vec<u8> = workspace_fingerprint(&project_root, &args);
+        let mut cache: BuildCache = BuildCache::load(&project_root);
+        let mut cached: Vec<String> = Vec::new();
+        let mut stale: Vec<(String, String, Option<String>)> = Vec::new();
+        for (label, manifest_path, contract) in targets.iter() {
+            let hash: Option<String> = contract.as_ref().and_then(|contract| {
+                source_hash(contract.manifest_path.parent()?, &fingerprint).ok()
+            });
+            match (contract, hash) {
+                (Some(contract), Some(hash))
+                    if !self.force
+                        && cache.is_fresh(&contract.name, &hash)
+                        && artifacts_exist(&project_root, &contract.name) =>
+                {
+                    cached.push(contract.name.clone())
+                }
+                (Some(contract), hash) => stale.push((contract.name.clone(), manifest_path.clone(), hash)),
+                (None, _) => stale.push((label.clone(), manifest_path.clone(), None)),
             }
-            // get absolute path
-            let current_path = PathBuf::from(&path_arg).canonicalize()?;
-            std::env::set_current_dir(&current_path)?;
-            println!("PATH CHANGED TO: {}", current_path.display());
         }
 
+        let mut builds: Vec<ContractBuild> = Vec::new();
         // if files is not None, compile the files
-        if self.files.is_none() {
+        if self.files.is_none() && cached.is_empty() {
             // compile all contracts in the contracts directory add compiler args and build args
-            output = Command::new("cargo")
-                .args(&args)
-                .output()
-                .expect("Failed to compile contracts");
-
-            if output.status.success() {
-                let output_str = String::from_utf8_lossy(&output.stdout);
-                print_success_message(&output_str);
+            let build: ContractBuild = run_build("all contracts", "[workspace]", &project_root, &args, None, self.json);
+            if build.success {
+                if !self.json {
+                    print_success_message("all contracts");
+                }
             } else {
-                print_error_message(
-                    "all contracts",
-                    String::from_utf8_lossy(&output.stderr).as_ref(),
-                );
+                print_error_message("all contracts", &build.error);
             }
-            // else compile all contracts in the specified files
+            // the workspace build is not timed per contract
+            builds.extend(stale.iter().map(|(name, _, _)| ContractBuild {
+                name: name.clone(),
+                build_ms: None,
+                ..build.clone()
+            }));
+            // else compile only the contracts that changed
         } else {
-            for file in self.files.as_ref().unwrap() {
-                let mut new_args = args.clone();
-                new_args.push(String::from("--manifest-path"));
-                new_args.push(file.to_string());
-                output = Command::new("cargo").args(&new_args).output()?;
-
-                if output.status.success() {
-                    print_success_message(file);
-                } else {
-                    println!("{:#?}", &output);
-                    print_error_message(file, String::from_utf8_lossy(&output.stderr).as_ref());
+            let jobs: usize = self.jobs.unwrap_or_else(default_jobs);
+            let run = |name: &str, prefix: &str, args: &[String], target_dir: Option<&Path>| {
+                run_build(name, prefix, &project_root, args, target_dir, self.json)
+            };
+            for build in build_contracts(&project_root, &args, &stale, jobs, run) {
+                if !build.success {
+                    print_error_message(&build.name, &build.error);
+                } else if !self.json {
+                    print_success_message(&build.name);
+                }
+                builds.push(build);
+            }
+        }
+
+        let mut rebuilt_contracts: Vec<(String, PathBuf)> = Vec::new();
+        for (name, manifest_path, hash) in stale.iter() {
+            let rebuilt: bool = builds.iter().any(|build| build.name == *name && build.success);
+            if rebuilt {
+                rebuilt_contracts.push((name.clone(), PathBuf::from(manifest_path)));
+            }
+            match hash {
+                Some(hash) if rebuilt => cache.record(name, hash),
+                _ => cache.forget(name),
+            }
+        }
+        // cached contracts keep the build info of the build that produced them
+        if !rebuilt_contracts.is_empty() {
+            let mut build_info: BuildInfo = BuildInfo::load(&project_root);
+            build_info.record(&project_root, &rebuilt_contracts);
+            if let Err(e) = build_info.save(&project_root) {
+                eprintln!("⚠️ Failed to write {}: {}", BUILD_INFO_FILE, e);
+            }
+        }
+        let names: Vec<String> = targets
+            .iter()
+            .map(|(label, _, contract)| contract.as_ref().map(|contract| contract.name.clone()).unwrap_or(label.clone()))
+            .collect();
+        let report: BuildReport = build_report(&project_root, &names, &cached, &builds, &mut cache, started);
+        if let Err(e) = cache.save(&project_root) {
+            eprintln!("⚠️ Failed to write the build cache: {}", e);
+        }
+
+        let rebuilt_any: bool = builds.iter().any(|build| build.success);
+        if self.files.is_none() && rebuilt_any && !args.iter().any(|arg| arg == NO_ZK_FLAG) {
+            match self.json {
+                false => report_zk_artifacts(&project_root),
+                true => {
+                    if let Err(e) = check_zk_artifacts(&project_root) {
+                        print_error_message("zero-knowledge contracts", &e.to_string());
+                    }
+                }
+            }
+        }
+        match self.json {
+            true => println!("{}", serde_json::to_string_pretty(&report)?),
+            false => println!("{}", report.render_table()),
+        }
+        // the report is printed first so the failures can be read from it
+        let failed: Vec<String> = report.failed_contracts();
+        if !failed.is_empty() {
+            return Err(format!("Failed to compile {}", failed.join(", ")).into());
+        }
+        let violations: Vec<String> = report.budget_violations();
+        if !violations.is_empty() {
+            return Err(format!("Artifacts exceed their size budget:\n  - {}", violations.join("\n  - ")).into());
+        }
+        Ok(report)
+    }
+
+    /// Watches the contracts and rebuilds the crates that change, until interrupted
+    /// 
+    /// `rust/contracts` and the workspace Cargo.toml are polled. Once a change is seen the
+    /// files have to be unchanged for `WATCH_DEBOUNCE_MS` before anything is built, so a
+    /// save of several files triggers one build. Only the crates containing changed files
+    /// are rebuilt, a change to the workspace Cargo.toml rebuilds every contract.
+    /// 
+    /// # Arguments
+    /// * `deploy_profile` - Redeploy rebuilt contracts to testnet from this account, which also regenerates the frontend bindings
+    /// 
+    /// # Returns
+    /// * `Result<()>` - Error if the project cannot be watched
+    pub fn watch(&self, deploy_profile: Option<PathBuf>) -> Result<(), Box<dyn std::error::Error>> {
+        let project_root: PathBuf = self.context.require_workspace_root()?;
+        if let Err(e) = self.compile_contracts() {
+            print_error_message("contracts", &e.to_string());
+        }
+        println!(
+            "👀 Watching {} for changes, press Ctrl+C to stop",
+            project_root.join(CONTRACTS_DIR).display()
+        );
+
+        let mut snapshot: WatchSnapshot = watch_snapshot(&project_root);
+        loop {
+            thread::sleep(Duration::from_millis(WATCH_POLL_INTERVAL_MS));
+            let mut current: WatchSnapshot = watch_snapshot(&project_root);
+            if current == snapshot {
+                continue;
+            }
+            loop {
+                thread::sleep(Duration::from_millis(WATCH_DEBOUNCE_MS));
+                let next: WatchSnapshot = watch_snapshot(&project_root);
+                if next == current {
+                    break;
+                }
+                current = next;
+            }
+            let changed: Vec<PathBuf> = changed_paths(&snapshot, &current);
+            snapshot = current;
+
+            let affected: Vec<ContractCrate> = affected_crates(&project_root, &changed);
+            if affected.is_empty() {
+                continue;
+            }
+            let names: Vec<&str> = affected.iter().map(|contract| contract.name.as_str()).collect();
+            println!("🔁 Changes detected, rebuilding {}", names.join(", "));
+            let compiler: ProjectCompiler = ProjectCompiler {
+                files: Some(
+                    affected
+                        .iter()
+                        .map(|contract| contract.manifest_path.display().to_string())
+                        .collect(),
+                ),
+                path: None,
+                build_args: self.build_args.clone(),
+                additional_args: self.additional_args.clone(),
+                force: self.force,
+                jobs: self.jobs,
+                json: false,
+                context: self.context.clone(),
+            };
+            let report: BuildReport = match compiler.compile_contracts() {
+                Ok(report) => report,
+                Err(e) => {
+                    print_error_message(&names.join(", "), &e.to_string());
+                    continue;
+                }
+            };
+            let rebuilt: Vec<String> = report
+                .contracts
+                .into_iter()
+                .filter(|contract| contract.status == "rebuilt")
+                .map(|contract| contract.name)
+                .collect();
+            if let Some(path_to_pk) = deploy_profile.as_ref().filter(|_| !rebuilt.is_empty()) {
+                if let Err(e) = redeploy(&project_root, rebuilt, path_to_pk) {
+                    eprintln!("❌ Redeploy failed: {}", e);
                 }
             }
         }
-        Ok(())
+    }
+}
+
+/// Modification time and size of every watched file
+type WatchSnapshot = BTreeMap<PathBuf, (Option<SystemTime>, u64)>;
+
+/// Lists the watched files, the workspace Cargo.toml and everything in `rust/contracts` outside of `target`
+/// 
+/// # Arguments
+/// * `project_root` - Root directory of project
+/// 
+/// # Returns
+/// * `WatchSnapshot` - Modification time and size per file
+fn watch_snapshot(project_root: &Path) -> WatchSnapshot {
+    let workspace_manifest: PathBuf = project_root.join("Cargo.toml");
+    WalkDir::new(project_root.join(CONTRACTS_DIR))
+        .into_iter()
+        .filter_entry(|entry: &DirEntry| !(entry.file_type().is_dir() && entry.file_name() == "target"))
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .chain(std::iter::once(workspace_manifest))
+        .filter_map(|path| {
+            let metadata: fs::Metadata = fs::metadata(&path).ok()?;
+            Some((path, (metadata.modified().ok(), metadata.len())))
+        })
+        .collect()
+}
+
+/// Files that were added, removed or modified between two snapshots
+/// 
+/// # Arguments
+/// * `before` - Earlier snapshot
+/// * `after` - Later snapshot
+/// 
+/// # Returns
+/// * `Vec<PathBuf>` - Changed files
+fn changed_paths(before: &WatchSnapshot, after: &WatchSnapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = after
+        .iter()
+        .filter(|(path, state)| before.get(*path) != Some(*state))
+        .map(|(path, _)| path.clone())
+        .collect();
+    changed.extend(before.keys().filter(|path| !after.contains_key(*path)).cloned());
+    changed
+}
+
+/// Contract crates containing one of the changed files
+/// 
+/// # Arguments
+/// * `project_root` - Root directory of project
+/// * `changed` - Changed files
+/// 
+/// # Returns
+/// * `Vec<ContractCrate>` - Crates to rebuild, every crate if the workspace Cargo.toml changed
+fn affected_crates(project_root: &Path, changed: &[PathBuf]) -> Vec<ContractCrate> {
+    let workspace_changed: bool = changed.contains(&project_root.join("Cargo.toml"));
+    find_contract_crates(project_root)
+        .into_iter()
+        .filter(|contract| {
+            workspace_changed
+                || contract
+                    .manifest_path
+                    .parent()
+                    .is_some_and(|crate_dir| changed.iter().any(|path| path.starts_with(crate_dir)))
+        })
+        .collect()
+}
+
+/// Deploys rebuilt contracts to testnet, with their arguments from partizee.toml
+/// 
+/// # Arguments
+/// * `project_root` - Root directory of project
+/// * `contract_names` - Contracts to deploy
+/// * `path_to_pk` - Path to the deployer's private key file
+/// 
+/// # Returns
+/// * `Result<()>` - Ok if every contract was deployed
+fn redeploy(
+    project_root: &Path,
+    contract_names: Vec<String>,
+    path_to_pk: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // watch mode only ever deploys to testnet, whatever the manifest says
+    let overrides: DeployConfigs = DeployConfigs {
+        contract_names,
+        network: Some(DEFAULT_NETWORK.to_string()),
+        deployer_args: None,
+        path_to_pk: Some(path_to_pk.to_path_buf()),
+        artifacts: None,
+        gas_limits: None,
+    };
+    let configs: Vec<DeployConfigs> = match DeployManifest::find(project_root, None)? {
+        Some(manifest) => manifest.deploy_configs(&overrides, project_root),
+        None => vec![overrides],
+    };
+    let mut deployers: Vec<DeploymentWithProfile> = Vec::new();
+    for config in configs {
+        let deployer: Deployer = Deployer {
+            network: DEFAULT_NETWORK.to_string(),
+            contract_names: config.contract_names,
+            deployer_args: config.deployer_args.unwrap_or_default(),
+            path_to_pk: path_to_pk.to_path_buf(),
+            artifacts: config.artifacts.unwrap_or_default(),
+            gas_limits: config.gas_limits.unwrap_or_default(),
+            confirm_mainnet: false,
+        };
+        deployers.push(DeploymentWithProfile::new(deployer, ProjectContext::at(project_root)));
+    }
+    let mut run: RunAddresses = RunAddresses::new(&deployers);
+    for mut deployer in order_deploy_groups(deployers)? {
+        deployer.deploy_contracts(None, &mut run)?;
+    }
+    Ok(())
+}
+
+/// Builds the report of a compilation and records the new artifact sizes in the cache
+/// 
+/// Size budgets are read from `[contracts.<name>.budget]` in partizee.toml.
+/// 
+/// # Arguments
+/// * `project_root` - Root directory of project
+/// * `names` - Every contract that was compiled or skipped, in report order
+/// * `cached` - Contracts skipped because they are unchanged
+/// * `builds` - Outcome of each build
+/// * `cache` - Build cache holding the sizes of the previous build
+/// * `started` - When the compilation started
+/// 
+/// # Returns
+/// * `BuildReport` - One line per contract
+fn build_report(
+    project_root: &Path,
+    names: &[String],
+    cached: &[String],
+    builds: &[ContractBuild],
+    cache: &mut BuildCache,
+    started: Instant,
+) -> BuildReport {
+    let release_dir: PathBuf = project_root.join(RELEASE_DIR);
+    let manifest: Option<DeployManifest> = DeployManifest::find(project_root, None).unwrap_or_else(|e| {
+        eprintln!("⚠️ Size budgets not checked: {}", e);
+        None
+    });
+    let mut contracts: Vec<ContractReport> = Vec::new();
+    for name in names {
+        let build: Option<&ContractBuild> = builds.iter().find(|build| build.name == *name);
+        let status: &str = match build {
+            Some(build) if !build.success => "failed",
+            Some(_) => "rebuilt",
+            None if cached.contains(name) => "cached",
+            None => continue,
+        };
+        let build_ms: Option<u64> = build.and_then(|build| build.build_ms);
+        if status == "failed" {
+            contracts.push(ContractReport {
+                name: name.clone(),
+                status: status.to_string(),
+                sizes: ArtifactSizes::default(),
+                size_change: None,
+                actions: None,
+                build_ms,
+                over_budget: Vec::new(),
+            });
+            continue;
+        }
+        let sizes: ArtifactSizes = ArtifactSizes::read(&release_dir, name);
+        let size_change: Option<i64> = cache
+            .sizes
+            .get(name)
+            .map(|previous| sizes.deployed() as i64 - previous.deployed() as i64);
+        let over_budget: Vec<String> = manifest
+            .as_ref()
+            .and_then(|manifest| manifest.budget(name))
+            .map(|budget| sizes.over_budget(budget))
+            .unwrap_or_default();
+        cache.sizes.insert(name.clone(), sizes);
+        contracts.push(ContractReport {
+            name: name.clone(),
+            status: status.to_string(),
+            sizes,
+            size_change,
+            actions: count_actions(&release_dir, name),
+            build_ms,
+            over_budget,
+        });
+    }
+    BuildReport {
+        contracts,
+        total_ms: started.elapsed().as_millis() as u64,
+        diagnostics: group_by_crate(builds.iter().flat_map(|build| build.diagnostics.clone()).collect()),
+    }
+}
+
+/// Outcome of a single cargo pbc build
+/// 
+/// # Fields
+/// * `name` - Contract or label of the build
+/// * `success` - Whether cargo exited successfully
+/// * `error` - First compiler error, or the last error line printed by cargo, empty on success
+/// * `build_ms` - Milliseconds the build took
+/// * `diagnostics` - Errors and warnings reported by the compiler
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractBuild {
+    pub name: String,
+    pub success: bool,
+    pub error: String,
+    pub build_ms: Option<u64>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Number of builds run at the same time when `--jobs` is not given
+fn default_jobs() -> usize {
+    thread::available_parallelism()
+        .map(|parallelism| parallelism.get())
+        .unwrap_or(1)
+}
+
+/// Builds contracts by manifest, up to `jobs` at the same time
+/// 
+/// Parallel builds each get their own cargo target directory under `target/partizee/jobs`,
+/// cargo locks a target directory for the duration of a build. Their artifacts are copied
+/// to the release directory afterwards. A single job builds in the workspace's target directory.
+/// 
+/// # Arguments
+/// * `project_root` - Root directory of project
+/// * `args` - Arguments passed to cargo
+/// * `contracts` - Name, manifest path and source hash of each contract to build
+/// * `jobs` - Maximum number of builds run at the same time
+/// * `run` - Runs one build, given the contract name, the output prefix, the arguments and the target directory
+/// 
+/// # Returns
+/// * `Vec<ContractBuild>` - Outcome per contract, in the order of `contracts`
+fn build_contracts(
+    project_root: &Path,
+    args: &[String],
+    contracts: &[(String, String, Option<String>)],
+    jobs: usize,
+    run: impl Fn(&str, &str, &[String], Option<&Path>) -> ContractBuild + Sync,
+) -> Vec<ContractBuild> {
+    let width: usize = contracts.iter().map(|(name, _, _)| name.len()).max().unwrap_or(0);
+    let manifest_args = |manifest_path: &str| -> Vec<String> {
+        let mut manifest_args: Vec<String> = args.to_vec();
+        manifest_args.push(String::from("--manifest-path"));
+        manifest_args.push(manifest_path.to_string());
+        manifest_args
+    };
+    let jobs: usize = jobs.clamp(1, contracts.len().max(1));
+    if jobs == 1 {
+        return contracts
+            .iter()
+            .map(|(name, manifest_path, _)| {
+                let prefix: String = format!("[{:<width$}]", name, width = width);
+                run(name, &prefix, &manifest_args(manifest_path), None)
+            })
+            .collect();
+    }
+
+    let next: AtomicUsize = AtomicUsize::new(0);
+    let builds: Mutex<Vec<(usize, ContractBuild)>> = Mutex::new(Vec::new());
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| {
+                loop {
+                    let index: usize = next.fetch_add(1, Ordering::SeqCst);
+                    let Some((name, manifest_path, _)) = contracts.get(index) else {
+                        break;
+                    };
+                    let prefix: String = format!("[{:<width$}]", name, width = width);
+                    let target_dir: PathBuf = project_root.join(JOBS_TARGET_DIR).join(name);
+                    let mut build: ContractBuild =
+                        run(name, &prefix, &manifest_args(manifest_path), Some(&target_dir));
+                    if build.success {
+                        let copied = copy_artifacts(
+                            &target_dir.join("wasm32-unknown-unknown/release"),
+                            &project_root.join(RELEASE_DIR),
+                            name,
+                        );
+                        if let Err(e) = copied {
+                            build.success = false;
+                            build.error = format!("failed to copy the artifacts: {}", e);
+                        }
+                    }
+                    if let Ok(mut builds) = builds.lock() {
+                        builds.push((index, build));
+                    }
+                }
+            });
+        }
+    });
+    let mut builds: Vec<(usize, ContractBuild)> = builds.into_inner().unwrap_or_default();
+    builds.sort_by_key(|(index, _)| *index);
+    builds.into_iter().map(|(_, build)| build).collect()
+}
+
+/// Runs cargo, streaming its output line by line under a prefix
+/// 
+/// Cargo prints its messages as JSON, the compiler's diagnostics are collected for the
+/// report instead of being printed. Other output is printed as it arrives.
+/// 
+/// # Arguments
+/// * `name` - Contract or label of the build
+/// * `prefix` - Prefix printed before every output line
+/// * `project_root` - Directory cargo is run in
+/// * `args` - Arguments passed to cargo
+/// * `target_dir` - Cargo target directory, the workspace's if None
+/// * `quiet_stdout` - Print cargo's stdout to stderr, keeping stdout for the JSON report
+/// 
+/// # Returns
+/// * `ContractBuild` - Outcome of the build
+fn run_build(
+    name: &str,
+    prefix: &str,
+    project_root: &Path,
+    args: &[String],
+    target_dir: Option<&Path>,
+    quiet_stdout: bool,
+) -> ContractBuild {
+    let started: Instant = Instant::now();
+    let mut command: Command = Command::new("cargo");
+    command
+        .args(args)
+        .arg(CARGO_MESSAGE_FORMAT)
+        .current_dir(project_root)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(target_dir) = target_dir {
+        command.env("CARGO_TARGET_DIR", target_dir);
+    }
+    let failed = |error: String, diagnostics: Vec<Diagnostic>| ContractBuild {
+        name: name.to_string(),
+        success: false,
+        error,
+        build_ms: Some(started.elapsed().as_millis() as u64),
+        diagnostics,
+    };
+    let mut child: Child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) => return failed(format!("failed to run cargo: {}", e), Vec::new()),
+    };
+
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    // cargo reports progress on stderr, both streams are printed as they arrive
+    let (last_error, diagnostics): (String, Vec<Diagnostic>) = thread::scope(|scope| {
+        let diagnostics = stdout.map(|stdout| {
+            scope.spawn(move || {
+                let mut diagnostics: Vec<Diagnostic> = Vec::new();
+                for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                    if let Some(diagnostic) = parse_cargo_message(&line) {
+                        diagnostics.push(diagnostic);
+                        continue;
+                    }
+                    // the other JSON messages report artifacts and build scripts
+                    if serde_json::from_str::<serde_json::Value>(&line).is_ok() {
+                        continue;
+                    }
+                    match quiet_stdout {
+                        true => eprintln!("{} {}", prefix, line),
+                        false => println!("{} {}", prefix, line),
+                    }
+                }
+                diagnostics
+            })
+        });
+        let mut last_error: String = String::new();
+        if let Some(stderr) = stderr {
+            for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                eprintln!("{} {}", prefix, line);
+                if line.trim_start().starts_with("error") {
+                    last_error = line.trim().to_string();
+                }
+            }
+        }
+        let diagnostics: Vec<Diagnostic> = diagnostics
+            .and_then(|handle| handle.join().ok())
+            .unwrap_or_default();
+        (last_error, diagnostics)
+    });
+    let error: String = diagnostics
+        .iter()
+        .find(|diagnostic| diagnostic.level == "error")
+        .map(|diagnostic| match diagnostic.span.as_ref() {
+            Some(span) => format!("{} at {}:{}:{}", diagnostic.message, span.file, span.line, span.column),
+            None => diagnostic.message.clone(),
+        })
+        .unwrap_or(last_error);
+
+    match child.wait() {
+        Ok(status) if status.success() => ContractBuild {
+            name: name.to_string(),
+            success: true,
+            error: String::new(),
+            build_ms: Some(started.elapsed().as_millis() as u64),
+            diagnostics,
+        },
+        Ok(status) if error.is_empty() => failed(format!("cargo exited with {}", status), diagnostics),
+        Ok(_) => failed(error, diagnostics),
+        Err(e) => failed(e.to_string(), diagnostics),
+    }
+}
+
+/// Copies the artifacts of a contract between release directories
+/// 
+/// # Arguments
+/// * `from` - Release directory of the contract's own target directory
+/// * `to` - Release directory of the workspace
+/// * `name` - Package name of the contract
+/// 
+/// # Returns
+/// * `Result<usize>` - Number of artifacts copied
+fn copy_artifacts(from: &Path, to: &Path, name: &str) -> Result<usize, Box<dyn std::error::Error>> {
+    let stem: String = name.replace('-', "_");
+    fs::create_dir_all(to)?;
+    let mut copied: usize = 0;
+    for extension in ARTIFACT_EXTENSIONS {
+        let file_name: String = format!("{}.{}", stem, extension);
+        if from.join(&file_name).is_file() {
+            fs::copy(from.join(&file_name), to.join(&file_name))?;
+            copied += 1;
+        }
+    }
+    Ok(copied)
+}
+
+/// Contract crate of the workspace
+/// 
+/// # Fields
+/// * `name` - Package name of the contract
+/// * `manifest_path` - Path to the crate's Cargo.toml
+/// * `zero_knowledge` - Whether the crate has a `[package.metadata.zk]` section
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractCrate {
+    pub name: String,
+    pub manifest_path: PathBuf,
+    pub zero_knowledge: bool,
+}
+
+/// Reads the package of a contract crate from its Cargo.toml
+/// 
+/// # Arguments
+/// * `manifest_path` - Path to the crate's Cargo.toml
+/// 
+/// # Returns
+/// * `Option<ContractCrate>` - The crate, None if the manifest has no package
+pub fn read_contract_crate(manifest_path: &Path) -> Option<ContractCrate> {
+    let manifest: toml::Value = fs::read_to_string(manifest_path).ok()?.parse().ok()?;
+    let package: &toml::Value = manifest.get("package")?;
+    Some(ContractCrate {
+        name: package.get("name")?.as_str()?.to_string(),
+        manifest_path: manifest_path.to_path_buf(),
+        zero_knowledge: package
+            .get("metadata")
+            .and_then(|metadata| metadata.get("zk"))
+            .is_some(),
+    })
+}
+
+/// Finds the contract crates listed as workspace members
+/// 
+/// # Arguments
+/// * `project_root` - Root directory of project
+/// 
+/// # Returns
+/// * `Vec<ContractCrate>` - Member crates, in the order of the workspace
+pub fn find_contract_crates(project_root: &Path) -> Vec<ContractCrate> {
+    let workspace: Option<toml::Value> = fs::read_to_string(project_root.join("Cargo.toml"))
+        .ok()
+        .and_then(|contents| contents.parse::<toml::Value>().ok());
+    let members: Vec<&str> = workspace
+        .as_ref()
+        .and_then(|workspace| workspace.get("workspace"))
+        .and_then(|workspace| workspace.get("members"))
+        .and_then(|members| members.as_array())
+        .map(|members| members.iter().filter_map(|member| member.as_str()).collect())
+        .unwrap_or_default();
+    members
+        .into_iter()
+        .filter_map(|member| read_contract_crate(&project_root.join(member).join("Cargo.toml")))
+        .collect()
+}
+
+/// Finds the workspace members that are zero-knowledge contracts
+/// 
+/// A member is a zero-knowledge contract if its Cargo.toml has `[package.metadata.zk]`,
+/// which points cargo pbc at the `#[zk_compute]` module to run the zkcompiler on.
+/// 
+/// # Arguments
+/// * `project_root` - Root directory of project
+/// 
+/// # Returns
+/// * `Vec<String>` - Package names of the zero-knowledge contracts
+pub fn find_zk_contracts(project_root: &Path) -> Vec<String> {
+    find_contract_crates(project_root)
+        .into_iter()
+        .filter(|contract| contract.zero_knowledge)
+        .map(|contract| contract.name)
+        .collect()
+}
+
+/// Whether the release directory holds the code and ABI of a contract
+/// 
+/// # Arguments
+/// * `project_root` - Root directory of project
+/// * `name` - Package name of the contract
+/// 
+/// # Returns
+/// * `bool` - True if an .abi and a .pbc, .wasm or .zkwa exist
+fn artifacts_exist(project_root: &Path, name: &str) -> bool {
+    let release_dir: PathBuf = project_root.join(RELEASE_DIR);
+    let stem: String = name.replace('-', "_");
+    release_dir.join(format!("{}.abi", stem)).is_file()
+        && ["pbc", "wasm", "zkwa"]
+            .iter()
+            .any(|extension| release_dir.join(format!("{}.{}", stem, extension)).is_file())
+}
+
+/// Checks that each zero-knowledge contract was built to a .zkwa and .abi pair
+/// 
+/// # Arguments
+/// * `project_root` - Root directory of project
+/// 
+/// # Returns
+/// * `Result<Vec<String>>` - Names of the built zero-knowledge contracts, Error naming those without artifacts
+pub fn check_zk_artifacts(project_root: &Path) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let release_dir: PathBuf = project_root.join(RELEASE_DIR);
+    let (built, missing): (Vec<String>, Vec<String>) = find_zk_contracts(project_root)
+        .into_iter()
+        .partition(|name| {
+            ["zkwa", "abi"]
+                .iter()
+                .all(|extension| release_dir.join(format!("{}.{}", name, extension)).is_file())
+        });
+    if !missing.is_empty() {
+        return Err(format!(
+            "no .zkwa and .abi were built for {}, check [workspace.metadata.zkcompiler] in Cargo.toml",
+            missing.join(", ")
+        )
+        .into());
+    }
+    Ok(built)
+}
+
+/// Prints the zero-knowledge contracts that were built, or which ones are missing artifacts
+/// 
+/// # Arguments
+/// * `project_root` - Root directory of project
+fn report_zk_artifacts(project_root: &Path) {
+    match check_zk_artifacts(project_root) {
+        Ok(built) => {
+            for name in built {
+                println!("🔒 Built zero-knowledge contract {}: {}.zkwa + {}.abi", name, name, name);
+            }
+        }
+        Err(e) => print_error_message("zero-knowledge contracts", &e.to_string()),
     }
 }
 
@@ -151,3 +928,168 @@ pub fn print_success_message(file: &str) {
 pub fn print_error_message(file: &str, error: &str) {
     eprintln!("❌ Failed to compile {}: {}", file, error);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_check_zk_artifacts() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"rust/contracts/counter\", \"rust/contracts/averageSalary\"]\n",
+        )
+        .unwrap();
+        for (member, manifest) in [
+            ("counter", "[package]\nname = \"counter\"\n"),
+            (
+                "averageSalary",
+                "[package]\nname = \"averageSalary\"\n\n[package.metadata.zk]\nzk-compute-path = \"src/zk_compute.rs\"\n",
+            ),
+        ] {
+            let crate_dir = root.join("rust/contracts").join(member);
+            fs::create_dir_all(&crate_dir).unwrap();
+            fs::write(crate_dir.join("Cargo.toml"), manifest).unwrap();
+        }
+        assert_eq!(find_zk_contracts(root), vec!["averageSalary".to_string()]);
+
+        let release_dir = root.join(RELEASE_DIR);
+        fs::create_dir_all(&release_dir).unwrap();
+        fs::write(release_dir.join("averageSalary.zkwa"), "").unwrap();
+        let error = check_zk_artifacts(root).unwrap_err().to_string();
+        assert!(error.contains("averageSalary"), "unexpected error: {}", error);
+
+        fs::write(release_dir.join("averageSalary.abi"), "").unwrap();
+        assert_eq!(check_zk_artifacts(root).unwrap(), vec!["averageSalary".to_string()]);
+        assert!(artifacts_exist(root, "averageSalary"));
+        assert!(!artifacts_exist(root, "counter"));
+    }
+
+    #[test]
+    fn test_build_contracts() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let contracts: Vec<(String, String, Option<String>)> = ["counter", "token", "swap"]
+            .iter()
+            .map(|name| (name.to_string(), format!("{}/Cargo.toml", name), None))
+            .collect();
+        let args: Vec<String> = vec![String::from("pbc"), String::from("build")];
+        let running: AtomicUsize = AtomicUsize::new(0);
+        let most_running: AtomicUsize = AtomicUsize::new(0);
+        let runs = Mutex::new(Vec::new());
+        let run = |name: &str, prefix: &str, args: &[String], target_dir: Option<&Path>| {
+            let now_running: usize = running.fetch_add(1, Ordering::SeqCst) + 1;
+            most_running.fetch_max(now_running, Ordering::SeqCst);
+            runs.lock().unwrap().push((prefix.to_string(), args.to_vec(), target_dir.map(Path::to_path_buf)));
+            // the builds overlap, each writes its artifact to its own target directory
+            thread::sleep(Duration::from_millis(50));
+            if let Some(target_dir) = target_dir {
+                let release_dir: PathBuf = target_dir.join("wasm32-unknown-unknown/release");
+                fs::create_dir_all(&release_dir).unwrap();
+                fs::write(release_dir.join(format!("{}.wasm", name)), "wasm").unwrap();
+            }
+            running.fetch_sub(1, Ordering::SeqCst);
+            ContractBuild {
+                name: name.to_string(),
+                success: name != "token",
+                error: if name == "token" { "mismatched types".to_string() } else { String::new() },
+                build_ms: Some(10),
+                diagnostics: Vec::new(),
+            }
+        };
+        let builds = build_contracts(root, &args, &contracts, 3, run);
+
+        // outcomes keep the order of the contracts, however the builds finished
+        let outcomes: Vec<(&str, bool)> = builds
+            .iter()
+            .map(|build| (build.name.as_str(), build.success))
+            .collect();
+        assert_eq!(outcomes, vec![("counter", true), ("token", false), ("swap", true)]);
+        assert_eq!(builds[1].error, "mismatched types");
+        assert!(most_running.load(Ordering::SeqCst) > 1, "builds did not run in parallel");
+
+        let runs = runs.into_inner().unwrap();
+        let (prefix, token_args, target_dir) = runs.iter().find(|(prefix, _, _)| prefix.starts_with("[token")).unwrap();
+        assert_eq!(prefix, "[token  ]");
+        assert_eq!(token_args, &vec!["pbc", "build", "--manifest-path", "token/Cargo.toml"]);
+        assert_eq!(target_dir.as_deref(), Some(root.join(JOBS_TARGET_DIR).join("token").as_path()));
+        // artifacts of the successful builds are copied to the workspace's release directory
+        assert!(root.join(RELEASE_DIR).join("counter.wasm").is_file());
+        assert!(root.join(RELEASE_DIR).join("swap.wasm").is_file());
+        assert!(!root.join(RELEASE_DIR).join("token.wasm").exists());
+
+        // a single job builds in the workspace's target directory
+        let target_dirs: Mutex<Vec<Option<PathBuf>>> = Mutex::new(Vec::new());
+        build_contracts(root, &args, &contracts, 1, |name, _, _, target_dir| {
+            target_dirs.lock().unwrap().push(target_dir.map(Path::to_path_buf));
+            ContractBuild {
+                name: name.to_string(),
+                success: true,
+                error: String::new(),
+                build_ms: None,
+                diagnostics: Vec::new(),
+            }
+        });
+        assert_eq!(target_dirs.into_inner().unwrap(), vec![None, None, None]);
+    }
+
+    #[test]
+    fn test_copy_artifacts() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let from = root.join("jobs/counter/release");
+        let to = root.join(RELEASE_DIR);
+        fs::create_dir_all(&from).unwrap();
+        fs::write(from.join("counter.wasm"), "wasm").unwrap();
+        fs::write(from.join("counter.abi"), "abi").unwrap();
+        assert_eq!(copy_artifacts(&from, &to, "counter").unwrap(), 2);
+        assert!(artifacts_exist(root, "counter"));
+    }
+
+    #[test]
+    #[ignore = "runs cargo"]
+    fn test_run_build_reports_cargo_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        // cargo rejects the argument, so the build fails without compiling anything
+        let args: Vec<String> = vec![String::from("--not-a-cargo-flag")];
+        let build = run_build("counter", "[counter]", temp_dir.path(), &args, None, true);
+        assert_eq!(build.name, "counter");
+        assert!(!build.success && !build.error.is_empty());
+    }
+
+    #[test]
+    fn test_affected_crates() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"rust/contracts/counter\", \"rust/contracts/token\"]\n",
+        )
+        .unwrap();
+        for name in ["counter", "token"] {
+            let crate_dir = root.join(CONTRACTS_DIR).join(name);
+            fs::create_dir_all(crate_dir.join("src")).unwrap();
+            fs::write(crate_dir.join("Cargo.toml"), format!("[package]\nname = \"{}\"\n", name)).unwrap();
+            fs::write(crate_dir.join("src/lib.rs"), "").unwrap();
+        }
+        let before = watch_snapshot(root);
+        assert_eq!(before.len(), 5);
+
+        fs::write(root.join(CONTRACTS_DIR).join("counter/src/lib.rs"), "pub fn increment() {}").unwrap();
+        fs::create_dir_all(root.join(CONTRACTS_DIR).join("token/target")).unwrap();
+        fs::write(root.join(CONTRACTS_DIR).join("token/target/token.wasm"), "wasm").unwrap();
+        let after = watch_snapshot(root);
+        let changed = changed_paths(&before, &after);
+        assert_eq!(changed, vec![root.join(CONTRACTS_DIR).join("counter/src/lib.rs")]);
+        let affected: Vec<String> = affected_crates(root, &changed)
+            .into_iter()
+            .map(|contract| contract.name)
+            .collect();
+        assert_eq!(affected, vec!["counter".to_string()]);
+
+        assert_eq!(affected_crates(root, &[root.join("Cargo.toml")]).len(), 2);
+    }
+}
@@ -1,17 +1,39 @@
+use crate::client::tracker::{TransactionTrace, TransactionTracker};
+use crate::client::transaction::{
+    now_millis, ContractDeployment, SentTransaction, TransactionClient,
+};
+use crate::commands::deployments::DeploymentHistory;
 use crate::commands::user_profile::Profile;
-use crate::utils::constants::DEFAULT_NETWORK;
+use crate::utils::abi::ContractAbi;
+use crate::utils::artifact::{ContractArtifact, ContractKind};
+use crate::utils::build_info::{BuildInfo, BuildInfoLink};
+use crate::utils::constants::{
+    DEFAULT_NETWORK, DEPLOY_BASE_GAS, DEPLOY_GAS_PER_BYTE, GAS_ESTIMATE_MARGIN_PERCENT,
+    INIT_BASE_GAS, TRANSACTION_VALIDITY_MS, ZK_NODE_ALLOCATION_GAS,
+};
+use crate::utils::context::ProjectContext;
+use crate::utils::cryptography::address::secret_key_from_hex;
+use crate::utils::frontend::generate_frontend_bindings;
 use crate::utils::fs_nav::{
-    find_dir, find_files_with_extension, find_paths_with_name, find_workspace_root,
-    get_all_contract_names,
+    find_dir, find_files_with_extension, find_paths_with_name, get_all_contract_names,
+};
+use crate::utils::journal::{DeployJournal, DeployLock};
+use crate::utils::mainnet::{is_mainnet, MainnetGuard};
+use crate::utils::placeholders::{
+    deployment_order, group_order, preview_placeholders, substitute_placeholders, DeployGroup,
+    PROFILE_REFERENCE,
 };
-use crate::utils::utils::load_account_from_pk_file;
+use crate::utils::utils::{block_on, get_chain_id, get_rpc_endpoint, load_account_from_pk_file};
+use k256::SecretKey;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
-use std::process::{Command, Output};
+use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// File the most recent deployment run is recorded in
+pub const LATEST_DEPLOYMENT_FILE: &str = "deployment-latest.json";
+
 /// Configuration for deploying Partisia Blockchain contracts
 /// 
 /// # Fields
@@ -19,27 +41,43 @@ use std::time::{SystemTime, UNIX_EPOCH};
 /// * `network` - Optional network to deploy to (e.g. testnet, mainnet)
 /// * `deployer_args` - Optional map of contract names to their deployment arguments
 /// * `path_to_pk` - Optional path to private key file
+/// * `artifacts` - Optional map of contract names to explicit artifact paths
+/// * `gas_limits` - Optional map of contract names to deployment gas limits
 #[derive(Debug, Clone)]
 pub struct DeployConfigs {
     pub contract_names: Vec<String>,
     pub network: Option<String>,
     pub deployer_args: Option<HashMap<String, Vec<String>>>,
     pub path_to_pk: Option<PathBuf>,
+    pub artifacts: Option<HashMap<String, PathBuf>>,
+    pub gas_limits: Option<HashMap<String, i64>>,
 }
 
 /// Represents a deployed contract with its metadata
 /// 
 /// # Fields
 /// * `name` - Name of the deployed contract
+/// * `network` - Network the contract was deployed to
 /// * `address` - Blockchain address where contract was deployed
+/// * `transaction_hash` - Hash of the deploy transaction
 /// * `args` - Arguments used during deployment
 /// * `timestamp` - Unix timestamp of deployment
+/// * `code_version` - Contract whose code runs at the address after an upgrade
+/// * `build_info` - Build that produced the deployed code, if it was built by `partizee compile`
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Deployment {
     pub name: String,
+    #[serde(default)]
+    pub network: String,
     pub address: String,
+    #[serde(default)]
+    pub transaction_hash: String,
     pub args: Vec<String>,
     pub timestamp: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_info: Option<BuildInfoLink>,
 }
 
 /// Configuration for deploying contracts with network and account details
@@ -49,12 +87,37 @@ pub struct Deployment {
 /// * `contract_names` - List of contracts to deploy
 /// * `deployer_args` - Map of contract names to their deployment arguments
 /// * `path_to_pk` - Path to private key file
+/// * `artifacts` - Map of contract names to explicit artifact paths
+/// * `gas_limits` - Map of contract names to deployment gas limits
+/// * `confirm_mainnet` - Confirms a mainnet deployment without asking
 #[derive(Debug, Clone)]
 pub struct Deployer {
     pub network: String,
     pub contract_names: Vec<String>,
     pub deployer_args: HashMap<String, Vec<String>>,
     pub path_to_pk: PathBuf,
+    pub artifacts: HashMap<String, PathBuf>,
+    pub gas_limits: HashMap<String, i64>,
+    pub confirm_mainnet: bool,
+}
+
+/// Contract resolved and validated before any transaction is sent
+/// 
+/// # Fields
+/// * `name` - Name of the contract
+/// * `artifact` - Code and ABI to deploy
+/// * `abi` - Parsed ABI of the contract
+/// * `args` - Deployment arguments, possibly containing placeholders
+/// * `estimated_gas` - Gas the deployment is estimated to need
+/// * `gas_limit` - Gas limit of the deploy transaction, the estimate unless configured
+#[derive(Debug, Clone)]
+pub struct PlannedDeployment {
+    pub name: String,
+    pub artifact: ContractArtifact,
+    pub abi: ContractAbi,
+    pub args: Vec<String>,
+    pub estimated_gas: i64,
+    pub gas_limit: i64,
 }
 
 /// Combines deployment configuration with account profile
@@ -62,35 +125,78 @@ pub struct Deployer {
 /// # Fields
 /// * `deploy_configs` - Deployment configuration
 /// * `account` - Account profile for deployment
+/// * `context` - Project the contracts are deployed from
 #[derive(Debug, Clone)]
 pub struct DeploymentWithProfile {
     deploy_configs: Deployer,
     account: Profile,
+    context: ProjectContext,
+}
+
+/// Contracts of one deploy run across all of its groups
+/// 
+/// A placeholder referencing a contract of the run resolves to the address deployed
+/// in the run, never to an older deployment of that contract.
+/// 
+/// # Fields
+/// * `contracts` - Network and lowercase name of every contract in the run, with its address once deployed
+#[derive(Debug, Clone, Default)]
+pub struct RunAddresses {
+    contracts: HashMap<(String, String), Option<String>>,
 }
 
 impl Default for DeployConfigs {
+    /// Creates default deployment configuration for the project in the current directory
+    fn default() -> Self {
+        Self::for_project(&ProjectContext::default())
+    }
+}
+
+impl DeployConfigs {
     /// Creates default deployment configuration
     /// Uses all available contracts and default network
-    fn default() -> Self {
-        let all_contract_names: Option<Vec<String>> = get_all_contract_names();
+    /// 
+    /// # Arguments
+    /// * `context` - Project the contracts are deployed from
+    /// 
+    /// # Returns
+    /// * `DeployConfigs` - Default configuration
+    pub fn for_project(context: &ProjectContext) -> Self {
+        let all_contract_names: Option<Vec<String>> = get_all_contract_names(&context.project_dir);
         Self {
             contract_names: all_contract_names.unwrap_or(Vec::new()),
             network: Some(DEFAULT_NETWORK.to_string()),
             deployer_args: None,
             path_to_pk: None,
+            artifacts: None,
+            gas_limits: None,
         }
     }
 }
 
 // default deployment with account, selects either the first account found or creates a new account if none are found
 impl Default for DeploymentWithProfile {
+    /// Creates default deployment with account for the project in the current directory
+    fn default() -> Self {
+        Self::for_project(ProjectContext::default())
+    }
+}
+
+#[allow(dead_code)]
+impl DeploymentWithProfile {
     /// Creates default deployment with account
     /// Selects first available account or creates new one if none found
-    fn default() -> Self {
-        let account: Profile = Profile::default();
-        let mut deploy_project: DeployConfigs = DeployConfigs::default();
+    /// 
+    /// # Arguments
+    /// * `context` - Project the contracts are deployed from
+    /// 
+    /// # Returns
+    /// * `DeploymentWithProfile` - New deployment instance
+    pub fn for_project(context: ProjectContext) -> Self {
+        let account: Profile = Profile::for_project(&context);
+        let mut deploy_project: DeployConfigs = DeployConfigs::for_project(&context);
         deploy_project.path_to_pk = Some(account.path_to_pk.clone());
-        let all_contract_names: Option<Vec<String>> = get_all_contract_names();
+        let all_contract_names: Option<Vec<String>> = get_all_contract_names(&context.project_dir);
 
         let deployer: Deployer = Deployer {
             network: deploy_project
@@ -103,48 +209,219 @@ impl Default for DeploymentWithProfile {
                 .clone()
                 .unwrap_or(HashMap::new()),
             path_to_pk: deploy_project.path_to_pk.clone().expect("No account found"),
+            artifacts: HashMap::new(),
+            gas_limits: HashMap::new(),
+            confirm_mainnet: false,
         };
         Self {
             deploy_configs: deployer,
             account: account,
+            context,
         }
     }
-}
 
-#[allow(dead_code)]
-impl DeploymentWithProfile {
+    /// Whether a journaled run was deployed to this network from this account
+    /// 
+    /// # Arguments
+    /// * `journal` - Journal of an earlier run
+    /// 
+    /// # Returns
+    /// * `bool` - True if this deployment can resume the run
+    pub fn resumes(&self, journal: &DeployJournal) -> bool {
+        journal.network == self.deploy_configs.network && journal.deployer == self.account.address
+    }
+
     /// Creates new deployment with specified configuration
     /// 
     /// # Arguments
     /// * `deploy_config` - Deployment configuration
+    /// * `context` - Project the contracts are deployed from
     /// 
     /// # Returns
     /// * `DeploymentWithProfile` - New deployment instance
-    pub fn new(deploy_config: Deployer) -> Self {
+    pub fn new(deploy_config: Deployer, context: ProjectContext) -> Self {
         let deployment_account: Profile =
             load_account_from_pk_file(&deploy_config.path_to_pk, &deploy_config.network)
                 .expect("Failed to load account");
         Self {
             deploy_configs: deploy_config,
             account: deployment_account,
+            context,
         }
     }
 
     /// Deploys all specified contracts to the blockchain
     /// 
-    /// Handles finding and loading contract files (.pbc, .abi, .wasm, .zkwa)
-    /// Saves deployment results to deployment-latest.json
+    /// Every contract is resolved and its arguments validated against the ABI
+    /// before the first transaction is sent. The run is journaled after each contract
+    /// so a failed run can be resumed, and a lock keeps other terminals from deploying
+    /// the project at the same time. Successful deployments are merged into
+    /// deployment-latest.json.
+    /// 
+    /// # Arguments
+    /// * `resume` - Journal of an earlier run of this network and deployer, contracts that
+    ///   succeeded in it are skipped
+    /// * `run` - Contracts of every group in the run, records the addresses this group deploys
     /// 
     /// # Returns
     /// * `Result<(), Box<dyn std::error::Error>>` - Ok if all deployments succeed
-    pub fn deploy_contracts(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        let project_root: PathBuf = find_workspace_root().unwrap();
+    pub fn deploy_contracts(
+        &mut self,
+        resume: Option<DeployJournal>,
+        run: &mut RunAddresses,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let project_root: PathBuf = self.context.require_workspace_root()?;
+        let planned_deployments: Vec<PlannedDeployment> = self.plan_deployments(&project_root)?;
+        self.mainnet_guard(&planned_deployments).enforce(&project_root)?;
+        let deployments_dir: PathBuf =
+            find_deployments_dir(&project_root).ok_or("Failed to find target directory")?;
+        let _lock: DeployLock = DeployLock::acquire(&deployments_dir)?;
 
-        let mut names: Vec<String> = self.deploy_configs.contract_names.clone();
+        let mut journal: DeployJournal = match resume {
+            Some(journal) => {
+                if !self.resumes(&journal) {
+                    return Err(format!(
+                        "Run {} deployed to {} from {}, resume it with the same network and account",
+                        journal.run_id, journal.network, journal.deployer
+                    )
+                    .into());
+                }
+                journal
+            }
+            None => {
+                let names: Vec<String> = planned_deployments
+                    .iter()
+                    .map(|planned| planned.name.clone())
+                    .collect();
+                DeployJournal::create(
+                    &deployments_dir,
+                    &self.deploy_configs.network,
+                    &self.account.address,
+                    &names,
+                )?
+            }
+        };
+        println!("Deploy run {}", journal.run_id);
+
+        // addresses placeholders can refer to, contracts of this run only resolve to the
+        // addresses deployed in it, by an earlier group or by the resumed journal
+        let network: String = self.deploy_configs.network.clone();
+        run.register(
+            &network,
+            planned_deployments.iter().map(|planned| planned.name.as_str()),
+        );
+        let mut known_addresses: HashMap<String, String> =
+            addresses_on_network(load_latest_deployments(&project_root), &network);
+        run.resolve(&network, &mut known_addresses);
+        known_addresses.extend(addresses_on_network(journal.deployments(), &network));
+        known_addresses.insert(PROFILE_REFERENCE.to_string(), self.account.address.clone());
+
+        for planned in planned_deployments.iter() {
+            let name: &str = &planned.name;
+            if let Some(deployment) = journal.succeeded(name) {
+                println!("⏭️ Skipping {}, deployed at {} in run {}", name, deployment.address, journal.run_id);
+                continue;
+            }
+            // a transaction sent by an interrupted run is checked instead of deploying again
+            let resumed: Option<Result<Deployment, Box<dyn std::error::Error>>> = journal
+                .sent(name)
+                .and_then(|(deployment, sent)| self.check_sent_deployment(deployment, &sent).transpose());
+            let result: Result<Deployment, Box<dyn std::error::Error>> = match resumed {
+                Some(result) => result,
+                None => substitute_placeholders(&planned.args, &known_addresses)
+                    .map_err(|e| format!("{} on {}", e, self.deploy_configs.network).into())
+                    .and_then(|contract_args| {
+                        if contract_args.len() > 0 {
+                            println!("Deploying {} with args: {:?}", &name, &contract_args);
+                        }
+                        self.deploy_contract(planned, contract_args, &mut journal)
+                    }),
+            };
+
+            match result {
+                Ok(deployment) => {
+                    known_addresses.insert(name.to_lowercase(), deployment.address.clone());
+                    run.record(&network, name, &deployment.address);
+                    journal.record_success(name, deployment)?;
+                }
+                Err(e) => {
+                    eprintln!("Error deploying contract {}: {}", name, e);
+                    journal.record_failure(name, &e.to_string())?;
+                }
+            }
+        }
+
+        let deployments: Vec<Deployment> = journal.deployments();
+        save_deployments(deployments.clone(), &project_root)?;
+        if !deployments.is_empty() {
+            self.update_frontend(&project_root, &deployments_dir, &planned_deployments, &deployments);
+        }
+
+        let failed: Vec<String> = journal.failed();
+        if !failed.is_empty() {
+            return Err(format!(
+                "{} failed in run {}, retry them with partizee deploy --resume {}",
+                failed.join(", "),
+                journal.run_id,
+                journal.run_id
+            )
+            .into());
+        }
+        Ok(())
+    }
 
-        let path_to_contracts: PathBuf =
-            find_dir(&project_root, "wasm32-unknown-unknown/release").unwrap();
+    /// Regenerates the frontend's deployed addresses and ABI bindings
+    /// 
+    /// Failures are reported as warnings, the contracts are already deployed
+    /// 
+    /// # Arguments
+    /// * `project_root` - Root directory of project
+    /// * `deployments_dir` - Directory the deployment history is recorded in
+    /// * `planned_deployments` - Contracts of the run
+    /// * `deployments` - Contracts deployed in the run
+    fn update_frontend(
+        &self,
+        project_root: &Path,
+        deployments_dir: &Path,
+        planned_deployments: &[PlannedDeployment],
+        deployments: &[Deployment],
+    ) {
+        let abis: Vec<(String, Vec<u8>)> = planned_deployments
+            .iter()
+            .filter(|planned| {
+                deployments
+                    .iter()
+                    .any(|deployment| deployment.name.eq_ignore_ascii_case(&planned.name))
+            })
+            .map(|planned| (planned.name.clone(), planned.artifact.abi_bytes.clone()))
+            .collect();
+        let result = DeploymentHistory::load(deployments_dir).and_then(|history| {
+            generate_frontend_bindings(project_root, &history, &self.deploy_configs.network, &abis)
+        });
+        match result {
+            Ok(Some(path)) => println!("Updated frontend deployments in {}", path.display()),
+            Ok(None) => {}
+            Err(e) => eprintln!("⚠️ Failed to update frontend deployments: {}", e),
+        }
+    }
 
+    /// Resolves the artifacts of all specified contracts and validates their arguments
+    /// 
+    /// Handles finding and loading contract files (.pbc, .abi, .wasm, .zkwa)
+    /// 
+    /// # Arguments
+    /// * `project_root` - Root directory of project
+    /// 
+    /// # Returns
+    /// * `Result<Vec<PlannedDeployment>>` - Contracts in deployment order, Error listing every invalid contract
+    pub fn plan_deployments(
+        &self,
+        project_root: &PathBuf,
+    ) -> Result<Vec<PlannedDeployment>, Box<dyn std::error::Error>> {
+        let mut names: Vec<String> = self.deploy_configs.contract_names.clone();
+
+        let path_to_contracts: PathBuf = find_dir(project_root, "wasm32-unknown-unknown/release")
+            .ok_or("No compiled contracts found, run partizee compile first")?;
         let mut contract_pbc_set: HashSet<PathBuf> = HashSet::new();
         let mut contract_abi_set: HashSet<PathBuf> = HashSet::new();
         let mut contract_wasm_set: HashSet<PathBuf> = HashSet::new();
@@ -154,7 +431,7 @@ impl DeploymentWithProfile {
         names = names_set.into_iter().collect();
 
         if names.is_empty() {
-            names = get_all_contract_names().unwrap_or(Vec::new());
+            names = get_all_contract_names(project_root).unwrap_or(Vec::new());
             // get all contract abis, pbc, wasm, and zkwa files
             contract_pbc_set = find_files_with_extension(&path_to_contracts, "pbc")
                 .into_iter()
@@ -220,191 +497,313 @@ impl DeploymentWithProfile {
         let contract_args_hashmap: HashMap<String, Vec<String>> =
             self.deploy_configs.deployer_args.clone();
 
-        let mut deployments: Vec<Deployment> = Vec::new();
+        // deploy referenced contracts before the contracts that take their address
+        let original_names: HashMap<String, String> = names
+            .iter()
+            .map(|name| (name.to_lowercase(), name.clone()))
+            .collect();
+        let lowercase_names: Vec<String> = original_names.keys().cloned().collect();
+        let ordered_names: Vec<String> =
+            deployment_order(&lowercase_names, &contract_args_hashmap)?
+                .into_iter()
+                .map(|name| original_names[&name].clone())
+                .collect();
 
-        for (_, name) in names.iter().enumerate() {
+        let mut planned_deployments: Vec<PlannedDeployment> = Vec::new();
+        let mut errors: Vec<String> = Vec::new();
+        for name in ordered_names.iter() {
             let name_lowercase = name.to_lowercase();
-            // get name of current contract
-            let contract_args: Vec<String> =
-                contract_args_hashmap.get(&name_lowercase).cloned().unwrap_or_default();
-            if contract_args.len() > 0 {
-                println!("Deploying {} with args: {:?}", &name, &contract_args);
-            }
-
-            let contract_pbc_path= contract_pbc_map.get(&name_lowercase).cloned();
-            let contract_abi_path= contract_abi_map.get(&name_lowercase).cloned();
-            let contract_wasm_path= contract_wasm_map.get(&name_lowercase).cloned();
-            let contract_zkwa_path= contract_zkwa_map.get(&name_lowercase).cloned();
-            
-
-
+            let mut contract_pbc_path= contract_pbc_map.get(&name_lowercase).cloned();
+            let mut contract_abi_path= contract_abi_map.get(&name_lowercase).cloned();
+            let mut contract_wasm_path= contract_wasm_map.get(&name_lowercase).cloned();
+            let mut contract_zkwa_path= contract_zkwa_map.get(&name_lowercase).cloned();
 
+            // an explicitly configured artifact replaces the discovered files
+            if let Some(artifact) = self.deploy_configs.artifacts.get(&name_lowercase) {
+                let sibling_abi: PathBuf = artifact.with_extension("abi");
+                if sibling_abi.is_file() {
+                    contract_abi_path = Some(sibling_abi);
+                }
+                contract_pbc_path = None;
+                contract_wasm_path = None;
+                contract_zkwa_path = None;
+                match artifact.extension().and_then(|ext| ext.to_str()) {
+                    Some("pbc") => contract_pbc_path = Some(artifact.clone()),
+                    Some("wasm") => contract_wasm_path = Some(artifact.clone()),
+                    Some("zkwa") => contract_zkwa_path = Some(artifact.clone()),
+                    _ => {
+                        errors.push(format!("{}: unsupported artifact {}", name, artifact.display()));
+                        continue;
+                    }
+                }
+            }
 
-            let result = self.deploy_contract(
-                name,
+            let args: Vec<String> = contract_args_hashmap
+                .get(&name_lowercase)
+                .cloned()
+                .unwrap_or_default();
+            let planned = load_artifact(
                 contract_pbc_path,
                 contract_abi_path,
                 contract_wasm_path,
                 contract_zkwa_path,
-                contract_args,
-            );
-
-            if result.is_err() {
-                eprintln!(
-                    "Error deploying contract {}: {:?}",
-                    name,
-                    result.err().unwrap()
-                );
-            } else {
-                let deployment = result.unwrap_or_else(|e| {
-                    panic!("Error deploying contract {}: {:?}", name, e);
-                });
-                deployments.push(deployment);
+            )
+            .and_then(|artifact| {
+                let abi: ContractAbi = ContractAbi::from_bytes(&artifact.abi_bytes)?;
+                let init_rpc: Vec<u8> = validate_init_args(&abi, &args)?;
+                let estimated_gas: i64 = estimate_deploy_gas(&artifact, &init_rpc);
+                Ok(PlannedDeployment {
+                    name: name.clone(),
+                    artifact,
+                    abi,
+                    args,
+                    estimated_gas,
+                    gas_limit: self
+                        .deploy_configs
+                        .gas_limits
+                        .get(&name_lowercase)
+                        .copied()
+                        .unwrap_or(estimated_gas),
+                })
+            });
+            match planned {
+                Ok(planned) => planned_deployments.push(planned),
+                Err(e) => errors.push(format!("{}: {}", name, e)),
             }
         }
 
-        save_deployments(deployments, &project_root)?;
+        if !errors.is_empty() {
+            return Err(format!(
+                "Refusing to deploy, invalid contracts:\n  {}",
+                errors.join("\n  ")
+            )
+            .into());
+        }
+        Ok(planned_deployments)
+    }
+
+    /// Validates the deployment and prints its plan without submitting anything
+    /// 
+    /// # Returns
+    /// * `Result<()>` - Error if a contract is invalid or the deployer cannot pay for the plan
+    pub fn dry_run(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let project_root: PathBuf = self.context.require_workspace_root()?;
+        let planned_deployments: Vec<PlannedDeployment> = self.plan_deployments(&project_root)?;
+        let client: TransactionClient = TransactionClient::new(
+            &get_rpc_endpoint(&self.deploy_configs.network)?,
+            &get_chain_id(&self.deploy_configs.network)?,
+        );
+        let balance: Result<i64, String> =
+            block_on(client.get_gas_balance(&self.account.address))
+                .and_then(|balance| balance)
+                .map_err(|e| e.to_string());
 
+        println!(
+            "Deploy plan for {} from {}",
+            self.deploy_configs.network, self.account.address
+        );
+        println!(
+            "{:<24} {:<8} {:>12} {:>14} {:>14}  ARGS",
+            "CONTRACT", "KIND", "SIZE (B)", "EST. GAS", "GAS LIMIT"
+        );
+        for planned in planned_deployments.iter() {
+            let kind: &str = match planned.artifact.kind {
+                ContractKind::Public => "public",
+                ContractKind::ZeroKnowledge => "zk",
+            };
+            println!(
+                "{:<24} {:<8} {:>12} {:>14} {:>14}  {}",
+                planned.name,
+                kind,
+                planned.artifact.contract_bytes.len() + planned.artifact.abi_bytes.len(),
+                planned.estimated_gas,
+                planned.gas_limit,
+                planned.args.join(" ")
+            );
+        }
+        let total_gas: i64 = planned_deployments
+            .iter()
+            .map(|planned| planned.gas_limit)
+            .sum();
+        println!("Total gas limit: {}", total_gas);
+        match balance {
+            Ok(balance) if balance >= total_gas => {
+                println!("✅ Balance of {} gas covers the deployment", balance)
+            }
+            Ok(balance) => {
+                return Err(format!(
+                    "Balance of {} gas does not cover the {} gas needed, mint gas with partizee profile mint-gas",
+                    balance, total_gas
+                )
+                .into())
+            }
+            Err(e) => eprintln!("⚠️ Could not check the deployer's balance: {}", e),
+        }
+        if is_mainnet(&self.deploy_configs.network)? {
+            let problems: Vec<String> = self.mainnet_guard(&planned_deployments).problems(&project_root);
+            if !problems.is_empty() {
+                return Err(format!("Mainnet checks failed:\n  - {}", problems.join("\n  - ")).into());
+            }
+            println!("✅ Mainnet checks passed, the deployment still has to be confirmed");
+        }
         Ok(())
     }
 
+    /// Mainnet checks for the planned deployments
+    /// 
+    /// # Arguments
+    /// * `planned_deployments` - Contracts about to be deployed
+    /// 
+    /// # Returns
+    /// * `MainnetGuard` - Guard covering the total gas limit and every artifact
+    fn mainnet_guard<'a>(&'a self, planned_deployments: &'a [PlannedDeployment]) -> MainnetGuard<'a> {
+        MainnetGuard {
+            network: &self.deploy_configs.network,
+            confirmed: self.deploy_configs.confirm_mainnet,
+            account: &self.account,
+            estimated_gas: planned_deployments.iter().map(|planned| planned.gas_limit).sum(),
+            artifacts: planned_deployments
+                .iter()
+                .map(|planned| (planned.name.as_str(), &planned.artifact))
+                .collect(),
+        }
+    }
+
     /// Deploys a single contract to the blockchain
     /// 
+    /// The sent transaction and the contract's address are journaled before waiting for
+    /// the transaction, so a resumed run checks it instead of deploying the contract again.
+    /// 
     /// # Arguments
-    /// * `name` - Name of contract to deploy
-    /// * `contract_pbc_path` - Optional path to .pbc file
-    /// * `contract_abi_path` - Optional path to .abi file
-    /// * `contract_wasm_path` - Optional path to .wasm file
-    /// * `contract_zkwa_path` - Optional path to .zkwa file
-    /// * `args` - Deployment arguments
+    /// * `planned` - Resolved and validated contract
+    /// * `args` - Deployment arguments with placeholders substituted
+    /// * `journal` - Journal of the run
     /// 
     /// # Returns
     /// * `Result<Deployment>` - Deployment result with contract address
     pub fn deploy_contract(
         &mut self,
-        name: &str,
-        contract_pbc_path: Option<PathBuf>,
-        contract_abi_path: Option<PathBuf>,
-        contract_wasm_path: Option<PathBuf>,
-        contract_zkwa_path: Option<PathBuf>,
+        planned: &PlannedDeployment,
         args: Vec<String>,
+        journal: &mut DeployJournal,
     ) -> Result<Deployment, Box<dyn std::error::Error>> {
-        println!("deploy_contract: {:#?}", contract_pbc_path);
-        println!("args: {:#?}", args);
-        println!("name: {:#?}", name);
-        // cargo partisia-contract transaction deploy --gas 10000000 --privatekey YourProfileFile.pk your_compiled_contract_file.pbc + contract inputs separated by spaces (strings in quotes)
-        let private_key_path: PathBuf = self.account.path_to_pk.clone();
         assert!(self.deploy_configs.network.len() > 0);
-        let network_command: String = format!("--net={}", &self.deploy_configs.network);
-        let mut command: Command = Command::new("cargo");
-        command
-            .arg("pbc")
-            .arg("transaction")
-            .arg("-v")
-            .arg(&network_command)
-            .arg("deploy")
-            .arg("--gas")
-            .arg("10000000")
-            .arg("--pk")
-            .arg(&private_key_path);
-
-        match (
-            contract_pbc_path.as_ref(),
-            contract_abi_path.as_ref(),
-            contract_wasm_path.as_ref(),
-            contract_zkwa_path.as_ref(),
-        ) {
-            (Some(pbc), Some(abi), None, None) => {
-                command.arg(pbc);
-                command.arg("--abi");
-                command.arg(abi.to_str().unwrap());
-            }
-            (None, Some(abi), Some(wasm), None) => {
-                command.arg(wasm);
-                command.arg("--abi");
-                command.arg(abi.to_str().unwrap());
-            }
-            (None, Some(abi), None, Some(zkwa)) => {
-                command.arg(zkwa);
-                command.arg("--abi");
-                command.arg(abi.to_str().unwrap());
-            }
-            (Some(pbc), None, None, None) => {
-                command.arg(pbc.to_str().unwrap());
-            }
-            (Some(pbc), Some(_abi), Some(_wasm), None) => {
-                command.arg(pbc.to_str().unwrap());
-            }
+        let name: &str = &planned.name;
 
-            (Some(pbc), None, Some(_wasm), None) => {
-                command.arg(pbc.to_str().unwrap());
-            }
-            (Some(pbc), None, Some(_wasm), Some(_zkwa)) => {
-                command.arg(pbc.to_str().unwrap());
-            }
-            (Some(pbc), None, None, Some(_zkwa)) => {
-                command.arg(pbc.to_str().unwrap());
-            }
-            (Some(pbc), Some(_abi), Some(_wasm), Some(_zkwa)) => {
-                command.arg(pbc.to_str().unwrap());
-            }
-            (None, Some(abi), Some(_wasm), Some(zkwa)) => {
-                command.arg(zkwa.to_str().unwrap());
-                command.arg("--abi");
-                command.arg(abi.to_str().unwrap());
-            }
-            _ => {
-                return Err("Need either pbc or wasm + abi or zkwa + abi paths provided".into());
-            }
-        }
+        let init_rpc: Vec<u8> = planned
+            .abi
+            .encode_call(planned.abi.init_function()?, &args)?;
 
-        command.args(&args);
+        let secret_key: SecretKey = secret_key_from_hex(&self.account.private_key)?;
+        let client: TransactionClient = TransactionClient::new(
+            &get_rpc_endpoint(&self.deploy_configs.network)?,
+            &get_chain_id(&self.deploy_configs.network)?,
+        );
         println!("Deploying {} to {}.", &name, &self.deploy_configs.network);
-        let deployment_tx: Output = command.output()?;
-
-        if deployment_tx.status.success() {
-            let output_str = String::from_utf8_lossy(&deployment_tx.stdout);
-            let address = output_str
-                .split(":")
-                .nth(1)
-                .unwrap_or("")
-                .split("\n")
-                .nth(0)
-                .unwrap_or("")
-                .trim()
-                .to_string();
-            if address.is_empty() {
-                return Err("Failed to get address from deployment output".into());
-            }
-            let timestamp: String = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap_or_else(|_| {
-                    panic!("Failed to get timestamp");
-                })
-                .as_secs()
-                .to_string();
-            let deployment = Deployment {
-                name: name.to_string(),
-                address,
-                args,
-                timestamp,
-            };
+        let result: ContractDeployment = block_on(client.deploy_contract(
+            &secret_key,
+            &planned.artifact,
+            &init_rpc,
+            planned.gas_limit,
+        ))?
+        .map_err(|e| {
+            eprintln!("❌ Failed to deploy contract: {}", e);
+            e
+        })?;
+
+        let timestamp: String = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_else(|_| {
+                panic!("Failed to get timestamp");
+            })
+            .as_secs()
+            .to_string();
+        let deployment = Deployment {
+            name: name.to_string(),
+            network: self.deploy_configs.network.clone(),
+            address: result.contract_address,
+            transaction_hash: result.transaction_hash,
+            args,
+            timestamp,
+            code_version: None,
+            build_info: BuildInfo::link(&self.context.search_root(), name, &planned.artifact),
+        };
+        let sent: SentTransaction = SentTransaction {
+            transaction_hash: deployment.transaction_hash.clone(),
+            destination_shard: result.destination_shard,
+        };
+        journal.record_sent(name, deployment.clone(), sent.destination_shard.clone())?;
+
+        // the contract only exists once the deploy event spawned on its shard has succeeded
+        println!("Waiting for deploy transaction {} to be finalized.", sent.transaction_hash);
+        let tracker: TransactionTracker = TransactionTracker::new(&client, TRANSACTION_VALIDITY_MS);
+        let trace: TransactionTrace = block_on(tracker.wait(&sent))??;
+        trace
+            .ensure_succeeded()
+            .inspect_err(|_| eprintln!("{}", trace.render()))?;
+        println!(
+            "✅ Successfully deployed contract '{}' to '{}' at address: {} (transaction {})",
+            name, &self.deploy_configs.network, deployment.address, deployment.transaction_hash
+        );
+        Ok(deployment)
+    }
+
+    /// Looks up the deploy transaction an interrupted run sent for a contract
+    /// 
+    /// A transaction that is not final yet is waited for, so the contract is never deployed twice.
+    /// 
+    /// # Arguments
+    /// * `deployment` - Deployment journaled when the transaction was sent
+    /// * `sent` - Deploy transaction
+    /// 
+    /// # Returns
+    /// * `Result<Option<Deployment>>` - The deployment if the transaction succeeded, None if it failed or expired and the contract has to be deployed again, Error if its outcome is still unknown
+    pub fn check_sent_deployment(
+        &self,
+        deployment: Deployment,
+        sent: &SentTransaction,
+    ) -> Result<Option<Deployment>, Box<dyn std::error::Error>> {
+        let client: TransactionClient = TransactionClient::new(
+            &get_rpc_endpoint(&self.deploy_configs.network)?,
+            &get_chain_id(&self.deploy_configs.network)?,
+        );
+        println!(
+            "Checking deploy transaction {} of {} sent by the interrupted run.",
+            sent.transaction_hash, deployment.name
+        );
+        let tracker: TransactionTracker = TransactionTracker::new(&client, TRANSACTION_VALIDITY_MS);
+        let mut trace: TransactionTrace = block_on(tracker.status(sent))??;
+
+        // a transaction that was not included within its validity can no longer be
+        let included: bool = trace
+            .transactions
+            .first()
+            .map(|traced| traced.executed.is_some())
+            .unwrap_or(false);
+        let sent_at: i64 = deployment.timestamp.parse().unwrap_or(0);
+        if !included && now_millis() / 1000 > sent_at + TRANSACTION_VALIDITY_MS / 1000 {
             println!(
-                "✅ Successfully deployed contract '{}' to '{}' at address: {}",
-                name, &self.deploy_configs.network, deployment.address
+                "Deploy transaction {} expired, deploying {} again.",
+                sent.transaction_hash, deployment.name
             );
-            return Ok(deployment);
-        } else {
-            let stdout = String::from_utf8_lossy(&deployment_tx.stdout);
-            let stderr = String::from_utf8_lossy(&deployment_tx.stderr);
-            let status = deployment_tx.status;
-            eprintln!("❌ Failed to deploy contract:");
-            eprintln!("Status: {}", status);
-            eprintln!("Stdout: {}", stdout);
-            eprintln!("Stderr: {}", stderr);
-            return Err("Failed to deploy contract".into());
+            return Ok(None);
+        }
+        if !trace.is_final() && trace.failure().is_none() {
+            println!("Waiting for deploy transaction {} to be finalized.", sent.transaction_hash);
+            trace = block_on(tracker.wait(sent))??;
         }
+        if let Some(failure) = trace.failure() {
+            eprintln!("{}", trace.render());
+            println!("{}, deploying {} again.", failure, deployment.name);
+            return Ok(None);
+        }
+        println!(
+            "✅ Contract '{}' was deployed by the interrupted run at address: {}",
+            deployment.name, deployment.address
+        );
+        Ok(Some(deployment))
     }
+
     /// Gets deployment arguments for a specific contract
     /// 
     /// # Arguments
@@ -436,74 +835,369 @@ impl DeploymentWithProfile {
 
 }
 
+impl RunAddresses {
+    /// Registers the contracts of every deploy group of a run
+    /// 
+    /// # Arguments
+    /// * `deployers` - Deploy groups of the run
+    /// 
+    /// # Returns
+    /// * `RunAddresses` - Run with none of its contracts deployed yet
+    pub fn new(deployers: &[DeploymentWithProfile]) -> Self {
+        let mut run: RunAddresses = RunAddresses::default();
+        for deployer in deployers {
+            run.register(
+                &deployer.deploy_configs.network,
+                deployer.deploy_configs.contract_names.iter().map(|name| name.as_str()),
+            );
+        }
+        run
+    }
+
+    /// Adds contracts to the run, contracts already in it keep their address
+    /// 
+    /// # Arguments
+    /// * `network` - Network the contracts are deployed to
+    /// * `names` - Names of the contracts
+    pub fn register<'a>(&mut self, network: &str, names: impl Iterator<Item = &'a str>) {
+        for name in names {
+            self.contracts
+                .entry((network.to_string(), name.to_lowercase()))
+                .or_insert(None);
+        }
+    }
+
+    /// Records the address a contract of the run was deployed at
+    /// 
+    /// # Arguments
+    /// * `network` - Network the contract was deployed to
+    /// * `name` - Name of the contract
+    /// * `address` - Address of the deployed contract
+    pub fn record(&mut self, network: &str, name: &str, address: &str) {
+        self.contracts.insert(
+            (network.to_string(), name.to_lowercase()),
+            Some(address.to_string()),
+        );
+    }
+
+    /// Replaces the recorded addresses of the run's contracts with the addresses deployed in it
+    /// 
+    /// Contracts of the run that are not deployed yet are removed, a placeholder referencing
+    /// them fails instead of resolving to an older deployment.
+    /// 
+    /// # Arguments
+    /// * `network` - Network the addresses are on
+    /// * `addresses` - Map of lowercase names to recorded addresses on the network
+    pub fn resolve(&self, network: &str, addresses: &mut HashMap<String, String>) {
+        for ((contract_network, name), address) in &self.contracts {
+            if contract_network != network {
+                continue;
+            }
+            match address {
+                Some(address) => addresses.insert(name.clone(), address.clone()),
+                None => addresses.remove(name),
+            };
+        }
+    }
+}
+
+/// Orders deploy groups so that a group deploys after the groups whose contracts it references
+/// 
+/// # Arguments
+/// * `deployers` - Deploy groups of one run
+/// 
+/// # Returns
+/// * `Result<Vec<DeploymentWithProfile>>` - Groups in deployment order, Error if groups reference each other
+pub fn order_deploy_groups(
+    deployers: Vec<DeploymentWithProfile>,
+) -> Result<Vec<DeploymentWithProfile>, Box<dyn std::error::Error>> {
+    let names: Vec<Vec<String>> = deployers
+        .iter()
+        .map(|deployer| {
+            deployer
+                .deploy_configs
+                .contract_names
+                .iter()
+                .map(|name| name.to_lowercase())
+                .collect()
+        })
+        .collect();
+    let groups: Vec<DeployGroup> = deployers
+        .iter()
+        .zip(names)
+        .map(|(deployer, names)| DeployGroup {
+            network: &deployer.deploy_configs.network,
+            names,
+            args: &deployer.deploy_configs.deployer_args,
+        })
+        .collect();
+    let order: Vec<usize> = group_order(&groups)?;
+    let mut deployers: Vec<Option<DeploymentWithProfile>> = deployers.into_iter().map(Some).collect();
+    Ok(order
+        .into_iter()
+        .filter_map(|index| deployers[index].take())
+        .collect())
+}
+
+/// Loads a deployable artifact from the files found for a contract
+/// 
+/// # Arguments
+/// * `contract_pbc_path` - Optional path to .pbc file
+/// * `contract_abi_path` - Optional path to .abi file
+/// * `contract_wasm_path` - Optional path to .wasm file
+/// * `contract_zkwa_path` - Optional path to .zkwa file
+/// 
+/// # Returns
+/// * `Result<ContractArtifact>` - Artifact with code and ABI
+fn load_artifact(
+    contract_pbc_path: Option<PathBuf>,
+    contract_abi_path: Option<PathBuf>,
+    contract_wasm_path: Option<PathBuf>,
+    contract_zkwa_path: Option<PathBuf>,
+) -> Result<ContractArtifact, Box<dyn std::error::Error>> {
+    match (
+        contract_pbc_path.as_ref(),
+        contract_abi_path.as_ref(),
+        contract_wasm_path.as_ref(),
+        contract_zkwa_path.as_ref(),
+    ) {
+        (None, Some(abi), Some(wasm), None) => ContractArtifact::from_files(wasm, abi),
+        (None, Some(abi), _, Some(zkwa)) => ContractArtifact::from_files(zkwa, abi),
+        (Some(pbc), _, _, _) => ContractArtifact::from_pbc(pbc),
+        _ => Err("Need either pbc or wasm + abi or zkwa + abi paths provided".into()),
+    }
+}
+
+/// Checks deployment arguments against the contract's `#[init]` signature
+/// 
+/// Placeholders are checked as if they were already replaced by an address.
+/// 
+/// # Arguments
+/// * `abi` - ABI of the contract
+/// * `args` - Deployment arguments, possibly containing placeholders
+/// 
+/// # Returns
+/// * `Result<Vec<u8>>` - Init RPC with placeholders replaced by a stand-in address
+pub fn validate_init_args(abi: &ContractAbi, args: &[String]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    abi.encode_call(abi.init_function()?, &preview_placeholders(args)?)
+}
+
+/// Estimates the gas a deployment needs
+/// 
+/// Every byte sent is charged as network fee, on top of a base cost for the deploy
+/// contract and the execution of `#[init]`. Zero-knowledge contracts also pay for
+/// allocating their ZK nodes. A margin is added to absorb fee changes.
+/// 
+/// # Arguments
+/// * `artifact` - Contract code and ABI
+/// * `init_rpc` - Serialized init call
+/// 
+/// # Returns
+/// * `i64` - Estimated gas
+pub fn estimate_deploy_gas(artifact: &ContractArtifact, init_rpc: &[u8]) -> i64 {
+    let payload_bytes: i64 =
+        (artifact.contract_bytes.len() + artifact.abi_bytes.len() + init_rpc.len()) as i64;
+    let deploy_base_gas: i64 = match artifact.kind {
+        ContractKind::Public => DEPLOY_BASE_GAS,
+        ContractKind::ZeroKnowledge => DEPLOY_BASE_GAS + ZK_NODE_ALLOCATION_GAS,
+    };
+    let gas: i64 = deploy_base_gas + INIT_BASE_GAS + payload_bytes * DEPLOY_GAS_PER_BYTE;
+    gas + gas * GAS_ESTIMATE_MARGIN_PERCENT / 100
+}
+
+/// Loads the ABI of a compiled contract from its .abi or .pbc file
+/// 
+/// # Arguments
+/// * `context` - Project the contract is compiled in
+/// * `contract_name` - Name of the contract
+/// 
+/// # Returns
+/// * `Option<ContractAbi>` - Parsed ABI if the contract has been compiled
+pub fn find_contract_abi(context: &ProjectContext, contract_name: &str) -> Option<ContractAbi> {
+    let project_root: PathBuf = context.workspace_root()?;
+    let path_to_contracts: PathBuf = find_dir(&project_root, "wasm32-unknown-unknown/release")?;
+    let paths: Vec<PathBuf> = find_paths_with_name(&path_to_contracts, contract_name);
+    let abi_bytes: Vec<u8> = match paths
+        .iter()
+        .find(|path| path.extension().unwrap_or_default() == "abi")
+    {
+        Some(abi_path) => fs::read(abi_path).ok()?,
+        None => {
+            let pbc_path: &PathBuf = paths
+                .iter()
+                .find(|path| path.extension().unwrap_or_default() == "pbc")?;
+            ContractArtifact::from_pbc(pbc_path).ok()?.abi_bytes
+        }
+    };
+    ContractAbi::from_bytes(&abi_bytes).ok()
+}
+
+/// Loads the compiled artifact of a contract from the release directory
+/// 
+/// # Arguments
+/// * `project_root` - Root directory of project
+/// * `contract_name` - Name of the contract, case insensitive
+/// 
+/// # Returns
+/// * `Result<ContractArtifact>` - Artifact with code and ABI
+pub fn find_contract_artifact(
+    project_root: &PathBuf,
+    contract_name: &str,
+) -> Result<ContractArtifact, Box<dyn std::error::Error>> {
+    let path_to_contracts: PathBuf = find_dir(project_root, "wasm32-unknown-unknown/release")
+        .ok_or("No compiled contracts found, run partizee compile first")?;
+    let paths: Vec<PathBuf> = find_paths_with_name(&path_to_contracts, contract_name);
+    let with_extension = |extension: &str| -> Option<PathBuf> {
+        paths
+            .iter()
+            .find(|path| {
+                path.extension().unwrap_or_default() == extension
+                    && path
+                        .file_stem()
+                        .and_then(|stem| stem.to_str())
+                        .is_some_and(|stem| stem.eq_ignore_ascii_case(contract_name))
+            })
+            .cloned()
+    };
+    load_artifact(
+        with_extension("pbc"),
+        with_extension("abi"),
+        with_extension("wasm"),
+        with_extension("zkwa"),
+    )
+    .map_err(|e| format!("{}: {}", contract_name, e).into())
+}
+
+/// Locates the `target/deployments` directory deployment history is written to
+/// 
+/// # Arguments
+/// * `project_root` - Root directory of project
+/// 
+/// # Returns
+/// * `Option<PathBuf>` - Deployments directory, None if the project has not been compiled
+pub fn find_deployments_dir(project_root: &PathBuf) -> Option<PathBuf> {
+    let release_dir: PathBuf = find_dir(project_root, "target/wasm32-unknown-unknown/release")?;
+    Some(release_dir.parent()?.parent()?.join("deployments"))
+}
+
+/// Loads the deployments recorded in deployment-latest.json
+/// 
+/// # Arguments
+/// * `project_root` - Root directory of project
+/// 
+/// # Returns
+/// * `Vec<Deployment>` - Recorded deployments, empty if none exist
+pub fn load_latest_deployments(project_root: &PathBuf) -> Vec<Deployment> {
+    let latest_path: Option<PathBuf> = find_deployments_dir(project_root)
+        .map(|deployment_dir| deployment_dir.join(LATEST_DEPLOYMENT_FILE));
+    latest_path
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Addresses placeholders can refer to when deploying to a network
+/// 
+/// Contracts recorded on other networks are left out, an address from another chain
+/// must never end up in the arguments.
+/// 
+/// # Arguments
+/// * `deployments` - Recorded deployments, later ones override earlier ones
+/// * `network` - Network the contracts are deployed to
+/// 
+/// # Returns
+/// * `HashMap<String, String>` - Addresses keyed by lowercase contract name
+pub fn addresses_on_network(deployments: Vec<Deployment>, network: &str) -> HashMap<String, String> {
+    deployments
+        .into_iter()
+        .filter(|deployment| deployment.network == network)
+        .map(|deployment| (deployment.name.to_lowercase(), deployment.address))
+        .collect()
+}
+
+/// Merges deployments into the recorded ones
+/// 
+/// A deployment replaces the record of the same contract on the same network,
+/// contracts that were not deployed again are kept.
+/// 
+/// # Arguments
+/// * `recorded` - Deployments recorded so far
+/// * `deployments` - New deployments
+/// 
+/// # Returns
+/// * `Vec<Deployment>` - Recorded deployments with the new ones merged in
+pub fn merge_deployments(recorded: Vec<Deployment>, deployments: Vec<Deployment>) -> Vec<Deployment> {
+    let mut merged: Vec<Deployment> = recorded;
+    for deployment in deployments {
+        match merged.iter_mut().find(|existing| {
+            existing.network == deployment.network && existing.name.eq_ignore_ascii_case(&deployment.name)
+        }) {
+            Some(existing) => *existing = deployment,
+            None => merged.push(deployment),
+        }
+    }
+    merged
+}
+
 /// Saves deployment results to JSON file
 /// 
-/// Creates deployment history by renaming existing deployment-latest.json
+/// The deployments are merged into deployment-latest.json, keyed by contract name and
+/// network. The previous deployment-latest.json is kept as deployment history.
 /// 
 /// # Arguments
 /// * `deployments` - Vector of deployment results
 /// * `project_root` - Root directory of project
 /// 
 /// # Returns
-/// * `Result<(), Box<dyn std::error::Error>>` - Ok if save succeeds
-fn save_deployments(
+/// * `Result<(), Box<dyn std::error::Error>>` - Ok if save succeeds, Error if the target directory is missing or a file cannot be written
+pub fn save_deployments(
     deployments: Vec<Deployment>,
     project_root: &PathBuf,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    // write deployment to target directory
-    let target_dir: PathBuf = find_dir(&project_root, "target/wasm32-unknown-unknown/release")
-        .unwrap_or_else(|| {
-            panic!("Failed to find target directory");
-        });
-    // pop the release directory and join the deployments directory
-    let deployment_dir: PathBuf = target_dir.parent().unwrap().join("deployments");
-    if !deployment_dir.exists() {
-        fs::create_dir_all(&deployment_dir).unwrap();
+    if deployments.is_empty() {
+        return Ok(());
     }
+    // write deployment to target directory
+    let deployment_dir: PathBuf =
+        find_deployments_dir(project_root).ok_or("Failed to find target directory")?;
+    fs::create_dir_all(&deployment_dir)
+        .map_err(|e| format!("Failed to create {}: {}", deployment_dir.display(), e))?;
+    let merged: Vec<Deployment> = merge_deployments(load_latest_deployments(project_root), deployments);
 
-    // get current deployment-latest.json and rename it to deployment-<timestamp>.json
-    let latest_path: PathBuf = deployment_dir.join("deployment-latest.json");
+    // get current deployment-latest.json and rename it to deployment-<milliseconds>.json
+    let latest_path: PathBuf = deployment_dir.join(LATEST_DEPLOYMENT_FILE);
     if latest_path.exists() {
-        let timestamp: String = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs()
-            .to_string();
-        let new_filename: PathBuf = deployment_dir.join(format!("deployment-{}.json", timestamp));
-        fs::rename(&latest_path, new_filename).unwrap_or_else(|e| {
-            eprintln!("Failed to rename deployment-latest.json: {}", e);
-            return ();
-        });
+        // snapshots are named by the millisecond they were archived at, saves within the
+        // same millisecond take the next free one so no snapshot is overwritten
+        let mut archived_at: u128 = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+        while deployment_dir.join(format!("deployment-{}.json", archived_at)).exists() {
+            archived_at += 1;
+        }
+        let new_filename: PathBuf = deployment_dir.join(format!("deployment-{}.json", archived_at));
+        // the history is not overwritten if it cannot be archived
+        fs::rename(&latest_path, &new_filename).map_err(|e| {
+            format!("Failed to archive {} as {}: {}", LATEST_DEPLOYMENT_FILE, new_filename.display(), e)
+        })?;
     }
 
-    if deployments.len() > 0 {
-        let deployments_json: String = serde_json::to_string(&deployments).unwrap_or_else(|e| {
-            eprintln!("Failed to write deployment: {}", e);
-            return "".to_string();
-        });
-        fs::write(&latest_path, deployments_json).unwrap_or_else(|e| {
-            eprintln!("Failed to write deployment: {}", e);
-            return ();
-        });
-    }
-    Ok(())
+    let deployments_json: String = serde_json::to_string(&merged)?;
+    fs::write(&latest_path, deployments_json)
+        .map_err(|e| format!("Failed to write {}: {}", latest_path.display(), e).into())
 }
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::client::transaction::tests::start_mock_node;
     use crate::utils::utils::setup_test_environment;
 
-    fn cleanup(original_dir: PathBuf) {
-        std::env::set_current_dir(original_dir).unwrap();
-    }
     #[test]
     fn test_create_default_deployment_with_account() {
-        let (temp_dir, temp_path, original_dir) = setup_test_environment();
-        std::env::set_current_dir(&temp_path).unwrap();
+        let (temp_dir, temp_path) = setup_test_environment();
         let path_to_pk: PathBuf = temp_path.join("00d277aa1bf5702ab9fc690b04bd68b5a981095530.pk");
         // get pk files
         // create new project
-        let deployment_with_account: DeploymentWithProfile = DeploymentWithProfile::default();
+        let deployment_with_account: DeploymentWithProfile =
+            DeploymentWithProfile::for_project(ProjectContext::at(&temp_path));
         assert!(deployment_with_account.deploy_configs.path_to_pk.is_file());
         assert_eq!(
             deployment_with_account.account.path_to_pk.is_file().clone(),
@@ -538,13 +1232,107 @@ mod tests {
                 .exists(),
             true
         );
-        cleanup(original_dir);
+    }
+
+    #[test]
+    fn test_resumes_only_matching_runs() {
+        let (_temp_dir, temp_path) = setup_test_environment();
+        let deployment_with_account: DeploymentWithProfile =
+            DeploymentWithProfile::for_project(ProjectContext::at(&temp_path));
+        let address: &str = &deployment_with_account.account.address;
+        let names: Vec<String> = vec!["counterV1".to_string()];
+        let journal = |network: &str, deployer: &str| {
+            DeployJournal::create(&temp_path, network, deployer, &names).unwrap()
+        };
+
+        assert!(deployment_with_account.resumes(&journal("testnet", address)));
+        assert!(!deployment_with_account.resumes(&journal("mainnet", address)));
+        assert!(!deployment_with_account.resumes(&journal("testnet", "00aa")));
+    }
+
+    #[test]
+    fn test_check_sent_deployment_does_not_redeploy() {
+        let (_temp_dir, temp_path) = setup_test_environment();
+        let mut deployer = DeploymentWithProfile::for_project(ProjectContext::at(&temp_path));
+        let deployment: Deployment = Deployment {
+            name: "counterV1".to_string(),
+            network: "testnet".to_string(),
+            address: "02aa".to_string(),
+            transaction_hash: "bb".repeat(32),
+            args: Vec::new(),
+            timestamp: (now_millis() / 1000).to_string(),
+            code_version: None,
+            build_info: None,
+        };
+        let sent: SentTransaction = SentTransaction {
+            transaction_hash: "bb".repeat(32),
+            destination_shard: Some("Shard1".to_string()),
+        };
+
+        // the interrupted run's transaction succeeded, the journaled deployment is kept
+        let (endpoint, handle) = start_mock_node(vec![
+            r#"{"chainId": "test chain"}"#.to_string(),
+            r#"{"executionStatus": {"blockId": "b0", "success": true, "finalized": true}}"#.to_string(),
+        ]);
+        deployer.deploy_configs.network = endpoint;
+        let checked = deployer.check_sent_deployment(deployment.clone(), &sent).unwrap();
+        assert_eq!(checked.unwrap().address, "02aa");
+        let requests = handle.join().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1].starts_with(&format!("GET /chain/shards/Shard1/transactions/{}", "bb".repeat(32))));
+
+        // it failed on chain, so the contract has to be deployed again
+        let (endpoint, _handle) = start_mock_node(vec![
+            r#"{"chainId": "test chain"}"#.to_string(),
+            r#"{"block": "b0", "executionSucceeded": false, "failureCause": {"errorMessage": "out of gas"}}"#
+                .to_string(),
+        ]);
+        deployer.deploy_configs.network = endpoint;
+        assert!(deployer.check_sent_deployment(deployment, &sent).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_run_addresses_ignore_stale_deployments_of_other_groups() {
+        let (_temp_dir, temp_path) = setup_test_environment();
+        let group = |names: &[&str], args: &[(&str, &str)]| {
+            let mut deployer = DeploymentWithProfile::for_project(ProjectContext::at(&temp_path));
+            deployer.deploy_configs.contract_names = names.iter().map(|name| name.to_string()).collect();
+            deployer.deploy_configs.deployer_args = args
+                .iter()
+                .map(|(name, arg)| (name.to_string(), vec![arg.to_string()]))
+                .collect();
+            deployer
+        };
+        // two profiles deploy to testnet, the swap group references the token group's contract
+        let deployers: Vec<DeploymentWithProfile> = vec![
+            group(&["swap"], &[("swap", "${token.address}")]),
+            group(&["Token"], &[]),
+        ];
+        let mut run: RunAddresses = RunAddresses::new(&deployers);
+        let ordered: Vec<DeploymentWithProfile> = order_deploy_groups(deployers).unwrap();
+        assert_eq!(ordered[0].deploy_configs.contract_names, vec!["Token"]);
+        assert_eq!(ordered[1].deploy_configs.contract_names, vec!["swap"]);
+
+        // a token recorded by an earlier run is not used until this run deployed it
+        let mut addresses: HashMap<String, String> =
+            HashMap::from([("token".to_string(), "02old".to_string())]);
+        run.resolve("testnet", &mut addresses);
+        assert!(!addresses.contains_key("token"));
+
+        run.record("testnet", "Token", "02new");
+        run.resolve("testnet", &mut addresses);
+        assert_eq!(addresses["token"], "02new");
+
+        // recorded addresses on other networks are left alone
+        let mut mainnet: HashMap<String, String> =
+            HashMap::from([("token".to_string(), "02main".to_string())]);
+        run.resolve("mainnet", &mut mainnet);
+        assert_eq!(mainnet["token"], "02main");
     }
 
     #[test]
     fn test_build_contract_file_map() {
-        let (_, temp_path, original_dir) = setup_test_environment();
-        let _ = std::env::set_current_dir(&temp_path);
+        let (_temp_dir, temp_path) = setup_test_environment();
         let contract_dir: PathBuf = temp_path.join("rust/contracts");
         let _ =fs::create_dir_all(&contract_dir);
         let contract1_path: PathBuf = contract_dir.join("Contract1.pbc");
@@ -556,14 +1344,12 @@ mod tests {
         assert_eq!(map.len(), 2);
         assert_eq!(map.get("contract1").unwrap(), &contract1_path.clone());
         assert_eq!(map.get("contract2").unwrap(), &contract2_path.clone());
-        cleanup(original_dir);
     }
 
     #[test]
     #[allow(unused_variables)]
     fn test_get_deployer_args() {
-        let (temp_dir, temp_path, original_dir) = setup_test_environment();
-        let _ = std::env::set_current_dir(&temp_path);
+        let (temp_dir, temp_path) = setup_test_environment();
         let contract_dir: PathBuf = temp_path.join("rust/contracts");
         let _ =fs::create_dir_all(&contract_dir);
         let contract1_path: PathBuf = contract_dir.join("Contract1.pbc");
@@ -582,8 +1368,12 @@ mod tests {
             contract_names: vec!["contract1".to_string(), "contract2".to_string()],
             deployer_args,
             path_to_pk: pk_path.unwrap(),
+            artifacts: HashMap::new(),
+            gas_limits: HashMap::new(),
+            confirm_mainnet: false,
         };
-        let deployment_with_account: DeploymentWithProfile = DeploymentWithProfile::new(deployer);
+        let deployment_with_account: DeploymentWithProfile =
+            DeploymentWithProfile::new(deployer, ProjectContext::at(&temp_path));
         let args: Vec<String> = deployment_with_account.get_deployer_args_for_name("Contract1").unwrap();
         assert_eq!(args, vec!["arg1".to_string(), "arg2".to_string()]);
         let args: Vec<String> = deployment_with_account.get_deployer_args_for_name("Contract2").unwrap();
@@ -593,7 +1383,113 @@ mod tests {
         assert_eq!(map.len(), 2);
         assert_eq!(map.get("contract1").unwrap(), &contract1_path.clone());
         assert_eq!(map.get("contract2").unwrap(), &contract2_path.clone());
-        cleanup(original_dir);
+    }
+
+    fn deployment(name: &str, network: &str, address: &str) -> Deployment {
+        Deployment {
+            name: name.to_string(),
+            network: network.to_string(),
+            address: address.to_string(),
+            transaction_hash: String::new(),
+            args: Vec::new(),
+            timestamp: "1700000000".to_string(),
+            code_version: None,
+            build_info: None,
+        }
+    }
+
+    #[test]
+    fn test_addresses_on_network() {
+        let recorded: Vec<Deployment> = vec![
+            deployment("Token", "testnet", "02aa"),
+            deployment("Token", "mainnet", "02bb"),
+            deployment("Registry", "testnet", "02cc"),
+        ];
+        let addresses: HashMap<String, String> = addresses_on_network(recorded, "mainnet");
+        assert_eq!(addresses.get("token"), Some(&"02bb".to_string()));
+        assert_eq!(addresses.get("registry"), None);
+        assert!(substitute_placeholders(&["${Registry.address}".to_string()], &addresses).is_err());
+    }
+
+    #[test]
+    fn test_save_deployments_reports_write_errors() {
+        let (_temp_dir, temp_path) = setup_test_environment();
+        // a file where the deployments directory belongs cannot be written into
+        let deployments_dir: PathBuf = find_deployments_dir(&temp_path).unwrap();
+        fs::write(&deployments_dir, "not a directory").unwrap();
+        let error = save_deployments(vec![deployment("counterV1", "testnet", "02aa")], &temp_path)
+            .unwrap_err()
+            .to_string();
+        assert!(error.starts_with("Failed to create"), "unexpected error: {}", error);
+    }
+
+    #[test]
+    fn test_save_deployments_merges_groups() {
+        let (_temp_dir, temp_path) = setup_test_environment();
+        save_deployments(vec![deployment("counterV1", "testnet", "02aa")], &temp_path).unwrap();
+        save_deployments(
+            vec![
+                deployment("counterV1", "mainnet", "02bb"),
+                deployment("counterV2", "testnet", "02cc"),
+            ],
+            &temp_path,
+        )
+        .unwrap();
+        save_deployments(vec![deployment("CounterV1", "testnet", "02dd")], &temp_path).unwrap();
+        save_deployments(Vec::new(), &temp_path).unwrap();
+        // saves within the same second each keep their own snapshot
+        let history = DeploymentHistory::load(&find_deployments_dir(&temp_path).unwrap()).unwrap();
+        assert_eq!(history.snapshots.len(), 3);
+
+        let latest: Vec<(String, String, String)> = load_latest_deployments(&temp_path)
+            .into_iter()
+            .map(|deployment| (deployment.name, deployment.network, deployment.address))
+            .collect();
+        assert_eq!(
+            latest,
+            vec![
+                ("CounterV1".to_string(), "testnet".to_string(), "02dd".to_string()),
+                ("counterV1".to_string(), "mainnet".to_string(), "02bb".to_string()),
+                ("counterV2".to_string(), "testnet".to_string(), "02cc".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_init_args() {
+        let abi = ContractAbi::from_bytes(&crate::utils::abi::tests::counter_abi_bytes()).unwrap();
+        assert!(validate_init_args(&abi, &["00d277aa1bf5702ab9fc690b04bd68b5a981095530".to_string()]).is_ok());
+        assert!(validate_init_args(&abi, &["${counterV1.address}".to_string()]).is_ok());
+        assert!(validate_init_args(&abi, &[]).is_err());
+        assert!(validate_init_args(&abi, &["42".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_estimate_deploy_gas() {
+        let small = ContractArtifact {
+            kind: ContractKind::Public,
+            contract_bytes: vec![0; 1_000],
+            abi_bytes: vec![0; 100],
+        };
+        let large = ContractArtifact {
+            kind: ContractKind::Public,
+            contract_bytes: vec![0; 100_000],
+            abi_bytes: vec![0; 100],
+        };
+        let small_gas = estimate_deploy_gas(&small, &[0; 26]);
+        assert_eq!(
+            small_gas,
+            (DEPLOY_BASE_GAS + INIT_BASE_GAS + 1_126 * DEPLOY_GAS_PER_BYTE) * 120 / 100
+        );
+        assert!(estimate_deploy_gas(&large, &[0; 26]) > small_gas);
+        let zk = ContractArtifact {
+            kind: ContractKind::ZeroKnowledge,
+            ..small
+        };
+        assert_eq!(
+            estimate_deploy_gas(&zk, &[0; 26]) - small_gas,
+            ZK_NODE_ALLOCATION_GAS * 120 / 100
+        );
     }
     
 }
@@ -0,0 +1,646 @@
+use crate::commands::deploy::{find_deployments_dir, Deployment, LATEST_DEPLOYMENT_FILE};
+use crate::utils::context::ProjectContext;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Snapshot id of deployment-latest.json
+pub const LATEST_SNAPSHOT_ID: &str = "latest";
+
+/// Deployments recorded by a single deploy run
+///
+/// # Fields
+/// * `id` - `latest` or the timestamp the snapshot was archived at
+/// * `deployments` - Contracts deployed in the run
+#[derive(Debug, Clone)]
+pub struct DeploymentSnapshot {
+    pub id: String,
+    pub deployments: Vec<Deployment>,
+}
+
+/// Live contracts keyed by network and lowercase contract name
+type DeploymentState<'a> = BTreeMap<(String, String), &'a Deployment>;
+
+/// Every recorded deploy run of a project, oldest first
+///
+/// # Fields
+/// * `snapshots` - Snapshots ordered from oldest to latest
+#[derive(Debug, Clone, Default)]
+pub struct DeploymentHistory {
+    pub snapshots: Vec<DeploymentSnapshot>,
+}
+
+/// Difference of a contract between two snapshots
+#[derive(Debug, Clone)]
+pub enum DeploymentChange {
+    Added(Deployment),
+    Removed(Deployment),
+    Changed { from: Box<Deployment>, to: Box<Deployment> },
+}
+
+/// Formats deployment history can be exported to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl ExportFormat {
+    /// Parses a format name
+    ///
+    /// # Arguments
+    /// * `name` - `json`, `csv` or `markdown`
+    ///
+    /// # Returns
+    /// * `Result<ExportFormat>` - Parsed format
+    pub fn parse(name: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        match name.to_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            "markdown" | "md" => Ok(Self::Markdown),
+            _ => Err(format!("Unknown export format {}, expected json, csv or markdown", name).into()),
+        }
+    }
+}
+
+impl DeploymentHistory {
+    /// Loads every snapshot in a deployments directory
+    ///
+    /// # Arguments
+    /// * `deployments_dir` - Directory containing deployment-*.json files
+    ///
+    /// # Returns
+    /// * `Result<DeploymentHistory>` - History, empty if the directory does not exist
+    pub fn load(deployments_dir: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        if !deployments_dir.is_dir() {
+            return Ok(Self::default());
+        }
+        let mut archived: Vec<(u64, DeploymentSnapshot)> = Vec::new();
+        let mut latest: Option<DeploymentSnapshot> = None;
+        for entry in fs::read_dir(deployments_dir)? {
+            let path: PathBuf = entry?.path();
+            let file_name: String = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default()
+                .to_string();
+            let id: &str = match file_name
+                .strip_prefix("deployment-")
+                .and_then(|name| name.strip_suffix(".json"))
+            {
+                Some(id) => id,
+                None => continue,
+            };
+            let contents: String = fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            let deployments: Vec<Deployment> = serde_json::from_str(&contents)
+                .map_err(|e| format!("Invalid deployment file {}: {}", path.display(), e))?;
+            if file_name == LATEST_DEPLOYMENT_FILE {
+                latest = Some(DeploymentSnapshot {
+                    id: LATEST_SNAPSHOT_ID.to_string(),
+                    deployments,
+                });
+            } else if let Ok(timestamp) = id.parse::<u64>() {
+                archived.push((
+                    timestamp,
+                    DeploymentSnapshot {
+                        id: id.to_string(),
+                        deployments,
+                    },
+                ));
+            }
+        }
+        archived.sort_by_key(|(timestamp, _)| *timestamp);
+        let mut snapshots: Vec<DeploymentSnapshot> =
+            archived.into_iter().map(|(_, snapshot)| snapshot).collect();
+        snapshots.extend(latest);
+        Ok(Self { snapshots })
+    }
+
+    /// Loads the history of a project
+    ///
+    /// # Arguments
+    /// * `context` - Directory the command works on
+    ///
+    /// # Returns
+    /// * `Result<DeploymentHistory>` - History, empty if nothing has been deployed
+    pub fn for_project(context: &ProjectContext) -> Result<Self, Box<dyn std::error::Error>> {
+        let project_root: PathBuf = context.require_workspace_root()?;
+        match find_deployments_dir(&project_root) {
+            Some(deployments_dir) => Self::load(&deployments_dir),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Every recorded deployment with the snapshot it was first recorded in, oldest first
+    ///
+    /// Snapshots carry over the contracts that were not redeployed, those are only listed once.
+    pub fn records(&self) -> Vec<(&DeploymentSnapshot, &Deployment)> {
+        let mut seen: HashSet<(&str, String, &str, &str)> = HashSet::new();
+        self.snapshots
+            .iter()
+            .flat_map(|snapshot| {
+                snapshot
+                    .deployments
+                    .iter()
+                    .map(move |deployment| (snapshot, deployment))
+            })
+            .filter(|(_, deployment)| {
+                seen.insert((
+                    deployment.network.as_str(),
+                    deployment.name.to_lowercase(),
+                    deployment.address.as_str(),
+                    deployment.timestamp.as_str(),
+                ))
+            })
+            .collect()
+    }
+
+    /// Most recent deployment of a contract
+    ///
+    /// # Arguments
+    /// * `contract` - Contract name, case insensitive
+    /// * `snapshot_id` - Optional snapshot to search instead of the whole history
+    /// * `network` - Optional network the contract has to be deployed on
+    ///
+    /// # Returns
+    /// * `Option<&Deployment>` - Newest matching deployment
+    pub fn latest(
+        &self,
+        contract: &str,
+        snapshot_id: Option<&str>,
+        network: Option<&str>,
+    ) -> Option<&Deployment> {
+        self.snapshots
+            .iter()
+            .rev()
+            .filter(|snapshot| match snapshot_id {
+                Some(id) => snapshot.id == id,
+                None => true,
+            })
+            .flat_map(|snapshot| snapshot.deployments.iter().rev())
+            .filter(|deployment| network.is_none_or(|network| deployment.network == network))
+            .find(|deployment| deployment.name.eq_ignore_ascii_case(contract))
+    }
+
+    /// Deployed contracts as of a snapshot, keyed by network and lowercase name
+    ///
+    /// Each run only records the contracts it deployed, so earlier snapshots are
+    /// folded in to get the full set of live contracts.
+    fn state_at(&self, id: &str) -> Result<DeploymentState<'_>, Box<dyn std::error::Error>> {
+        let index: usize = self
+            .snapshots
+            .iter()
+            .position(|snapshot| snapshot.id == id)
+            .ok_or_else(|| format!("No deployment snapshot {}", id))?;
+        let mut state: DeploymentState = BTreeMap::new();
+        for snapshot in &self.snapshots[..=index] {
+            for deployment in &snapshot.deployments {
+                state.insert(
+                    (deployment.network.clone(), deployment.name.to_lowercase()),
+                    deployment,
+                );
+            }
+        }
+        Ok(state)
+    }
+
+    /// Compares the deployed contracts of two snapshots
+    ///
+    /// # Arguments
+    /// * `from` - Older snapshot id, defaults to the snapshot before `to`
+    /// * `to` - Newer snapshot id, defaults to the latest snapshot
+    ///
+    /// # Returns
+    /// * `Result<Vec<DeploymentChange>>` - Contracts added, removed or redeployed
+    pub fn diff(
+        &self,
+        from: Option<&str>,
+        to: Option<&str>,
+    ) -> Result<Vec<DeploymentChange>, Box<dyn std::error::Error>> {
+        let to_id: String = match to {
+            Some(id) => id.to_string(),
+            None => self
+                .snapshots
+                .last()
+                .map(|snapshot| snapshot.id.clone())
+                .ok_or("No deployments recorded")?,
+        };
+        let from_id: String = match from {
+            Some(id) => id.to_string(),
+            None => {
+                let to_index: usize = self
+                    .snapshots
+                    .iter()
+                    .position(|snapshot| snapshot.id == to_id)
+                    .ok_or_else(|| format!("No deployment snapshot {}", to_id))?;
+                if to_index == 0 {
+                    return Err(format!("Snapshot {} has no earlier snapshot to compare with", to_id).into());
+                }
+                self.snapshots[to_index - 1].id.clone()
+            }
+        };
+        let from_state = self.state_at(&from_id)?;
+        let to_state = self.state_at(&to_id)?;
+
+        let mut changes: Vec<DeploymentChange> = Vec::new();
+        for (key, from_deployment) in &from_state {
+            match to_state.get(key) {
+                None => changes.push(DeploymentChange::Removed((*from_deployment).clone())),
+                Some(to_deployment) if to_deployment.address != from_deployment.address => {
+                    changes.push(DeploymentChange::Changed {
+                        from: Box::new((*from_deployment).clone()),
+                        to: Box::new((*to_deployment).clone()),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+        for (key, to_deployment) in &to_state {
+            if !from_state.contains_key(key) {
+                changes.push(DeploymentChange::Added((*to_deployment).clone()));
+            }
+        }
+        Ok(changes)
+    }
+
+    /// Renders every recorded deployment
+    ///
+    /// # Arguments
+    /// * `format` - Output format
+    ///
+    /// # Returns
+    /// * `String` - Exported history
+    pub fn export(&self, format: ExportFormat) -> String {
+        let records = self.records();
+        match format {
+            ExportFormat::Json => {
+                // the whole record is exported, including the code version and build it links to
+                let rows: Vec<Value> = records
+                    .iter()
+                    .map(|(snapshot, deployment)| {
+                        let mut row: Value = serde_json::to_value(deployment).unwrap_or_default();
+                        row["snapshot"] = json!(snapshot.id);
+                        row
+                    })
+                    .collect();
+                serde_json::to_string_pretty(&rows).unwrap_or_default() + "\n"
+            }
+            ExportFormat::Csv => {
+                let mut output: String =
+                    "snapshot,network,name,address,transaction_hash,args,timestamp,code_version,build_info,built_at,git_commit\n".to_string();
+                for (snapshot, deployment) in records {
+                    let build_info = deployment.build_info.as_ref();
+                    let fields: Vec<String> = vec![
+                        snapshot.id.clone(),
+                        deployment.network.clone(),
+                        deployment.name.clone(),
+                        deployment.address.clone(),
+                        deployment.transaction_hash.clone(),
+                        deployment.args.join(" "),
+                        deployment.timestamp.clone(),
+                        deployment.code_version.clone().unwrap_or_default(),
+                        build_info.map(|link| link.path.display().to_string()).unwrap_or_default(),
+                        build_info.map(|link| link.built_at.to_string()).unwrap_or_default(),
+                        build_info.and_then(|link| link.git_commit.clone()).unwrap_or_default(),
+                    ];
+                    let escaped: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+                    output.push_str(&escaped.join(","));
+                    output.push('\n');
+                }
+                output
+            }
+            ExportFormat::Markdown => {
+                let mut output: String = "| Snapshot | Network | Contract | Address | Transaction | Args | Deployed | Code | Commit |\n|---|---|---|---|---|---|---|---|---|\n".to_string();
+                for (snapshot, deployment) in records {
+                    let commit: &str = deployment
+                        .build_info
+                        .as_ref()
+                        .and_then(|link| link.git_commit.as_deref())
+                        .unwrap_or("");
+                    output.push_str(&format!(
+                        "| {} | {} | {} | `{}` | `{}` | {} | {} | {} | {} |\n",
+                        snapshot.id,
+                        display_network(deployment),
+                        deployment.name,
+                        deployment.address,
+                        deployment.transaction_hash,
+                        deployment.args.join(" ").replace('|', "\\|"),
+                        format_timestamp(&deployment.timestamp),
+                        deployment.code_version.as_deref().unwrap_or(&deployment.name),
+                        commit
+                    ));
+                }
+                output
+            }
+        }
+    }
+}
+
+/// Prints every recorded deployment, oldest first
+///
+/// # Arguments
+/// * `history` - Deployment history
+pub fn print_deployment_list(history: &DeploymentHistory) {
+    let records = history.records();
+    if records.is_empty() {
+        println!("No deployments recorded yet, run partizee deploy first");
+        return;
+    }
+    println!(
+        "{:<12} {:<10} {:<24} {:<44} DEPLOYED",
+        "SNAPSHOT", "NETWORK", "CONTRACT", "ADDRESS"
+    );
+    for (snapshot, deployment) in records {
+        println!(
+            "{:<12} {:<10} {:<24} {:<44} {}",
+            snapshot.id,
+            display_network(deployment),
+            deployment.name,
+            deployment.address,
+            format_timestamp(&deployment.timestamp)
+        );
+    }
+}
+
+/// Prints a single deployment in detail
+///
+/// # Arguments
+/// * `deployment` - Deployment to print
+pub fn print_deployment(deployment: &Deployment) {
+    println!("Contract:    {}", deployment.name);
+    println!("Network:     {}", display_network(deployment));
+    println!("Address:     {}", deployment.address);
+    if let Some(code_version) = deployment.code_version.as_ref() {
+        println!("Upgraded to: {}", code_version);
+    }
+    println!("Transaction: {}", deployment.transaction_hash);
+    println!("Arguments:   {}", deployment.args.join(" "));
+    println!("Deployed:    {}", format_timestamp(&deployment.timestamp));
+    if let Some(build_info) = deployment.build_info.as_ref() {
+        println!(
+            "Built:       {} from commit {} ({})",
+            format_timestamp(&build_info.built_at.to_string()),
+            build_info.git_commit.as_deref().unwrap_or("unknown"),
+            build_info.path.display()
+        );
+    }
+}
+
+/// Prints the changes between two snapshots
+///
+/// # Arguments
+/// * `changes` - Changes returned by `DeploymentHistory::diff`
+pub fn print_deployment_diff(changes: &[DeploymentChange]) {
+    if changes.is_empty() {
+        println!("No changes");
+    }
+    for change in changes {
+        match change {
+            DeploymentChange::Added(deployment) => println!(
+                "+ {} ({}) {}",
+                deployment.name,
+                display_network(deployment),
+                deployment.address
+            ),
+            DeploymentChange::Removed(deployment) => println!(
+                "- {} ({}) {}",
+                deployment.name,
+                display_network(deployment),
+                deployment.address
+            ),
+            DeploymentChange::Changed { from, to } => println!(
+                "~ {} ({}) {} -> {}",
+                to.name,
+                display_network(to),
+                from.address,
+                to.address
+            ),
+        }
+    }
+}
+
+/// Network of a deployment, records written before networks were tracked show as unknown
+fn display_network(deployment: &Deployment) -> &str {
+    if deployment.network.is_empty() {
+        "unknown"
+    } else {
+        &deployment.network
+    }
+}
+
+/// Quotes a CSV field when it contains a separator, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Formats unix seconds as a UTC date and time
+///
+/// # Arguments
+/// * `timestamp` - Unix timestamp in seconds
+///
+/// # Returns
+/// * `String` - `YYYY-MM-DD HH:MM:SS UTC`, or the input if it is not a timestamp
+fn format_timestamp(timestamp: &str) -> String {
+    let seconds: i64 = match timestamp.parse() {
+        Ok(seconds) => seconds,
+        Err(_) => return timestamp.to_string(),
+    };
+    let days: i64 = seconds.div_euclid(86_400);
+    let time_of_day: i64 = seconds.rem_euclid(86_400);
+    // civil date from days since the unix epoch
+    let shifted: i64 = days + 719_468;
+    let era: i64 = shifted.div_euclid(146_097);
+    let day_of_era: i64 = shifted.rem_euclid(146_097);
+    let year_of_era: i64 =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year: i64 = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index: i64 = (5 * day_of_year + 2) / 153;
+    let day: i64 = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month: i64 = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year: i64 = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        time_of_day / 3_600,
+        time_of_day % 3_600 / 60,
+        time_of_day % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::build_info::BuildInfoLink;
+    use tempfile::TempDir;
+
+    fn deployment(name: &str, address: &str, timestamp: &str) -> Deployment {
+        Deployment {
+            name: name.to_string(),
+            network: "testnet".to_string(),
+            address: address.to_string(),
+            transaction_hash: "aa".repeat(32),
+            args: vec!["${profile.address}".to_string()],
+            timestamp: timestamp.to_string(),
+            code_version: None,
+            build_info: None,
+        }
+    }
+
+    fn write_snapshot(dir: &Path, file_name: &str, deployments: &[Deployment]) {
+        fs::write(dir.join(file_name), serde_json::to_string(deployments).unwrap()).unwrap();
+    }
+
+    fn history() -> (TempDir, DeploymentHistory) {
+        let temp_dir = TempDir::new().unwrap();
+        write_snapshot(
+            temp_dir.path(),
+            "deployment-1700000100.json",
+            &[deployment("counterV2", "02bb", "1700000050")],
+        );
+        write_snapshot(
+            temp_dir.path(),
+            "deployment-1700000000.json",
+            &[
+                deployment("counterV1", "02aa", "1699999990"),
+                deployment("counterV2", "02b0", "1699999991"),
+            ],
+        );
+        write_snapshot(
+            temp_dir.path(),
+            LATEST_DEPLOYMENT_FILE,
+            &[deployment("counterV3", "02cc", "1700000200")],
+        );
+        fs::write(temp_dir.path().join("notes.txt"), "ignored").unwrap();
+        let history = DeploymentHistory::load(temp_dir.path()).unwrap();
+        (temp_dir, history)
+    }
+
+    #[test]
+    fn test_load_history_in_order() {
+        let (_temp_dir, history) = history();
+        let ids: Vec<&str> = history.snapshots.iter().map(|snapshot| snapshot.id.as_str()).collect();
+        assert_eq!(ids, vec!["1700000000", "1700000100", LATEST_SNAPSHOT_ID]);
+        assert_eq!(history.records().len(), 4);
+        assert!(DeploymentHistory::load(Path::new("/does/not/exist"))
+            .unwrap()
+            .snapshots
+            .is_empty());
+    }
+
+    #[test]
+    fn test_records_skip_carried_over_deployments() {
+        let temp_dir = TempDir::new().unwrap();
+        let counter_v1: Deployment = deployment("counterV1", "02aa", "1699999990");
+        write_snapshot(temp_dir.path(), "deployment-1700000000.json", &[counter_v1.clone()]);
+        write_snapshot(
+            temp_dir.path(),
+            LATEST_DEPLOYMENT_FILE,
+            &[counter_v1, deployment("counterV2", "02bb", "1700000050")],
+        );
+        let history = DeploymentHistory::load(temp_dir.path()).unwrap();
+        let records: Vec<(&str, &str)> = history
+            .records()
+            .into_iter()
+            .map(|(snapshot, deployment)| (snapshot.id.as_str(), deployment.name.as_str()))
+            .collect();
+        assert_eq!(records, vec![("1700000000", "counterV1"), (LATEST_SNAPSHOT_ID, "counterV2")]);
+        assert_eq!(history.latest("counterV1", Some(LATEST_SNAPSHOT_ID), None).unwrap().address, "02aa");
+    }
+
+    #[test]
+    fn test_latest_address() {
+        let (_temp_dir, history) = history();
+        assert_eq!(history.latest("counterv2", None, None).unwrap().address, "02bb");
+        assert_eq!(
+            history.latest("counterV2", Some("1700000000"), None).unwrap().address,
+            "02b0"
+        );
+        assert!(history.latest("missing", None, None).is_none());
+
+        let temp_dir = TempDir::new().unwrap();
+        let on_mainnet = Deployment {
+            network: "mainnet".to_string(),
+            ..deployment("counterV1", "02bb", "1700000100")
+        };
+        write_snapshot(
+            temp_dir.path(),
+            LATEST_DEPLOYMENT_FILE,
+            &[deployment("counterV1", "02aa", "1700000000"), on_mainnet],
+        );
+        let history = DeploymentHistory::load(temp_dir.path()).unwrap();
+        assert_eq!(history.latest("counterV1", None, None).unwrap().address, "02bb");
+        assert_eq!(history.latest("counterV1", None, Some("testnet")).unwrap().address, "02aa");
+        assert!(history.latest("counterV1", None, Some("http://localhost:8080")).is_none());
+    }
+
+    #[test]
+    fn test_diff_snapshots() {
+        let (_temp_dir, history) = history();
+        let changes = history.diff(None, None).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert!(matches!(&changes[0], DeploymentChange::Added(deployment) if deployment.name == "counterV3"));
+
+        let changes = history.diff(Some("1700000000"), Some(LATEST_SNAPSHOT_ID)).unwrap();
+        assert_eq!(changes.len(), 2);
+        assert!(changes.iter().any(|change| matches!(change,
+            DeploymentChange::Changed { from, to } if from.address == "02b0" && to.address == "02bb")));
+        assert!(history.diff(None, Some("1700000000")).is_err());
+        assert!(history.diff(Some("nope"), None).is_err());
+    }
+
+    #[test]
+    fn test_export_formats() {
+        let (_temp_dir, history) = history();
+        let exported: Value = serde_json::from_str(&history.export(ExportFormat::Json)).unwrap();
+        assert_eq!(exported.as_array().unwrap().len(), 4);
+        assert_eq!(exported[3]["snapshot"], LATEST_SNAPSHOT_ID);
+
+        let csv = history.export(ExportFormat::Csv);
+        assert_eq!(csv.lines().count(), 5);
+        assert!(csv.lines().nth(1).unwrap().starts_with("1700000000,testnet,counterV1,02aa,"));
+
+        let markdown = history.export(ExportFormat::Markdown);
+        assert!(markdown.contains("| latest | testnet | counterV3 | `02cc` |"));
+
+        let temp_dir = TempDir::new().unwrap();
+        let upgraded = Deployment {
+            code_version: Some("counterV2".to_string()),
+            build_info: Some(BuildInfoLink {
+                path: PathBuf::from("target/wasm32-unknown-unknown/release/build-info.json"),
+                built_at: 1700000000,
+                git_commit: Some("abc1234".to_string()),
+            }),
+            ..deployment("counterV1", "02aa", "1700000100")
+        };
+        write_snapshot(temp_dir.path(), LATEST_DEPLOYMENT_FILE, &[upgraded]);
+        let history = DeploymentHistory::load(temp_dir.path()).unwrap();
+        let exported: Value = serde_json::from_str(&history.export(ExportFormat::Json)).unwrap();
+        assert_eq!(exported[0]["code_version"], "counterV2");
+        assert_eq!(exported[0]["build_info"]["git_commit"], "abc1234");
+        let csv = history.export(ExportFormat::Csv);
+        assert!(csv.lines().nth(1).unwrap().ends_with(
+            ",counterV2,target/wasm32-unknown-unknown/release/build-info.json,1700000000,abc1234"
+        ));
+        assert!(history.export(ExportFormat::Markdown).contains("| counterV2 | abc1234 |"));
+        assert_eq!(ExportFormat::parse("md").unwrap(), ExportFormat::Markdown);
+        assert!(ExportFormat::parse("xml").is_err());
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp("0"), "1970-01-01 00:00:00 UTC");
+        assert_eq!(format_timestamp("1700000000"), "2023-11-14 22:13:20 UTC");
+        assert_eq!(format_timestamp("1709251199"), "2024-02-29 23:59:59 UTC");
+        assert_eq!(format_timestamp("soon"), "soon");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+    }
+}
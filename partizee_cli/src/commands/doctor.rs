@@ -0,0 +1,516 @@
+use crate::client::transaction::TransactionClient;
+use crate::utils::constants::{REQUIRED_JAVA_VERSION, REQUIRED_RUST_VERSION, WASM_TARGET};
+use crate::utils::context::ProjectContext;
+use crate::utils::fs_nav::{get_pk_files, id_pbc_path};
+use crate::utils::manifest::{DeployManifest, MANIFEST_FILE_NAME};
+use crate::utils::utils::{block_on, command_output, get_rpc_endpoint, known_chain_id, OutputStream};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Outcome of a single doctor check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    /// Nothing to fix
+    Ok,
+    /// Works, but may cause problems later
+    Warning,
+    /// Has to be fixed before contracts can be built or deployed
+    Failed,
+}
+
+/// Result of checking one part of the environment
+///
+/// # Fields
+/// * `name` - What was checked
+/// * `status` - Outcome of the check
+/// * `detail` - What was found
+/// * `remediation` - How to fix a warning or failure
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DoctorCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+    pub remediation: Option<String>,
+}
+
+impl DoctorCheck {
+    fn ok(name: &str, detail: String) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Ok,
+            detail,
+            remediation: None,
+        }
+    }
+
+    fn warning(name: &str, detail: String, remediation: String) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Warning,
+            detail,
+            remediation: Some(remediation),
+        }
+    }
+
+    fn failed(name: &str, detail: String, remediation: String) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Failed,
+            detail,
+            remediation: Some(remediation),
+        }
+    }
+}
+
+/// Checks the toolchain, keys and network configuration a project depends on
+///
+/// # Fields
+/// * `context` - Project the checks run for, tools are run from its directory so toolchain overrides apply
+/// * `offline` - Skip contacting the network endpoints
+pub struct Doctor {
+    pub context: ProjectContext,
+    pub offline: bool,
+}
+
+impl Doctor {
+    /// Runs every check
+    ///
+    /// # Returns
+    /// * `Vec<DoctorCheck>` - One result per check, in the order they ran
+    pub fn run(&self) -> Vec<DoctorCheck> {
+        let mut checks: Vec<DoctorCheck> = vec![
+            self.check_rustc(),
+            self.check_rustup(),
+            self.check_wasm_target(),
+            self.check_java(),
+            self.check_git(),
+            self.check_cargo_pbc(),
+            check_id_pbc(),
+        ];
+        checks.extend(self.check_pk_permissions());
+        checks.extend(self.check_networks());
+        checks
+    }
+
+    fn check_rustc(&self) -> DoctorCheck {
+        let name: &str = "rustc";
+        let remediation: String = format!(
+            "rustup toolchain install {0} && rustup override set {0}",
+            REQUIRED_RUST_VERSION
+        );
+        let output: Option<String> =
+            command_output(&self.context.project_dir, "rustc", &["--version"], OutputStream::Stdout);
+        let output: String = match output {
+            Some(output) => output,
+            None => {
+                return DoctorCheck::failed(
+                    name,
+                    "rustc was not found".to_string(),
+                    "install Rust with rustup, see https://rustup.rs/".to_string(),
+                )
+            }
+        };
+        match parse_version(&output) {
+            Some(version) if version_matches(&version, &parse_numbers(REQUIRED_RUST_VERSION)) => {
+                DoctorCheck::ok(name, format_version(&version))
+            }
+            Some(version) => DoctorCheck::failed(
+                name,
+                format!("{} found, {} is required", format_version(&version), REQUIRED_RUST_VERSION),
+                remediation,
+            ),
+            None => DoctorCheck::failed(name, format!("unrecognized version: {}", output.trim()), remediation),
+        }
+    }
+
+    fn check_rustup(&self) -> DoctorCheck {
+        let output: Option<String> =
+            command_output(&self.context.project_dir, "rustup", &["--version"], OutputStream::Stdout);
+        match output.as_deref().and_then(parse_version) {
+            Some(version) => DoctorCheck::ok("rustup", format_version(&version)),
+            None => DoctorCheck::failed(
+                "rustup",
+                "rustup was not found".to_string(),
+                "install rustup, see https://rustup.rs/".to_string(),
+            ),
+        }
+    }
+
+    fn check_wasm_target(&self) -> DoctorCheck {
+        let remediation: String = format!("rustup target add {}", WASM_TARGET);
+        let output: Option<String> = command_output(
+            &self.context.project_dir,
+            "rustup",
+            &["target", "list", "--installed"],
+            OutputStream::Stdout,
+        );
+        match output {
+            Some(output) if output.lines().any(|line| line.trim() == WASM_TARGET) => {
+                DoctorCheck::ok("wasm target", format!("{} installed", WASM_TARGET))
+            }
+            Some(_) => DoctorCheck::failed("wasm target", format!("{} is not installed", WASM_TARGET), remediation),
+            None => DoctorCheck::failed("wasm target", "installed targets could not be listed".to_string(), remediation),
+        }
+    }
+
+    fn check_java(&self) -> DoctorCheck {
+        let remediation: String = format!(
+            "install OpenJDK {0} and put it first on your PATH, see https://openjdk.org/install/",
+            REQUIRED_JAVA_VERSION
+        );
+        // java prints its version to stderr
+        match command_output(&self.context.project_dir, "java", &["-version"], OutputStream::Stderr) {
+            None => DoctorCheck::failed("java", "java was not found".to_string(), remediation),
+            Some(output) => match java_major_version(&output) {
+                Some(major) if major == REQUIRED_JAVA_VERSION => DoctorCheck::ok("java", format!("Java {}", major)),
+                Some(major) => DoctorCheck::failed(
+                    "java",
+                    format!("Java {} found, {} is required", major, REQUIRED_JAVA_VERSION),
+                    remediation,
+                ),
+                None => DoctorCheck::failed("java", format!("unrecognized version: {}", output.trim()), remediation),
+            },
+        }
+    }
+
+    fn check_git(&self) -> DoctorCheck {
+        let output: Option<String> =
+            command_output(&self.context.project_dir, "git", &["--version"], OutputStream::Stdout);
+        match output.as_deref().and_then(parse_version) {
+            Some(version) => DoctorCheck::ok("git", format_version(&version)),
+            None => DoctorCheck::failed(
+                "git",
+                "git was not found".to_string(),
+                "install git, see https://git-scm.com/downloads".to_string(),
+            ),
+        }
+    }
+
+    fn check_cargo_pbc(&self) -> DoctorCheck {
+        let name: &str = "cargo pbc";
+        let output: Option<String> =
+            command_output(&self.context.project_dir, "cargo", &["pbc", "--version"], OutputStream::Stdout);
+        let installed: Vec<u64> = match output.as_deref().and_then(parse_version) {
+            Some(version) => version,
+            None => {
+                return DoctorCheck::failed(
+                    name,
+                    "cargo-partisia-contract is not installed".to_string(),
+                    "cargo install cargo-partisia-contract".to_string(),
+                )
+            }
+        };
+        let required: Option<String> = match self.context.workspace_root() {
+            Some(root) => required_cargo_partisia_version(&root.join("Cargo.toml")),
+            None => {
+                return DoctorCheck::ok(
+                    name,
+                    format!("{}, not in a partizee project so the required version was not checked", format_version(&installed)),
+                )
+            }
+        };
+        match required {
+            Some(required) if !version_at_least(&installed, &parse_numbers(&required)) => DoctorCheck::failed(
+                name,
+                format!(
+                    "{} found, [workspace.metadata.partisiablockchain] requires {}",
+                    format_version(&installed),
+                    required
+                ),
+                "cargo install cargo-partisia-contract --force".to_string(),
+            ),
+            Some(required) => DoctorCheck::ok(name, format!("{}, {} required", format_version(&installed), required)),
+            None => DoctorCheck::warning(
+                name,
+                format!(
+                    "{}, but the workspace Cargo.toml has no [workspace.metadata.partisiablockchain] cargo-partisia version",
+                    format_version(&installed)
+                ),
+                "add cargo-partisia = \"<version>\" under [workspace.metadata.partisiablockchain]".to_string(),
+            ),
+        }
+    }
+
+    fn check_pk_permissions(&self) -> Vec<DoctorCheck> {
+        let pk_files: Vec<PathBuf> = get_pk_files(&self.context.project_dir);
+        if pk_files.is_empty() {
+            return vec![DoctorCheck::warning(
+                "private keys",
+                "no .pk files found".to_string(),
+                "create an account with partizee profile create".to_string(),
+            )];
+        }
+        pk_files
+            .iter()
+            .map(|path| {
+                let name: String = format!("private key {}", path.display());
+                match file_mode(path) {
+                    Some(mode) => match permission_problem(mode) {
+                        Some(problem) => DoctorCheck::failed(&name, problem, format!("chmod 600 {}", path.display())),
+                        None => DoctorCheck::ok(&name, format!("mode {:o}", mode & 0o777)),
+                    },
+                    None => DoctorCheck::ok(&name, "permissions are not checked on this platform".to_string()),
+                }
+            })
+            .collect()
+    }
+
+    fn check_networks(&self) -> Vec<DoctorCheck> {
+        let mut checks: Vec<DoctorCheck> = Vec::new();
+        let mut configured: BTreeSet<String> = BTreeSet::new();
+        if let Some(root) = self.context.workspace_root() {
+            match DeployManifest::find(&root, None) {
+                Ok(Some(manifest)) => {
+                    configured.extend(manifest.deploy.network.clone());
+                    configured.extend(manifest.contracts.values().filter_map(|contract| contract.network.clone()));
+                }
+                Ok(None) => {}
+                Err(e) => checks.push(DoctorCheck::failed(
+                    MANIFEST_FILE_NAME,
+                    e.to_string(),
+                    format!("fix the syntax of {}", root.join(MANIFEST_FILE_NAME).display()),
+                )),
+            }
+        }
+
+        // built-in networks are always checked, but only configured ones have to be reachable
+        let mut networks: Vec<(String, bool)> = ["testnet", "mainnet"]
+            .iter()
+            .map(|network| (network.to_string(), configured.contains(*network)))
+            .collect();
+        networks.extend(
+            configured
+                .iter()
+                .filter(|network| !matches!(network.as_str(), "testnet" | "mainnet"))
+                .map(|network| (network.clone(), true)),
+        );
+
+        for (network, in_manifest) in networks {
+            let name: String = format!("network {}", network);
+            let endpoint: String = match get_rpc_endpoint(&network) {
+                Ok(endpoint) => endpoint,
+                Err(e) => {
+                    checks.push(DoctorCheck::failed(
+                        &name,
+                        e.to_string(),
+                        format!("set network to testnet, mainnet or a node url in {}", MANIFEST_FILE_NAME),
+                    ));
+                    continue;
+                }
+            };
+            if self.offline {
+                checks.push(DoctorCheck::ok(&name, format!("{}, not contacted", endpoint)));
+                continue;
+            }
+            // other node urls sign for the chain they serve, only known networks can mismatch
+            let expected_chain_id: Option<&str> = known_chain_id(&network);
+            let client: TransactionClient =
+                TransactionClient::new(&endpoint, expected_chain_id.unwrap_or_default());
+            let chain: Result<Value, String> = block_on(client.get_chain())
+                .map_err(|e| e.to_string())
+                .and_then(|chain| chain.map_err(|e| e.to_string()));
+            let check: DoctorCheck = match chain {
+                Ok(chain) => match chain["chainId"].as_str() {
+                    Some(chain_id) if expected_chain_id.is_some_and(|expected| expected != chain_id) => DoctorCheck::failed(
+                        &name,
+                        format!("{} serves chain {}, transactions are signed for {}", endpoint, chain_id, client.chain_id()),
+                        "point the network at a node of the intended chain".to_string(),
+                    ),
+                    _ => DoctorCheck::ok(&name, format!("{} reachable", endpoint)),
+                },
+                Err(e) if in_manifest => DoctorCheck::failed(
+                    &name,
+                    format!("{} is unreachable: {}", endpoint, e),
+                    format!("check your connection or set a reachable node url as network in {}", MANIFEST_FILE_NAME),
+                ),
+                Err(e) => DoctorCheck::warning(
+                    &name,
+                    format!("{} is unreachable: {}", endpoint, e),
+                    "check your connection, or pass a reachable node url with --net".to_string(),
+                ),
+            };
+            checks.push(check);
+        }
+        checks
+    }
+}
+
+/// Checks that a wallet was created with `cargo pbc wallet create`
+fn check_id_pbc() -> DoctorCheck {
+    match id_pbc_path() {
+        Some(path) => DoctorCheck::ok("wallet", format!("{} found", path.display())),
+        None => DoctorCheck::warning(
+            "wallet",
+            "~/.pbc/id_pbc not found".to_string(),
+            "create a wallet with partizee profile create".to_string(),
+        ),
+    }
+}
+
+/// Reads the cargo-partisia version a workspace requires
+///
+/// # Arguments
+/// * `manifest_path` - Workspace Cargo.toml
+///
+/// # Returns
+/// * `Option<String>` - `cargo-partisia` of `[workspace.metadata.partisiablockchain]` if set
+fn required_cargo_partisia_version(manifest_path: &Path) -> Option<String> {
+    let contents: String = fs::read_to_string(manifest_path).ok()?;
+    let manifest: toml::Value = toml::from_str(&contents).ok()?;
+    manifest
+        .get("workspace")?
+        .get("metadata")?
+        .get("partisiablockchain")?
+        .get("cargo-partisia")?
+        .as_str()
+        .map(|version| version.to_string())
+}
+
+/// Finds the first version number in a tool's output
+///
+/// # Arguments
+/// * `output` - e.g. `rustc 1.86.0 (05f9846f8 2025-03-31)` or `openjdk version "17.0.15" 2025-04-15`
+///
+/// # Returns
+/// * `Option<Vec<u64>>` - Version components, None if the output has no version
+pub fn parse_version(output: &str) -> Option<Vec<u64>> {
+    output
+        .split_whitespace()
+        .map(|word| word.trim_matches(|c: char| c == '"' || c == ',' || c == 'v'))
+        .find(|word| word.contains('.') && word.starts_with(|c: char| c.is_ascii_digit()))
+        .map(parse_numbers)
+}
+
+/// Splits a version into its numeric components, stopping at the first non-numeric one
+fn parse_numbers(version: &str) -> Vec<u64> {
+    version
+        .split(['.', '_', '-', '+'])
+        .map_while(|part| part.parse::<u64>().ok())
+        .collect()
+}
+
+/// Major Java version, reading the legacy `1.8` scheme as 8
+///
+/// # Arguments
+/// * `output` - Output of `java -version`
+///
+/// # Returns
+/// * `Option<u64>` - Major version
+pub fn java_major_version(output: &str) -> Option<u64> {
+    let version: Vec<u64> = parse_version(output)?;
+    match version.as_slice() {
+        [1, major, ..] => Some(*major),
+        [major, ..] => Some(*major),
+        [] => None,
+    }
+}
+
+/// Whether a version starts with every component of the required version
+fn version_matches(version: &[u64], required: &[u64]) -> bool {
+    version.len() >= required.len() && version[..required.len()] == *required
+}
+
+/// Whether a version is the required version or newer, missing components count as 0
+fn version_at_least(version: &[u64], required: &[u64]) -> bool {
+    let len: usize = version.len().max(required.len());
+    let pad = |v: &[u64]| -> Vec<u64> { (0..len).map(|i| v.get(i).copied().unwrap_or(0)).collect() };
+    pad(version) >= pad(required)
+}
+
+fn format_version(version: &[u64]) -> String {
+    version.iter().map(|part| part.to_string()).collect::<Vec<String>>().join(".")
+}
+
+/// Describes what is wrong with the permissions of a private key file
+///
+/// # Arguments
+/// * `mode` - Unix mode of the file
+///
+/// # Returns
+/// * `Option<String>` - Problem, None if only the owner can access the file
+pub fn permission_problem(mode: u32) -> Option<String> {
+    if mode & 0o077 == 0 {
+        return None;
+    }
+    Some(format!("mode {:o} lets other users read the private key", mode & 0o777))
+}
+
+#[cfg(unix)]
+fn file_mode(path: &Path) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).ok().map(|metadata| metadata.permissions().mode())
+}
+
+#[cfg(not(unix))]
+fn file_mode(_path: &Path) -> Option<u32> {
+    None
+}
+
+/// Prints the results of the checks with their remediations
+///
+/// # Arguments
+/// * `checks` - Results of `Doctor::run`
+///
+/// # Returns
+/// * `usize` - Number of failed checks
+pub fn print_checks(checks: &[DoctorCheck]) -> usize {
+    for check in checks {
+        let icon: &str = match check.status {
+            CheckStatus::Ok => "✅",
+            CheckStatus::Warning => "⚠️",
+            CheckStatus::Failed => "❌",
+        };
+        println!("{} {}: {}", icon, check.name, check.detail);
+        if let Some(remediation) = &check.remediation {
+            println!("    fix: {}", remediation);
+        }
+    }
+    checks.iter().filter(|check| check.status == CheckStatus::Failed).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_versions() {
+        assert_eq!(parse_version("rustc 1.86.0 (05f9846f8 2025-03-31)"), Some(vec![1, 86, 0]));
+        assert_eq!(parse_version("cargo-partisia-contract 5.204.0"), Some(vec![5, 204, 0]));
+        assert_eq!(parse_version("git version 2.39.5"), Some(vec![2, 39, 5]));
+        assert_eq!(parse_version("no version here"), None);
+
+        assert_eq!(
+            java_major_version("openjdk version \"17.0.15\" 2025-04-15\nOpenJDK Runtime Environment"),
+            Some(17)
+        );
+        assert_eq!(java_major_version("java version \"1.8.0_392\""), Some(8));
+
+        assert!(version_matches(&[1, 86, 0], &parse_numbers(REQUIRED_RUST_VERSION)));
+        assert!(!version_matches(&[1, 95, 0], &parse_numbers(REQUIRED_RUST_VERSION)));
+        assert!(version_at_least(&[5, 204, 0], &[5, 85, 0]));
+        assert!(version_at_least(&[5, 85], &[5, 85, 0]));
+        assert!(!version_at_least(&[5, 9, 1], &[5, 85, 0]));
+    }
+
+    #[test]
+    fn test_configuration_problems() {
+        assert_eq!(permission_problem(0o100600), None);
+        assert_eq!(
+            permission_problem(0o100644),
+            Some("mode 644 lets other users read the private key".to_string())
+        );
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let manifest_path: PathBuf = temp_dir.path().join("Cargo.toml");
+        fs::write(
+            &manifest_path,
+            "[workspace]\nmembers = []\n\n[workspace.metadata.partisiablockchain]\ncargo-partisia = \"5.85.0\"\n",
+        )
+        .unwrap();
+        assert_eq!(required_cargo_partisia_version(&manifest_path), Some("5.85.0".to_string()));
+        fs::write(&manifest_path, "[workspace]\nmembers = []\n").unwrap();
+        assert_eq!(required_cargo_partisia_version(&manifest_path), None);
+    }
+}
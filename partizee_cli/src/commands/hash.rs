@@ -0,0 +1,92 @@
+use crate::client::transaction::TransactionClient;
+use crate::commands::call::resolve_contract;
+use crate::commands::deploy::{find_contract_artifact, Deployment};
+use crate::utils::artifact::{ContractArtifact, ContractHashes};
+use crate::utils::context::ProjectContext;
+use crate::utils::utils::{block_on, get_chain_id, get_rpc_endpoint};
+use std::path::PathBuf;
+
+/// Computation of the `ContractHashes` of a compiled contract
+///
+/// # Fields
+/// * `contract` - Contract whose compiled artifact is hashed
+/// * `binder_hash` - Hex binder hash, read from a deployed contract if not set
+/// * `binder_from` - Name or address of the deployed contract to read the binder from, defaults to `contract`
+/// * `network` - Network the binder is read from, defaults to the network the contract was recorded on
+/// * `context` - Project the artifact is read from
+#[derive(Debug, Clone)]
+pub struct ContractHashQuery {
+    pub contract: String,
+    pub binder_hash: Option<String>,
+    pub binder_from: Option<String>,
+    pub network: Option<String>,
+    pub context: ProjectContext,
+}
+
+impl ContractHashQuery {
+    /// Hashes the local artifact of the contract
+    ///
+    /// Only the binder hash needs the chain, passing `binder_hash` keeps the computation offline.
+    ///
+    /// # Returns
+    /// * `Result<ContractHashes>` - Hashes of the artifact
+    pub fn compute(&self) -> Result<ContractHashes, Box<dyn std::error::Error>> {
+        let project_root: PathBuf = self.context.require_workspace_root()?;
+        let artifact: ContractArtifact = find_contract_artifact(&project_root, &self.contract)?;
+        let binder_hash: [u8; 32] = match self.binder_hash.as_ref() {
+            Some(binder_hash) => parse_hash(binder_hash)?,
+            None => {
+                let deployed: &str = self.binder_from.as_deref().unwrap_or(&self.contract);
+                let (address, _, network): (String, Option<Deployment>, String) =
+                    resolve_contract(&self.context, deployed, self.network.as_deref()).map_err(|e| {
+                        format!("{}, pass --binder-hash or --binder-from <deployed contract>", e)
+                    })?;
+                let client: TransactionClient =
+                    TransactionClient::new(&get_rpc_endpoint(&network)?, &get_chain_id(&network)?);
+                block_on(client.get_binder_hash(&address))??
+            }
+        };
+        Ok(ContractHashes::new(binder_hash, &artifact))
+    }
+}
+
+/// Parses a hex encoded 32 byte hash, with or without a 0x prefix
+///
+/// # Arguments
+/// * `hash` - Hex encoded hash
+///
+/// # Returns
+/// * `Result<[u8; 32]>` - Hash bytes
+pub fn parse_hash(hash: &str) -> Result<[u8; 32], Box<dyn std::error::Error>> {
+    hex::decode(hash.trim_start_matches("0x"))
+        .map_err(|e| format!("Invalid hash {}: {}", hash, e))?
+        .try_into()
+        .map_err(|_| format!("Hash {} must be 32 bytes", hash).into())
+}
+
+/// Prints contract hashes in hex, RPC and argument form
+///
+/// # Arguments
+/// * `name` - Contract the hashes belong to
+/// * `hashes` - Hashes to print
+pub fn print_contract_hashes(name: &str, hashes: &ContractHashes) {
+    println!("Contract hashes of {}:", name);
+    println!("  binder:   {}", hex::encode(hashes.binder));
+    println!("  contract: {}", hex::encode(hashes.contract));
+    println!("  abi:      {}", hex::encode(hashes.abi));
+    println!("RPC:        {}", hex::encode(hashes.to_rpc()));
+    println!("Argument:   {}", hashes.to_json_arg());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hash() {
+        assert_eq!(parse_hash(&"ab".repeat(32)).unwrap(), [0xab; 32]);
+        assert_eq!(parse_hash(&format!("0x{}", "AB".repeat(32))).unwrap(), [0xab; 32]);
+        assert!(parse_hash("abcd").unwrap_err().to_string().contains("must be 32 bytes"));
+        assert!(parse_hash("xyz").is_err());
+    }
+}
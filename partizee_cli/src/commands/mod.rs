@@ -1,4 +1,11 @@
+pub mod call;
 pub mod compile;
 pub mod deploy;
+pub mod deployments;
+pub mod doctor;
+pub mod hash;
 pub mod new;
+pub mod state;
+pub mod upgrade;
 pub mod user_profile;
+pub mod verify;
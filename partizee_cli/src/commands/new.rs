@@ -6,14 +6,19 @@ use std::{
     process::Command,
 };
 
+/// Templates only copied into zero-knowledge projects, overriding files of the same path
+pub const ZK_TEMPLATE_DIR: &str = "zk/";
+
 pub struct NewProject {
     pub output_dir: PathBuf,
     // the root of the project
+    pub zero_knowledge: bool,
 }
 
 pub struct ProjectConfig {
     pub name: String,
     pub output_dir: Option<String>,
+    pub zero_knowledge: bool,
 }
 
 #[derive(Embed)]
@@ -28,12 +33,38 @@ impl NewProject {
             .unwrap_or_else(|| format!("{}/", config.name.clone()))
             .into();
 
-        Ok(NewProject { output_dir })
+        Ok(NewProject {
+            output_dir,
+            zero_knowledge: config.zero_knowledge,
+        })
+    }
+
+    /// Maps a template path to its path in the new project
+    ///
+    /// # Arguments
+    /// * `template_path` - Path of the template inside `templates/`
+    ///
+    /// # Returns
+    /// * `Option<&str>` - Path relative to the project root, None if the template is not part of this project
+    pub fn project_path<'a>(&self, template_path: &'a str) -> Option<&'a str> {
+        match template_path.strip_prefix(ZK_TEMPLATE_DIR) {
+            Some(zk_path) if self.zero_knowledge => Some(zk_path),
+            Some(_) => None,
+            // overridden by the zero-knowledge template of the same path
+            None if self.zero_knowledge
+                && Templates::get(&format!("{}{}", ZK_TEMPLATE_DIR, template_path)).is_some() =>
+            {
+                None
+            }
+            None => Some(template_path),
+        }
     }
 
     pub fn create_project_directory(&self) -> Result<(), Box<dyn std::error::Error>> {
         for entry in Templates::iter() {
-            let dir_path = entry.as_ref();
+            let Some(dir_path) = self.project_path(entry.as_ref()) else {
+                continue;
+            };
             if PathBuf::from(dir_path).is_dir() {
                 // Create the corresponding directory in the new project root
                 let new_dir = self.output_dir.join(dir_path);
@@ -77,8 +108,11 @@ impl NewProject {
     pub fn copy_all_files(&self) -> Result<(), Box<dyn std::error::Error>> {
         for entry in Templates::iter() {
             let path = entry.as_ref();
+            let Some(project_path) = self.project_path(path) else {
+                continue;
+            };
 
-            let dest_path = self.output_dir.join(path);
+            let dest_path = self.output_dir.join(project_path);
 
             // Skip directories, just ensure they exist
             if path.ends_with('/') {
@@ -127,6 +161,9 @@ impl NewProject {
         println!("\n \n \n \n");
         println!("📁 Project created at: {}", self.output_dir.display());
         println!("  └─ 📂 rust/  (Partisia smart contracts)");
+        if self.zero_knowledge {
+            println!("      └─ 📂 contracts/averageSalary/  (zero-knowledge contract with #[zk_compute])");
+        }
         println!("  └─ 📂 frontend/  (Web 2 components)");
         println!("✨ Project scaffolding complete!");
         println!("\n📝 Next steps:");
@@ -134,3 +171,36 @@ impl NewProject {
         println!("  2. Follow the setup instructions in contract/README.md and frontend/README.md");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_project(zero_knowledge: bool) -> NewProject {
+        NewProject::new(ProjectConfig {
+            name: "my-dapp".to_string(),
+            output_dir: None,
+            zero_knowledge,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_project_path() {
+        let public = new_project(false);
+        assert_eq!(public.project_path("Cargo.template.toml"), Some("Cargo.template.toml"));
+        assert_eq!(public.project_path("zk/Cargo.template.toml"), None);
+
+        let zk = new_project(true);
+        assert_eq!(zk.project_path("Cargo.template.toml"), None);
+        assert_eq!(zk.project_path("zk/Cargo.template.toml"), Some("Cargo.template.toml"));
+        assert_eq!(
+            zk.project_path("zk/rust/contracts/averageSalary/src/zk_compute.rs"),
+            Some("rust/contracts/averageSalary/src/zk_compute.rs")
+        );
+        assert_eq!(
+            zk.project_path("rust/contracts/counterV1/src/lib.rs"),
+            Some("rust/contracts/counterV1/src/lib.rs")
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use crate::client::transaction::TransactionClient;
+use crate::commands::call::{load_contract_abi, resolve_contract};
+use crate::commands::deploy::Deployment;
+use crate::utils::abi::ContractAbi;
+use crate::utils::context::ProjectContext;
+use crate::utils::state::{decode_state, select_field, AvlTrees};
+use crate::utils::utils::{block_on, get_chain_id, get_rpc_endpoint};
+use serde_json::Value;
+
+/// Read of a deployed contract's state
+///
+/// # Fields
+/// * `contract` - Name or address of the deployed contract
+/// * `network` - Network of the contract, defaults to the network it was recorded on
+/// * `at_block` - Block time to read the state at, defaults to the latest block
+/// * `field` - Path of a nested value to print instead of the whole state
+/// * `context` - Project the contract's deployment is looked up in
+#[derive(Debug, Clone)]
+pub struct StateQuery {
+    pub contract: String,
+    pub network: Option<String>,
+    pub at_block: Option<i64>,
+    pub field: Option<String>,
+    pub context: ProjectContext,
+}
+
+impl StateQuery {
+    /// Fetches the contract's state and decodes it with the contract ABI
+    ///
+    /// # Returns
+    /// * `Result<Value>` - Decoded state, or the selected field
+    pub fn fetch(&self) -> Result<Value, Box<dyn std::error::Error>> {
+        let (address, deployment, network): (String, Option<Deployment>, String) =
+            resolve_contract(&self.context, &self.contract, self.network.as_deref())?;
+        let client: TransactionClient =
+            TransactionClient::new(&get_rpc_endpoint(&network)?, &get_chain_id(&network)?);
+        let abi: ContractAbi = load_contract_abi(&client, &self.context, &address, deployment.as_ref())?;
+        let (state, avl_trees): (Vec<u8>, AvlTrees) =
+            block_on(client.get_contract_state(&address, self.at_block))??;
+        let decoded: Value = decode_state(&abi, &state, &avl_trees)
+            .map_err(|e| format!("Failed to decode the state of {}: {}", self.contract, e))?;
+        match self.field.as_ref() {
+            Some(field) => select_field(&decoded, field),
+            None => Ok(decoded),
+        }
+    }
+}
@@ -0,0 +1,384 @@
+use crate::client::rpc::address_to_bytes;
+use crate::client::tracker::{TransactionTrace, TransactionTracker};
+use crate::client::transaction::{upgrade_contract_rpc, SentTransaction, TransactionClient};
+use crate::commands::deploy::{
+    estimate_deploy_gas, find_contract_abi, find_contract_artifact, find_deployments_dir,
+    save_deployments, Deployment,
+};
+use crate::commands::deployments::DeploymentHistory;
+use crate::commands::hash::{parse_hash, print_contract_hashes};
+use crate::commands::user_profile::Profile;
+use crate::utils::abi::{ContractAbi, FnAbi};
+use crate::utils::artifact::{ContractArtifact, ContractHashes, ContractKind};
+use crate::utils::build_info::BuildInfo;
+use crate::utils::constants::{DEFAULT_ACTION_GAS, PUB_DEPLOY_ADDRESS, TRANSACTION_VALIDITY_MS};
+use crate::utils::context::ProjectContext;
+use crate::utils::cryptography::address::{secret_key_from_hex, ADDRESS_LENGTH};
+use crate::utils::frontend::generate_frontend_bindings;
+use crate::utils::journal::DeployLock;
+use crate::utils::mainnet::MainnetGuard;
+use crate::utils::utils::{block_on, get_chain_id, get_rpc_endpoint, load_account_from_pk_file};
+use k256::SecretKey;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Action pre-approving the code a contract may be upgraded to
+pub const ALLOW_UPGRADE_ACTION: &str = "allow_upgrade_to";
+
+/// Configuration for upgrading a deployed contract
+///
+/// # Fields
+/// * `contract` - Name or address of the deployed contract
+/// * `target` - Contract whose compiled artifact replaces the deployed code
+/// * `network` - Network of the deployment, defaults to the network it was recorded on
+/// * `path_to_pk` - Path to private key file
+/// * `allow_upgrade` - Send `allow_upgrade_to` with the new hashes before upgrading
+/// * `upgrade_rpc` - Serialized arguments of the new code's `#[upgrade]` function
+/// * `gas_limit` - Gas limit of the upgrade transaction, estimated if not set
+/// * `binder_hash` - Hex binder hash, read from the deployed contract if not set
+/// * `confirm_mainnet` - Confirms a mainnet upgrade without asking
+/// * `context` - Project the deployment and artifact are read from
+#[derive(Debug, Clone)]
+pub struct UpgradeConfigs {
+    pub contract: String,
+    pub target: String,
+    pub network: Option<String>,
+    pub path_to_pk: PathBuf,
+    pub allow_upgrade: bool,
+    pub upgrade_rpc: Vec<u8>,
+    pub gas_limit: Option<i64>,
+    pub binder_hash: Option<String>,
+    pub confirm_mainnet: bool,
+    pub context: ProjectContext,
+}
+
+/// Upgrade of a recorded deployment, signed by the given profile
+///
+/// # Fields
+/// * `configs` - Upgrade configuration
+/// * `deployment` - Latest record of the deployed contract
+/// * `network` - Network the contract is deployed on
+/// * `account` - Account sending the transactions
+#[derive(Debug, Clone)]
+pub struct ContractUpgrade {
+    configs: UpgradeConfigs,
+    deployment: Deployment,
+    network: String,
+    account: Profile,
+}
+
+impl ContractUpgrade {
+    /// Resolves the deployed contract and loads the upgrading account
+    ///
+    /// # Arguments
+    /// * `configs` - Upgrade configuration
+    ///
+    /// # Returns
+    /// * `Result<ContractUpgrade>` - Upgrade ready to be sent
+    pub fn new(configs: UpgradeConfigs) -> Result<Self, Box<dyn std::error::Error>> {
+        let history: DeploymentHistory = DeploymentHistory::for_project(&configs.context)?;
+        let deployment: Deployment =
+            resolve_deployment(&history, &configs.contract, configs.network.as_deref())?;
+        let network: String = configs
+            .network
+            .clone()
+            .unwrap_or_else(|| deployment.network.clone());
+        let account: Profile = load_account_from_pk_file(&configs.path_to_pk, &network)?;
+        Ok(Self {
+            configs,
+            deployment,
+            network,
+            account,
+        })
+    }
+
+    /// Upgrades the contract and records the new code version
+    ///
+    /// Sends the optional `allow_upgrade_to` approval and waits for it to succeed before
+    /// sending the upgrade. Once the upgrade has succeeded the deployment is recorded
+    /// again under the same name and address, other recorded contracts are kept.
+    ///
+    /// # Returns
+    /// * `Result<Deployment>` - Recorded deployment running the new code
+    pub fn upgrade(&self) -> Result<Deployment, Box<dyn std::error::Error>> {
+        let project_root: PathBuf = self.configs.context.require_workspace_root()?;
+        let deployments_dir: PathBuf =
+            find_deployments_dir(&project_root).ok_or("Failed to find target directory")?;
+        let _lock: DeployLock = DeployLock::acquire(&deployments_dir)?;
+
+        let artifact: ContractArtifact = find_contract_artifact(&project_root, &self.configs.target)?;
+        if artifact.kind != ContractKind::Public {
+            return Err("Zero-knowledge contracts cannot be upgraded natively yet".into());
+        }
+        let address: [u8; ADDRESS_LENGTH] = address_to_bytes(&self.deployment.address)?;
+        let secret_key: SecretKey = secret_key_from_hex(&self.account.private_key)?;
+        let client: TransactionClient =
+            TransactionClient::new(&get_rpc_endpoint(&self.network)?, &get_chain_id(&self.network)?);
+
+        // upgrades keep the binder, so the deployed contract's binder hash is reused
+        let binder_hash: [u8; 32] = match self.configs.binder_hash.as_ref() {
+            Some(binder_hash) => parse_hash(binder_hash)?,
+            None => block_on(client.get_binder_hash(&self.deployment.address))??,
+        };
+        let hashes: ContractHashes = ContractHashes::new(binder_hash, &artifact);
+        print_contract_hashes(&self.configs.target, &hashes);
+
+        let mut approval: Option<([u8; ADDRESS_LENGTH], Vec<u8>, i64)> = None;
+        if self.configs.allow_upgrade {
+            // the approval is an action of the code currently at the address
+            let running_code: &str = self
+                .deployment
+                .code_version
+                .as_deref()
+                .unwrap_or(&self.deployment.name);
+            let abi: ContractAbi = find_contract_abi(&self.configs.context, running_code)
+                .ok_or_else(|| format!("No ABI found for {}, compile it first", running_code))?;
+            let approval_rpc: Vec<u8> =
+                allow_upgrade_rpc(&abi, &hashes, &self.configs.upgrade_rpc)?;
+            approval = Some((address, approval_rpc, DEFAULT_ACTION_GAS));
+        }
+        let gas_limit: i64 = self
+            .configs
+            .gas_limit
+            .unwrap_or_else(|| estimate_deploy_gas(&artifact, &self.configs.upgrade_rpc));
+
+        MainnetGuard {
+            network: &self.network,
+            confirmed: self.configs.confirm_mainnet,
+            account: &self.account,
+            estimated_gas: gas_limit + approval.as_ref().map_or(0, |(_, _, gas)| *gas),
+            artifacts: vec![(self.configs.target.as_str(), &artifact)],
+        }
+        .enforce(&project_root)?;
+
+        println!(
+            "Upgrading {} at {} on {} to {}.",
+            self.deployment.name, self.deployment.address, self.network, self.configs.target
+        );
+        let tracker: TransactionTracker = TransactionTracker::new(&client, TRANSACTION_VALIDITY_MS);
+        // the upgrade is only accepted once the approval has executed on the contract's shard
+        if let Some((approval_address, approval_rpc, approval_gas)) = approval {
+            let sent: SentTransaction = block_on(client.sign_and_send(
+                &secret_key,
+                approval_address,
+                approval_rpc,
+                approval_gas,
+            ))??;
+            println!("Waiting for approval transaction {} to be finalized.", sent.transaction_hash);
+            let trace: TransactionTrace = block_on(tracker.wait(&sent))??;
+            trace.ensure_succeeded().inspect_err(|_| eprintln!("{}", trace.render()))?;
+            println!("Approved the upgrade in transaction {}", sent.transaction_hash);
+        }
+        let upgrade_transaction: SentTransaction = block_on(client.sign_and_send(
+            &secret_key,
+            address_to_bytes(PUB_DEPLOY_ADDRESS)?,
+            upgrade_contract_rpc(&address, &artifact, &self.configs.upgrade_rpc),
+            gas_limit,
+        ))??;
+        println!(
+            "Waiting for upgrade transaction {} to be finalized.",
+            upgrade_transaction.transaction_hash
+        );
+        let trace: TransactionTrace = block_on(tracker.wait(&upgrade_transaction))??;
+        trace.ensure_succeeded().inspect_err(|_| eprintln!("{}", trace.render()))?;
+
+        let timestamp: String = SystemTime::now()
+            .duration_since(UNIX_EPOCH)?
+            .as_secs()
+            .to_string();
+        let upgraded = Deployment {
+            name: self.deployment.name.clone(),
+            network: self.network.clone(),
+            address: self.deployment.address.clone(),
+            transaction_hash: upgrade_transaction.transaction_hash.clone(),
+            args: self.deployment.args.clone(),
+            timestamp,
+            code_version: Some(self.configs.target.clone()),
+            build_info: BuildInfo::link(&project_root, &self.configs.target, &artifact),
+        };
+        save_deployments(vec![upgraded.clone()], &project_root)?;
+        println!(
+            "✅ Upgraded {} at {} to {} (transaction {})",
+            upgraded.name, upgraded.address, self.configs.target, upgraded.transaction_hash
+        );
+
+        // the address is unchanged but the frontend needs bindings for the new ABI
+        let abis: Vec<(String, Vec<u8>)> = vec![(upgraded.name.clone(), artifact.abi_bytes.clone())];
+        let frontend = DeploymentHistory::load(&deployments_dir)
+            .and_then(|history| generate_frontend_bindings(&project_root, &history, &self.network, &abis));
+        if let Err(e) = frontend {
+            eprintln!("⚠️ Failed to update frontend deployments: {}", e);
+        }
+        Ok(upgraded)
+    }
+}
+
+/// Finds the newest recorded deployment of a contract
+///
+/// # Arguments
+/// * `history` - Deployment history of the project
+/// * `contract` - Contract name, case insensitive, or its address
+/// * `network` - Only consider deployments on this network
+///
+/// # Returns
+/// * `Result<Deployment>` - Newest matching deployment
+pub fn resolve_deployment(
+    history: &DeploymentHistory,
+    contract: &str,
+    network: Option<&str>,
+) -> Result<Deployment, Box<dyn std::error::Error>> {
+    history
+        .records()
+        .into_iter()
+        .rev()
+        .map(|(_, deployment)| deployment)
+        .filter(|deployment| network.is_none_or(|network| deployment.network == network))
+        .find(|deployment| {
+            deployment.name.eq_ignore_ascii_case(contract)
+                || deployment.address.eq_ignore_ascii_case(contract)
+        })
+        .cloned()
+        .ok_or_else(|| match network {
+            Some(network) => format!("No deployment of {} recorded on {}", contract, network).into(),
+            None => format!("No deployment of {} recorded", contract).into(),
+        })
+}
+
+/// Serializes the `allow_upgrade_to` action approving new contract code
+///
+/// # Arguments
+/// * `abi` - ABI of the code currently deployed
+/// * `hashes` - Hashes of the new code
+/// * `upgrade_rpc` - Serialized arguments of the new code's `#[upgrade]` function
+///
+/// # Returns
+/// * `Result<Vec<u8>>` - Action RPC
+pub fn allow_upgrade_rpc(
+    abi: &ContractAbi,
+    hashes: &ContractHashes,
+    upgrade_rpc: &[u8],
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let function: &FnAbi = abi.function(ALLOW_UPGRADE_ACTION).ok_or_else(|| {
+        format!(
+            "The deployed contract has no {} action, upgrade without --allow-upgrade",
+            ALLOW_UPGRADE_ACTION
+        )
+    })?;
+    abi.encode_call(function, &[hashes.to_json_arg(), hex::encode(upgrade_rpc)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::deploy::LATEST_DEPLOYMENT_FILE;
+    use crate::utils::abi::{FieldAbi, FnKind, NamedTypeSpec, TypeSpec};
+    use crate::utils::utils::setup_test_environment;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn deployment(name: &str, network: &str, address: &str) -> Deployment {
+        Deployment {
+            name: name.to_string(),
+            network: network.to_string(),
+            address: address.to_string(),
+            transaction_hash: String::new(),
+            args: Vec::new(),
+            timestamp: "1700000000".to_string(),
+            code_version: None,
+            build_info: None,
+        }
+    }
+
+    #[test]
+    fn test_resolve_deployment() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join(LATEST_DEPLOYMENT_FILE),
+            serde_json::to_string(&[
+                deployment("counterV1", "testnet", "02aa"),
+                deployment("counterV1", "mainnet", "02bb"),
+            ])
+            .unwrap(),
+        )
+        .unwrap();
+        let history = DeploymentHistory::load(temp_dir.path()).unwrap();
+
+        assert_eq!(resolve_deployment(&history, "counterv1", None).unwrap().address, "02bb");
+        assert_eq!(
+            resolve_deployment(&history, "counterV1", Some("testnet")).unwrap().address,
+            "02aa"
+        );
+        assert_eq!(resolve_deployment(&history, "02AA", None).unwrap().network, "testnet");
+        assert!(resolve_deployment(&history, "counterV2", None).is_err());
+    }
+
+    #[test]
+    fn test_upgraded_deployment_keeps_other_contracts() {
+        let (_temp_dir, temp_path) = setup_test_environment();
+        let recorded = vec![
+            deployment("counterV1", "testnet", "02aa"),
+            deployment("token", "testnet", "02cc"),
+        ];
+        save_deployments(recorded, &temp_path).unwrap();
+        let upgraded = Deployment {
+            timestamp: "1700000100".to_string(),
+            code_version: Some("counterV2".to_string()),
+            ..deployment("counterV1", "testnet", "02aa")
+        };
+        save_deployments(vec![upgraded], &temp_path).unwrap();
+
+        let history = DeploymentHistory::load(&find_deployments_dir(&temp_path).unwrap()).unwrap();
+        let counter = resolve_deployment(&history, "counterV1", Some("testnet")).unwrap();
+        assert_eq!(counter.code_version.as_deref(), Some("counterV2"));
+        assert_eq!(resolve_deployment(&history, "token", Some("testnet")).unwrap().address, "02cc");
+    }
+
+    #[test]
+    fn test_allow_upgrade_rpc() {
+        let hash = |name: &str| FieldAbi {
+            name: name.to_string(),
+            type_spec: TypeSpec::Hash,
+        };
+        let abi = ContractAbi {
+            binder_version: [10, 0, 0],
+            client_version: [5, 4, 0],
+            named_types: vec![NamedTypeSpec::Struct {
+                name: "ContractHashes".to_string(),
+                fields: vec![hash("binder"), hash("contract"), hash("abi")],
+            }],
+            functions: vec![FnAbi {
+                kind: FnKind::Action,
+                name: ALLOW_UPGRADE_ACTION.to_string(),
+                shortname: vec![0x70],
+                arguments: vec![
+                    FieldAbi {
+                        name: "new_contract_hashes".to_string(),
+                        type_spec: TypeSpec::Named(0),
+                    },
+                    FieldAbi {
+                        name: "upgrade_rpc".to_string(),
+                        type_spec: TypeSpec::Vec(Box::new(TypeSpec::U8)),
+                    },
+                ],
+                secret_argument: None,
+            }],
+            state_type: TypeSpec::U8,
+        };
+        let hashes = ContractHashes {
+            binder: [1; 32],
+            contract: [2; 32],
+            abi: [3; 32],
+        };
+        let rpc = allow_upgrade_rpc(&abi, &hashes, &[0xab]).unwrap();
+        let mut expected: Vec<u8> = vec![0x70];
+        expected.extend([hashes.binder, hashes.contract, hashes.abi].concat());
+        expected.extend([0, 0, 0, 1, 0xab]);
+        assert_eq!(rpc, expected);
+
+        let without_action = ContractAbi {
+            functions: Vec::new(),
+            ..abi
+        };
+        assert!(allow_upgrade_rpc(&without_action, &hashes, &[]).is_err());
+    }
+}
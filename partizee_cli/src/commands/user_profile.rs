@@ -1,13 +1,15 @@
-use crate::utils::constants::DEFAULT_NETWORK;
-use crate::utils::fs_nav::{find_paths_with_name, find_workspace_root, get_pk_files, id_pbc_path};
+use crate::client::tracker::{transaction_hash_in_output, TransactionTrace, TransactionTracker};
+use crate::client::transaction::{SentTransaction, TransactionClient};
+use crate::utils::constants::{DEFAULT_NETWORK, TRANSACTION_VALIDITY_MS};
+use crate::utils::context::ProjectContext;
+use crate::utils::fs_nav::{find_paths_with_name, get_pk_files, id_pbc_path};
 use crate::utils::menus::{create_new_profile_menu, select_pk_menu};
 use crate::utils::pbc_commands::{pbc_create_new_account, pbc_create_new_wallet};
 use crate::utils::utils::{
-    address_is_valid, create_pk_file, get_address_from_pk, load_account_from_pk_file, print_error,
-    print_output,
+    address_is_valid, block_on, create_pk_file, get_address_from_pk, get_chain_id,
+    get_rpc_endpoint, load_account_from_pk_file, print_error, print_output,
 };
 use serde::{Deserialize, Serialize};
-use std::env;
 
 use std::error::Error;
 
@@ -45,15 +47,26 @@ pub struct Profile {
 
 #[allow(dead_code)]
 impl Default for Profile {
+    /// Creates a default profile for the project in the current directory
+    fn default() -> Self {
+        Self::for_project(&ProjectContext::default())
+    }
+}
+
+#[allow(dead_code)]
+impl Profile {
     /// Creates a default profile
     /// 
     /// Attempts to load existing account from .pk file
     /// If no account exists, creates new wallet and account
     /// 
+    /// # Arguments
+    /// * `context` - Project the .pk files are searched from
+    /// 
     /// # Returns
     /// * `Profile` - New profile instance
-    fn default() -> Self {
-        let pk_files: Vec<PathBuf> = get_pk_files();
+    pub fn for_project(context: &ProjectContext) -> Self {
+        let pk_files: Vec<PathBuf> = get_pk_files(&context.project_dir);
         if pk_files.len() > 0 {
             let account: Profile = load_account_from_pk_file(&pk_files[0], DEFAULT_NETWORK)
                 .unwrap_or_else(|e| {
@@ -83,7 +96,7 @@ impl Default for Profile {
             pbc_create_new_account(DEFAULT_NETWORK)
                 .expect("Default account: Failed to create new account");
 
-            let pk_files: Vec<PathBuf> = get_pk_files();
+            let pk_files: Vec<PathBuf> = get_pk_files(&context.project_dir);
             let path_to_pk: PathBuf = if pk_files.len() > 0 {
                 pk_files[0].clone()
             } else {
@@ -99,10 +112,7 @@ impl Default for Profile {
             };
         }
     }
-}
 
-#[allow(dead_code)]
-impl Profile {
     /// Creates a new profile with specified configuration
     /// 
     /// Can load from existing .pk file or create new account
@@ -110,10 +120,14 @@ impl Profile {
     /// 
     /// # Arguments
     /// * `account_config` - Configuration for new profile
+    /// * `context` - Project the .pk files are searched from and new ones are written to
     /// 
     /// # Returns
     /// * `Result<Profile>` - New profile if successful
-    pub fn new(account_config: ProfileConfig) -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn new(
+        account_config: ProfileConfig,
+        context: &ProjectContext,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
         // initialize new account
         let new_profile: Self;
         let network: String = account_config
@@ -157,7 +171,7 @@ impl Profile {
                     )
                     .into());
                 }
-                let path_to_pk: PathBuf = create_pk_file(&private_key)?;
+                let path_to_pk: PathBuf = create_pk_file(&private_key, context)?;
 
                 new_profile = Self {
                     network: network,
@@ -168,7 +182,7 @@ impl Profile {
             }
             (None, Some(private_key)) => {
                 let final_address: String = get_address_from_pk(&private_key)?;
-                let path_to_pk: PathBuf = create_pk_file(&private_key)?;
+                let path_to_pk: PathBuf = create_pk_file(&private_key, context)?;
 
                 let is_valid: bool = address_is_valid(&final_address, &private_key)?;
                 if !is_valid {
@@ -188,10 +202,8 @@ impl Profile {
             (Some(address), None) => {
                 let final_address: String = address.to_string();
                 // look for pk file with address in name
-                let pk_files: Vec<PathBuf> = find_paths_with_name(
-                    &find_workspace_root().unwrap_or(env::current_dir().unwrap()),
-                    &final_address,
-                );
+                let pk_files: Vec<PathBuf> =
+                    find_paths_with_name(&context.search_root(), &final_address);
 
                 match pk_files.len() {
                     1 => {
@@ -201,11 +213,11 @@ impl Profile {
                             });
                     }
                     n if n > 1 => {
-                        let account_file: PathBuf = select_pk_menu()?;
+                        let account_file: PathBuf = select_pk_menu(context)?;
                         new_profile = load_account_from_pk_file(&account_file, &network)?;
                     }
                     0 => {
-                        new_profile = create_new_profile_menu()?;
+                        new_profile = create_new_profile_menu(context)?;
                     }
                     _ => {
                         return Err(
@@ -215,18 +227,18 @@ impl Profile {
                 }
             }
             (None, None) => {
-                let pk_files: Vec<PathBuf> = get_pk_files();
+                let pk_files: Vec<PathBuf> = get_pk_files(&context.project_dir);
                 let pk_files_len: usize = pk_files.len();
                 match pk_files_len {
                     1 => {
                         new_profile = load_account_from_pk_file(&pk_files[0], &network)?;
                     }
                     n if n > 1 => {
-                        let account_file: PathBuf = select_pk_menu()?;
+                        let account_file: PathBuf = select_pk_menu(context)?;
                         new_profile = load_account_from_pk_file(&account_file, &network)?;
                     }
                     0 => {
-                        new_profile = create_new_profile_menu()?;
+                        new_profile = create_new_profile_menu(context)?;
                     }
                     _ => {
                         return Err(
@@ -309,7 +321,7 @@ impl Profile {
         self.network = network.to_string();
     }
 
-    /// Mints gas for the account on testnet
+    /// Mints gas for the account on testnet and waits for the mint to be finalized
     /// 
     /// # Returns
     /// * `Result<()>` - Ok if minting succeeds
@@ -317,19 +329,38 @@ impl Profile {
         // can only mint gas on testnet
         let network_command: String = format!("--net=testnet");
 
-        let output = Command::new("cargo")
+        let output: Output = Command::new("cargo")
             .arg("pbc")
             .arg("account")
             .arg("mintgas")
             .arg(network_command)
             .arg(&self.address)
-            .output();
-        if output.as_ref().unwrap().stdout.len() > 0 {
-            println!("{}", String::from_utf8(output.unwrap().stdout).unwrap());
-        } else {
-            eprintln!("{}", String::from_utf8(output.unwrap().stderr).unwrap());
+            .output()?;
+        let stdout: String = String::from_utf8_lossy(&output.stdout).to_string();
+        if !output.status.success() {
+            return Err(format!(
+                "Failed to mint gas for {}: {}",
+                self.address,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )
+            .into());
         }
-        Ok(())
+        println!("{}", stdout.trim());
+
+        let Some(transaction_hash) = transaction_hash_in_output(&stdout) else {
+            eprintln!("⚠️ cargo pbc did not report the mint transaction, its status is unknown");
+            return Ok(());
+        };
+        let client: TransactionClient =
+            TransactionClient::new(&get_rpc_endpoint("testnet")?, &get_chain_id("testnet")?);
+        let tracker: TransactionTracker = TransactionTracker::new(&client, TRANSACTION_VALIDITY_MS);
+        let sent: SentTransaction = SentTransaction {
+            transaction_hash,
+            destination_shard: None,
+        };
+        let trace: TransactionTrace = block_on(tracker.wait(&sent))??;
+        println!("{}", trace.render());
+        trace.ensure_succeeded()
     }
 
     /// Gets the profile's private key
@@ -376,14 +407,11 @@ mod tests {
     use super::*;
     use crate::utils::utils::setup_test_environment;
 
-    fn cleanup(original_dir: PathBuf) {
-        std::env::set_current_dir(original_dir).unwrap();
-    }
     #[test]
     fn test_create_new_default_account() {
-        let (temp_dir, _, original_dir) = setup_test_environment();
-        std::env::set_current_dir(temp_dir.path()).unwrap();
-        let account: Profile = Profile::default();
+        let (temp_dir, _) = setup_test_environment();
+        let context: ProjectContext = ProjectContext::at(temp_dir.path());
+        let account: Profile = Profile::for_project(&context);
         assert_eq!(
             account.clone().private_key().len() > 0,
             true,
@@ -395,25 +423,23 @@ mod tests {
             "address is not set"
         );
         assert_eq!(account.clone().network, "testnet", "network is not set");
-        cleanup(original_dir);
     }
 
     #[test]
     fn test_load_account_from_path_to_pk_invalid_path() {
-        let (temp_dir, _, original_dir) = setup_test_environment();
-        std::env::set_current_dir(temp_dir.path()).unwrap();
-        let mut account: Profile = Profile::default();
+        let (temp_dir, _) = setup_test_environment();
+        let context: ProjectContext = ProjectContext::at(temp_dir.path());
+        let mut account: Profile = Profile::for_project(&context);
         let result =
             account.load_account_from_path_to_pk(Some("testnet"), &PathBuf::from("invalid_path"));
         assert_eq!(result.is_err(), true, "should be an error");
         assert_eq!(result.err().unwrap().to_string(), "Failed to load account from path_to_pk: load_account_from_pk_file: Failed to read file: invalid_path");
-        cleanup(original_dir);
     }
 
     #[test]
     fn test_create_new_account_from_path() {
-        let (temp_dir, _, original_dir) = setup_test_environment();
-        std::env::set_current_dir(temp_dir.path()).unwrap();
+        let (temp_dir, _) = setup_test_environment();
+        let context: ProjectContext = ProjectContext::at(temp_dir.path());
         let account: Profile = Profile::new(ProfileConfig {
             network: Some("mainnet".to_string()),
             address: None,
@@ -423,7 +449,7 @@ mod tests {
                     .path()
                     .join("00d277aa1bf5702ab9fc690b04bd68b5a981095530.pk"),
             ),
-        })
+        }, &context)
         .unwrap();
         assert_eq!(
             account.clone().private_key().len() > 0,
@@ -436,16 +462,15 @@ mod tests {
             "address is not set"
         );
         assert_eq!(account.clone().network, "mainnet", "network is not set");
-        cleanup(original_dir);
     }
 
     #[test]
     fn test_load_account_from_path() {
-        let (temp_dir, _, original_dir) = setup_test_environment();
-        std::env::set_current_dir(temp_dir.path()).unwrap();
+        let (temp_dir, _) = setup_test_environment();
+        let context: ProjectContext = ProjectContext::at(temp_dir.path());
         // find a pk file in root
 
-        let mut account: Profile = Profile::default();
+        let mut account: Profile = Profile::for_project(&context);
         account
             .load_account_from_path_to_pk(
                 Some("testnet"),
@@ -475,6 +500,5 @@ mod tests {
             false,
             "path is not a file"
         );
-        cleanup(original_dir);
     }
 }
@@ -0,0 +1,304 @@
+use crate::client::transaction::{deployed_code_hashes, TransactionClient};
+use crate::commands::deploy::{
+    find_contract_artifact, find_deployments_dir, load_latest_deployments, Deployment,
+};
+use crate::commands::deployments::DeploymentHistory;
+use crate::commands::upgrade::resolve_deployment;
+use crate::utils::artifact::ContractArtifact;
+use crate::utils::constants::DEFAULT_NETWORK;
+use crate::utils::context::ProjectContext;
+use crate::utils::utils::{block_on, get_chain_id, get_rpc_endpoint};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// File in the deployments directory recording every verified deployment
+pub const VERIFIED_DEPLOYMENTS_FILE: &str = "verified.json";
+
+/// Outcome of comparing a deployed contract with the local build
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verification {
+    /// Code and ABI hashes match the local artifact
+    Verified,
+    /// Hashes that differ, as `(name, on-chain hash, local hash)`
+    Mismatch(Vec<(String, String, String)>),
+    /// The comparison could not be made
+    Unverifiable(String),
+}
+
+/// Verification of a single recorded deployment
+///
+/// # Fields
+/// * `deployment` - Recorded deployment
+/// * `outcome` - Result of the comparison
+#[derive(Debug, Clone)]
+pub struct VerifiedDeployment {
+    pub deployment: Deployment,
+    pub outcome: Verification,
+}
+
+/// Deployment that was found to match the local build
+///
+/// # Fields
+/// * `name` - Name of the deployed contract
+/// * `network` - Network the contract is deployed on
+/// * `address` - Address of the contract
+/// * `contract_hash` - Hex sha256 of the verified contract code
+/// * `abi_hash` - Hex sha256 of the verified ABI
+/// * `timestamp` - Unix timestamp of the verification
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct VerificationRecord {
+    pub name: String,
+    pub network: String,
+    pub address: String,
+    pub contract_hash: String,
+    pub abi_hash: String,
+    pub timestamp: String,
+}
+
+/// Compares deployed contracts with the artifacts in the project's release directory
+///
+/// # Fields
+/// * `contract` - Name or address of a single deployed contract, all of `deployment-latest.json` if None
+/// * `network` - Only verify deployments on this network
+/// * `context` - Project whose deployments are verified
+#[derive(Debug, Clone)]
+pub struct ContractVerifier {
+    pub contract: Option<String>,
+    pub network: Option<String>,
+    pub context: ProjectContext,
+}
+
+impl ContractVerifier {
+    /// Verifies the selected deployments
+    ///
+    /// # Returns
+    /// * `Result<Vec<VerifiedDeployment>>` - Outcome per deployment
+    pub fn verify(&self) -> Result<Vec<VerifiedDeployment>, Box<dyn std::error::Error>> {
+        let project_root: PathBuf = self.context.require_workspace_root()?;
+        let deployments: Vec<Deployment> = match self.contract.as_ref() {
+            Some(contract) => {
+                let history: DeploymentHistory = DeploymentHistory::for_project(&self.context)?;
+                vec![resolve_deployment(&history, contract, self.network.as_deref())?]
+            }
+            None => load_latest_deployments(&project_root)
+                .into_iter()
+                .filter(|deployment| {
+                    self.network
+                        .as_ref()
+                        .is_none_or(|network| deployment.network == *network)
+                })
+                .collect(),
+        };
+        if deployments.is_empty() {
+            return Err("No deployments recorded in deployment-latest.json".into());
+        }
+
+        let verified: Vec<VerifiedDeployment> = deployments
+            .into_iter()
+            .map(|deployment| {
+                let outcome: Verification = verify_deployment(&project_root, &deployment)
+                    .unwrap_or_else(|e| Verification::Unverifiable(e.to_string()));
+                VerifiedDeployment { deployment, outcome }
+            })
+            .collect();
+        if let Err(e) = record_verifications(&project_root, &verified) {
+            eprintln!("⚠️ Failed to record verified deployments: {}", e);
+        }
+        Ok(verified)
+    }
+}
+
+/// Loads the deployments recorded by earlier verifications
+///
+/// # Arguments
+/// * `project_root` - Root directory of project
+///
+/// # Returns
+/// * `Vec<VerificationRecord>` - Recorded verifications, empty if none exist
+pub fn load_verifications(project_root: &PathBuf) -> Vec<VerificationRecord> {
+    find_deployments_dir(project_root)
+        .and_then(|deployments_dir| fs::read_to_string(deployments_dir.join(VERIFIED_DEPLOYMENTS_FILE)).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Records the deployments that match the local build in verified.json
+///
+/// Records are kept across runs, a deployment verified again with the same code is
+/// only recorded once.
+///
+/// # Arguments
+/// * `project_root` - Root directory of project
+/// * `verified` - Outcomes returned by `ContractVerifier::verify`
+///
+/// # Returns
+/// * `Result<()>` - Ok if the records were written
+pub fn record_verifications(
+    project_root: &PathBuf,
+    verified: &[VerifiedDeployment],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let deployments_dir: PathBuf =
+        find_deployments_dir(project_root).ok_or("Failed to find target directory")?;
+    let timestamp: String = SystemTime::now()
+        .duration_since(UNIX_EPOCH)?
+        .as_secs()
+        .to_string();
+    let mut records: Vec<VerificationRecord> = load_verifications(project_root);
+    for VerifiedDeployment { deployment, outcome } in verified {
+        if *outcome != Verification::Verified {
+            continue;
+        }
+        let artifact_name: &str = deployment.code_version.as_deref().unwrap_or(&deployment.name);
+        let artifact: ContractArtifact = find_contract_artifact(project_root, artifact_name)?;
+        let record = VerificationRecord {
+            name: deployment.name.clone(),
+            network: deployment.network.clone(),
+            address: deployment.address.clone(),
+            contract_hash: hex::encode(artifact.contract_hash()),
+            abi_hash: hex::encode(artifact.abi_hash()),
+            timestamp: timestamp.clone(),
+        };
+        let known: bool = records.iter().any(|known| {
+            known.address == record.address
+                && known.network == record.network
+                && known.contract_hash == record.contract_hash
+                && known.abi_hash == record.abi_hash
+        });
+        if !known {
+            records.push(record);
+        }
+    }
+    fs::create_dir_all(&deployments_dir)?;
+    fs::write(
+        deployments_dir.join(VERIFIED_DEPLOYMENTS_FILE),
+        serde_json::to_string_pretty(&records)?,
+    )?;
+    Ok(())
+}
+
+/// Fetches a deployed contract and compares it with its local artifact
+///
+/// Upgraded contracts are compared with the artifact of the code they were upgraded to.
+///
+/// # Arguments
+/// * `project_root` - Root directory of project
+/// * `deployment` - Recorded deployment
+///
+/// # Returns
+/// * `Result<Verification>` - Outcome, Error if the artifact or contract could not be loaded
+pub fn verify_deployment(
+    project_root: &PathBuf,
+    deployment: &Deployment,
+) -> Result<Verification, Box<dyn std::error::Error>> {
+    let artifact_name: &str = deployment.code_version.as_deref().unwrap_or(&deployment.name);
+    let artifact: ContractArtifact = find_contract_artifact(project_root, artifact_name)?;
+    let network: &str = match deployment.network.as_str() {
+        "" => DEFAULT_NETWORK,
+        network => network,
+    };
+    let client: TransactionClient = TransactionClient::new(&get_rpc_endpoint(network)?, &get_chain_id(network)?);
+    let contract: Value = block_on(client.get_contract(&deployment.address))??;
+    Ok(compare_code(&artifact, &contract))
+}
+
+/// Compares the hashes of a local artifact with those of a contract document
+///
+/// # Arguments
+/// * `artifact` - Local contract code and ABI
+/// * `contract` - Contract document returned by `/chain/contracts/<address>`
+///
+/// # Returns
+/// * `Verification` - Outcome of the comparison
+pub fn compare_code(artifact: &ContractArtifact, contract: &Value) -> Verification {
+    let (contract_hash, abi_hash) = deployed_code_hashes(contract);
+    let mut mismatches: Vec<(String, String, String)> = Vec::new();
+    for (name, deployed, local) in [
+        ("contract", contract_hash, artifact.contract_hash()),
+        ("abi", abi_hash, artifact.abi_hash()),
+    ] {
+        match deployed {
+            None => {
+                return Verification::Unverifiable(format!("The node did not report the {} hash", name))
+            }
+            Some(deployed) if deployed != local => {
+                mismatches.push((name.to_string(), hex::encode(deployed), hex::encode(local)))
+            }
+            Some(_) => {}
+        }
+    }
+    match mismatches.is_empty() {
+        true => Verification::Verified,
+        false => Verification::Mismatch(mismatches),
+    }
+}
+
+/// Prints the outcome of each verified deployment
+///
+/// # Arguments
+/// * `verified` - Outcomes returned by `ContractVerifier::verify`
+///
+/// # Returns
+/// * `bool` - Whether every deployment was verified
+pub fn print_verification(verified: &[VerifiedDeployment]) -> bool {
+    let mut all_verified: bool = true;
+    for VerifiedDeployment { deployment, outcome } in verified {
+        let label: String = format!("{} ({}) {}", deployment.name, deployment.network, deployment.address);
+        match outcome {
+            Verification::Verified => println!("✅ {} matches the local build", label),
+            Verification::Mismatch(mismatches) => {
+                all_verified = false;
+                println!("❌ {} does not match the local build", label);
+                for (name, deployed, local) in mismatches {
+                    println!("    {:<8} on chain {}", name, deployed);
+                    println!("    {:<8} local    {}", "", local);
+                }
+            }
+            Verification::Unverifiable(reason) => {
+                all_verified = false;
+                println!("⚠️ {} could not be verified: {}", label, reason);
+            }
+        }
+    }
+    all_verified
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::artifact::ContractKind;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use serde_json::json;
+
+    #[test]
+    fn test_compare_code() {
+        let artifact = ContractArtifact {
+            kind: ContractKind::Public,
+            contract_bytes: b"wasm".to_vec(),
+            abi_bytes: b"abi".to_vec(),
+        };
+        let deployed = json!({
+            "contractIdentifier": hex::encode(artifact.contract_hash()),
+            "abi": STANDARD.encode(b"abi"),
+        });
+        assert_eq!(compare_code(&artifact, &deployed), Verification::Verified);
+
+        let changed_abi = json!({
+            "contractIdentifier": hex::encode(artifact.contract_hash()),
+            "abi": STANDARD.encode(b"old abi"),
+        });
+        match compare_code(&artifact, &changed_abi) {
+            Verification::Mismatch(mismatches) => {
+                assert_eq!(mismatches.len(), 1);
+                assert_eq!(mismatches[0].0, "abi");
+                assert_eq!(mismatches[0].2, hex::encode(artifact.abi_hash()));
+            }
+            outcome => panic!("unexpected outcome {:?}", outcome),
+        }
+
+        let missing = json!({"abi": STANDARD.encode(b"abi")});
+        assert!(matches!(compare_code(&artifact, &missing), Verification::Unverifiable(_)));
+    }
+}
@@ -2,19 +2,40 @@ use clap::Parser;
 
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::commands::user_profile::{Profile, ProfileConfig};
 use crate::utils::pbc_commands::{pbc_create_new_account, pbc_create_new_wallet};
 
 
+use crate::client::tracker::{TransactionTrace, TransactionTracker};
+use crate::client::transaction::{SentTransaction, TransactionClient};
+use crate::commands::call::{CallConfigs, ContractCall};
 use crate::commands::compile::ProjectCompiler;
-use crate::commands::deploy::{DeployConfigs, Deployer, DeploymentWithProfile};
+use crate::commands::deploy::{
+    order_deploy_groups, DeployConfigs, Deployer, DeploymentWithProfile, RunAddresses,
+};
+use crate::commands::deployments::{
+    print_deployment, print_deployment_diff, print_deployment_list, DeploymentHistory,
+    ExportFormat,
+};
+use crate::commands::doctor::{print_checks, Doctor, DoctorCheck};
+use crate::commands::hash::{print_contract_hashes, ContractHashQuery};
 use crate::commands::new::{NewProject, ProjectConfig};
+use crate::commands::state::StateQuery;
+use crate::commands::upgrade::{ContractUpgrade, UpgradeConfigs};
+use crate::commands::verify::{print_verification, ContractVerifier, VerifiedDeployment};
 
-use crate::utils::utils::{assert_partizee_project, get_address_from_pk, parse_deploy_args};
-use crate::utils::clap_cli::{Arguments, Commands, ProfileSubcommands};
-use crate::utils::fs_nav::{id_pbc_path};
+use crate::utils::utils::{
+    assert_partizee_project, block_on, get_address_from_pk, get_chain_id, get_rpc_endpoint,
+    parse_deploy_args, parse_gas_limits,
+};
+use crate::utils::artifact::ContractHashes;
+use crate::utils::clap_cli::{Arguments, Commands, DeploymentsSubcommands, ProfileSubcommands};
+use crate::utils::context::ProjectContext;
+use crate::utils::fs_nav::id_pbc_path;
+use crate::utils::journal::DeployJournal;
+use crate::utils::manifest::DeployManifest;
 use crate::utils::menus::{
     compile_menu, create_new_pbc_account_menu, create_new_wallet_menu, deploy_menu,
     new_project_menu, select_pk_menu
@@ -24,12 +45,14 @@ use crate::utils::constants::DEFAULT_NETWORK;
 #[allow(unused_variables, unused_assignments)]
 pub fn partizee() -> Result<(), Box<dyn std::error::Error>> {
     let partizee_cli: Arguments = Arguments::parse();
+    // every command works on this directory, the process never changes its working directory
+    let context: ProjectContext = ProjectContext::new(partizee_cli.project_dir.as_deref())?;
     match partizee_cli.commands {
         Commands::New {
             interactive,
             name,
             output_dir,
-            zero_knowledge, // for future use
+            zero_knowledge,
         } => {
             let new_project: NewProject;
             let mut interactive = interactive;
@@ -38,15 +61,15 @@ pub fn partizee() -> Result<(), Box<dyn std::error::Error>> {
                 interactive = true;
             }
             if interactive {
-                let menu_args: ProjectConfig = new_project_menu(name, output_dir)?;
+                let menu_args: ProjectConfig = new_project_menu(name, output_dir, zero_knowledge)?;
                 new_project = NewProject::new(menu_args)?;
             } else {
                 new_project = NewProject::new(ProjectConfig {
                     name: name.expect("must provide name for new project"),
                     output_dir: output_dir,
+                    zero_knowledge,
                 })?;
             }
-            // Pass zero_knowledge as needed
             new_project.create_new_project()?;
         }
         Commands::Compile {
@@ -55,8 +78,14 @@ pub fn partizee() -> Result<(), Box<dyn std::error::Error>> {
             files_to_compile,
             build_args,
             additional_args,
+            force,
+            jobs,
+            output,
+            watch,
+            deploy,
+            pk_path,
         } => {
-            assert_partizee_project()?;
+            assert_partizee_project(&context)?;
 
             // create a new ProjectCompiler with the provided args
             let compile_args: ProjectCompiler = ProjectCompiler {
@@ -64,6 +93,10 @@ pub fn partizee() -> Result<(), Box<dyn std::error::Error>> {
                 files: files_to_compile,
                 build_args: build_args,
                 additional_args: additional_args,
+                force,
+                jobs,
+                json: output == "json",
+                context: context.clone(),
             };
 
             let project_compiler: ProjectCompiler;
@@ -74,7 +107,16 @@ pub fn partizee() -> Result<(), Box<dyn std::error::Error>> {
                 project_compiler = ProjectCompiler::new(compile_args);
             }
 
-            project_compiler.compile_contracts()?;
+            if watch {
+                let deploy_profile: Option<PathBuf> = match (deploy, pk_path.map(PathBuf::from)) {
+                    (false, _) => None,
+                    (true, Some(path)) if path.is_file() => Some(path),
+                    (true, _) => Some(select_pk_menu(&context)?),
+                };
+                project_compiler.watch(deploy_profile)?;
+            } else {
+                project_compiler.compile_contracts()?;
+            }
         }
         Commands::Deploy {
             interactive,
@@ -82,32 +124,63 @@ pub fn partizee() -> Result<(), Box<dyn std::error::Error>> {
             contract_names,
             deploy_args,
             pk_path,
+            manifest,
+            gas,
+            dry_run,
+            resume,
+            confirm_mainnet,
         } => {
-            assert_partizee_project()?;
+            assert_partizee_project(&context)?;
             // check if the project is compiled
+            let project_root: PathBuf = context.require_workspace_root()?;
+            let deploy_manifest: Option<DeployManifest> = DeployManifest::find(
+                &project_root,
+                manifest.map(|path| context.resolve(Path::new(&path))).as_ref(),
+            )?;
 
             let mut use_interactive: bool = interactive;
-            // if all args are empty open interactive menu
+            // if all args are empty and there is no manifest open interactive menu
             if  custom_net.is_none()
                 && contract_names.is_none()
                 && deploy_args.is_none()
                 && pk_path.is_none()
+                && gas.is_none()
+                && resume.is_none()
+                && deploy_manifest.is_none()
             {
                 use_interactive = true;
             }
 
-            let mut deployer: DeploymentWithProfile;
+            let mut deployers: Vec<DeploymentWithProfile> = Vec::new();
+
+            // a resumed run deploys its own contracts to its own network unless told otherwise
+            let resume_journal: Option<DeployJournal> = match resume.as_ref() {
+                Some(run_id) => Some(DeployJournal::find(&project_root, run_id)?),
+                None => None,
+            };
+            let custom_net: Option<String> = custom_net.or(resume_journal
+                .as_ref()
+                .map(|journal| journal.network.clone()));
 
             // if no contracts are provided, get all contract names from the project
             let mut contracts_to_deploy: Option<Vec<String>> = None;
             
-                contracts_to_deploy = contract_names;
+                contracts_to_deploy = contract_names.or(resume_journal
+                    .as_ref()
+                    .map(|journal| journal.contract_names()));
 
             let mut deployer_args_hashmap: Option<HashMap<String, Vec<String>>> = None;
             if deploy_args.is_some() {
+            // contract names in the manifest also split the positional args
+            let split_names: Vec<String> = contracts_to_deploy.clone().unwrap_or(
+                deploy_manifest
+                    .as_ref()
+                    .map(|manifest| manifest.contract_names())
+                    .unwrap_or(Vec::new()),
+            );
             let parsed_deploy_args: Option<HashMap<String, Vec<String>>> = parse_deploy_args(
                 deploy_args,
-                contracts_to_deploy.as_ref().unwrap_or(&Vec::new()).clone(),
+                split_names,
             );
             deployer_args_hashmap = parsed_deploy_args;
            }
@@ -118,16 +191,21 @@ pub fn partizee() -> Result<(), Box<dyn std::error::Error>> {
                 contract_names: contracts_to_deploy.unwrap_or(Vec::new()),
                 deployer_args: deployer_args_hashmap,
                 path_to_pk: pk_path.clone().map(|path| PathBuf::from(path)),
+                artifacts: None,
+                gas_limits: parse_gas_limits(gas)?,
             };
 
             // if interactive, get options from interactive menu and pass deployer_args as needed
             if use_interactive {
-                let menu_args: DeployConfigs = deploy_menu(config)?;
+                let menu_args: DeployConfigs = deploy_menu(config, &context)?;
                 let mut deployer_args: Deployer = Deployer {
                     network: menu_args.network.clone().unwrap_or(DEFAULT_NETWORK.to_string()),
                     contract_names: menu_args.contract_names.clone(),
                     deployer_args: menu_args.deployer_args.clone().unwrap_or(HashMap::new()),
                     path_to_pk: menu_args.path_to_pk.unwrap_or(PathBuf::from("")).clone(),
+                    artifacts: menu_args.artifacts.unwrap_or(HashMap::new()),
+                    gas_limits: menu_args.gas_limits.unwrap_or(HashMap::new()),
+                    confirm_mainnet,
                 };
                 //merge deployer_args with menu_args.deployer_args
                 let mut deployer_args_hashmap: HashMap<String, Vec<String>> = HashMap::new();
@@ -137,34 +215,214 @@ pub fn partizee() -> Result<(), Box<dyn std::error::Error>> {
 
                 println!("deployer_args: {:#?}", deployer_args);
 
-                deployer = DeploymentWithProfile::new(deployer_args);
+                deployers.push(DeploymentWithProfile::new(deployer_args, context.clone()));
             } else {
-                let final_pk_path: PathBuf;
-                if config.path_to_pk.is_none() {
-                    let pk_path: PathBuf = select_pk_menu()?;
-                    final_pk_path = pk_path;
-                } else {
-                    // if passed in path is a file, use it, otherwise select a new account
-                    if config.path_to_pk.as_ref().unwrap().is_file() {
-                        final_pk_path = config.path_to_pk.clone().unwrap();
-                    } else {
-                        let pk_path: PathBuf = select_pk_menu()?;
+                // a manifest yields one configuration per network and deployer profile
+                let configs: Vec<DeployConfigs> = match deploy_manifest.as_ref() {
+                    Some(deploy_manifest) => deploy_manifest.deploy_configs(&config, &project_root),
+                    None => vec![config],
+                };
+                for config in configs {
+                    let final_pk_path: PathBuf;
+                    if config.path_to_pk.is_none() {
+                        let pk_path: PathBuf = select_pk_menu(&context)?;
                         final_pk_path = pk_path;
+                    } else {
+                        // if passed in path is a file, use it, otherwise select a new account
+                        if config.path_to_pk.as_ref().unwrap().is_file() {
+                            final_pk_path = config.path_to_pk.clone().unwrap();
+                        } else {
+                            let pk_path: PathBuf = select_pk_menu(&context)?;
+                            final_pk_path = pk_path;
+                        }
                     }
+                    let deployer_args: Deployer = Deployer {
+                        network: config.network.unwrap_or(DEFAULT_NETWORK.to_string()),
+                        contract_names: config.contract_names,
+                        deployer_args: config.deployer_args.unwrap_or(HashMap::new()),
+                        path_to_pk: final_pk_path,
+                        artifacts: config.artifacts.unwrap_or(HashMap::new()),
+                        gas_limits: config.gas_limits.unwrap_or(HashMap::new()),
+                        confirm_mainnet,
+                    };
+                    deployers.push(DeploymentWithProfile::new(deployer_args, context.clone()));
                 }
-                let deployer_args: Deployer = Deployer {
-                    network: config.network.unwrap_or(DEFAULT_NETWORK.to_string()),
-                    contract_names: config.contract_names,
-                    deployer_args: config.deployer_args.unwrap_or(HashMap::new()),
-                    path_to_pk: final_pk_path,
+            }
+            // only the group deployed to the run's network from the run's account resumes it
+            if let Some(journal) = resume_journal.as_ref() {
+                if !deployers.iter().any(|deployer| deployer.resumes(journal)) {
+                    return Err(format!(
+                        "Run {} deployed to {} from {}, no deploy group uses that network and account",
+                        journal.run_id, journal.network, journal.deployer
+                    )
+                    .into());
+                }
+            }
+            // groups deploy after the groups whose contracts they reference, every group is
+            // attempted and the command fails if any of them did
+            let mut deployers: Vec<DeploymentWithProfile> = order_deploy_groups(deployers)?;
+            let mut run: RunAddresses = RunAddresses::new(&deployers);
+            let group_count: usize = deployers.len();
+            let mut failed_groups: usize = 0;
+            for deployer in deployers.iter_mut() {
+                let result: Result<(), Box<dyn std::error::Error>> = if dry_run {
+                    deployer.dry_run().map_err(|e| format!("❌ Dry run failed: {}", e).into())
+                } else {
+                    deployer
+                        .deploy_contracts(
+                            resume_journal
+                                .clone()
+                                .filter(|journal| deployer.resumes(journal)),
+                            &mut run,
+                        )
+                        .map_err(|e| format!("Contracts deployment failed: {}", e).into())
                 };
-                deployer = DeploymentWithProfile::new(deployer_args);
+                if let Err(e) = result {
+                    eprintln!("{}", e);
+                    failed_groups += 1;
+                }
             }
-            let result = deployer.deploy_contracts();
-            if !result.is_ok() {
-                eprintln!("Contracts deployment failed");
+            if failed_groups > 0 {
+                return Err(format!(
+                    "{} of {} deployment groups failed",
+                    failed_groups, group_count
+                )
+                .into());
             }
         }
+        Commands::Call {
+            contract,
+            action,
+            args,
+            custom_net,
+            pk_path,
+            gas,
+            wait,
+            confirm_mainnet,
+        } => {
+            let path_to_pk: PathBuf = match pk_path.map(PathBuf::from) {
+                Some(path) if path.is_file() => path,
+                _ => select_pk_menu(&context)?,
+            };
+            let call: ContractCall = ContractCall::new(CallConfigs {
+                contract,
+                action,
+                args,
+                network: custom_net,
+                path_to_pk,
+                gas_limit: gas,
+                wait,
+                confirm_mainnet,
+                context: context.clone(),
+            })?;
+            call.call()?;
+        }
+        Commands::State {
+            contract,
+            custom_net,
+            at_block,
+            field,
+        } => {
+            let state: serde_json::Value = StateQuery {
+                contract,
+                network: custom_net,
+                at_block,
+                field,
+                context: context.clone(),
+            }
+            .fetch()?;
+            println!("{}", serde_json::to_string_pretty(&state)?);
+        }
+        Commands::Tx {
+            hash,
+            custom_net,
+            shard,
+            wait,
+            timeout,
+        } => {
+            let network: String = custom_net.unwrap_or(DEFAULT_NETWORK.to_string());
+            let client: TransactionClient =
+                TransactionClient::new(&get_rpc_endpoint(&network)?, &get_chain_id(&network)?);
+            let tracker: TransactionTracker = TransactionTracker::new(&client, timeout as i64 * 1000);
+            let sent: SentTransaction = SentTransaction {
+                transaction_hash: hash.trim_start_matches("0x").to_lowercase(),
+                destination_shard: shard,
+            };
+            let trace: TransactionTrace = if wait {
+                block_on(tracker.wait(&sent))??
+            } else {
+                block_on(tracker.status(&sent))??
+            };
+            println!("{}", trace.render());
+            trace.ensure_succeeded()?;
+            if !trace.is_final() {
+                println!("Not final yet, wait for it with partizee tx {} --wait", sent.transaction_hash);
+            }
+        }
+        Commands::Verify { contract, custom_net } => {
+            assert_partizee_project(&context)?;
+            let verified: Vec<VerifiedDeployment> = ContractVerifier {
+                contract,
+                network: custom_net,
+                context: context.clone(),
+            }
+            .verify()?;
+            if !print_verification(&verified) {
+                return Err("Deployed contracts do not match the local build".into());
+            }
+        }
+        Commands::Hash {
+            contract,
+            binder_hash,
+            binder_from,
+            custom_net,
+        } => {
+            assert_partizee_project(&context)?;
+            let hashes: ContractHashes = ContractHashQuery {
+                contract: contract.clone(),
+                binder_hash,
+                binder_from,
+                network: custom_net,
+                context: context.clone(),
+            }
+            .compute()?;
+            print_contract_hashes(&contract, &hashes);
+        }
+        Commands::Upgrade {
+            contract,
+            target,
+            custom_net,
+            pk_path,
+            allow_upgrade,
+            upgrade_rpc,
+            gas,
+            binder_hash,
+            confirm_mainnet,
+        } => {
+            assert_partizee_project(&context)?;
+            let path_to_pk: PathBuf = match pk_path.map(PathBuf::from) {
+                Some(path) if path.is_file() => path,
+                _ => select_pk_menu(&context)?,
+            };
+            let upgrade_rpc: Vec<u8> = match upgrade_rpc {
+                Some(upgrade_rpc) => hex::decode(upgrade_rpc.trim_start_matches("0x"))
+                    .map_err(|e| format!("Invalid --upgrade-rpc: {}", e))?,
+                None => Vec::new(),
+            };
+            let upgrade: ContractUpgrade = ContractUpgrade::new(UpgradeConfigs {
+                contract,
+                target,
+                network: custom_net,
+                path_to_pk,
+                allow_upgrade,
+                upgrade_rpc,
+                gas_limit: gas,
+                binder_hash,
+                confirm_mainnet,
+                context: context.clone(),
+            })?;
+            upgrade.upgrade()?;
+        }
         Commands::Profile { commands } => match commands {
             ProfileSubcommands::ProfileCreate { shared_args } => {
                 let mut interactive: bool = shared_args.interactive;
@@ -193,14 +451,14 @@ pub fn partizee() -> Result<(), Box<dyn std::error::Error>> {
             }
             ProfileSubcommands::ProfileShow { shared_args } => {
                 if shared_args.interactive {
-                    let accout_path: PathBuf = select_pk_menu().expect("Failed to select account");
+                    let accout_path: PathBuf = select_pk_menu(&context).expect("Failed to select account");
                     let account_config: ProfileConfig = ProfileConfig {
                         network: shared_args.network,
                         address: shared_args.address,
                         private_key: None,
                         path_to_pk: Some(accout_path),
                     };
-                    let account: Profile = Profile::new(account_config).unwrap();
+                    let account: Profile = Profile::new(account_config, &context).unwrap();
 
                     let account_output: String = account.show_account()?;
                     println!("{}", account_output);
@@ -213,7 +471,7 @@ pub fn partizee() -> Result<(), Box<dyn std::error::Error>> {
                         path_to_pk: None,
                     };
 
-                    match Profile::new(account_config) {
+                    match Profile::new(account_config, &context) {
                         Ok(account) => {
                             let account_output = account.show_account()?;
                             println!("{}", account_output);
@@ -233,14 +491,14 @@ pub fn partizee() -> Result<(), Box<dyn std::error::Error>> {
                 }
 
                 if interactive {
-                    let pk_path: PathBuf = select_pk_menu().expect("Failed to select account");
+                    let pk_path: PathBuf = select_pk_menu(&context).expect("Failed to select account");
                     let account_config: ProfileConfig = ProfileConfig {
                         network: shared_args.network,
                         address: shared_args.address,
                         private_key: None,
                         path_to_pk: Some(pk_path),
                     };
-                    let account: Profile = Profile::new(account_config).unwrap();
+                    let account: Profile = Profile::new(account_config, &context).unwrap();
                     account.mint_gas()?;
                 } else {
                     let mut address: Option<String> = shared_args.address;
@@ -258,7 +516,7 @@ pub fn partizee() -> Result<(), Box<dyn std::error::Error>> {
                             return Err("Cannot derive private key from address alone".into());
                         }
                         (false, false, false) => {
-                            pk_path = Some(select_pk_menu().expect("Failed to select account"));
+                            pk_path = Some(select_pk_menu(&context).expect("Failed to select account"));
                             private_key = Some(fs::read_to_string(&pk_path.clone().unwrap())?);
                             address = Some(get_address_from_pk(&private_key.clone().unwrap())?);
                         }
@@ -275,11 +533,61 @@ pub fn partizee() -> Result<(), Box<dyn std::error::Error>> {
                         address: address.clone(),
                         private_key: private_key.clone(),
                         path_to_pk: pk_path.clone(),
-                    })?;
+                    }, &context)?;
                     account.mint_gas()?;
                 }
             }
         },
+        Commands::Deployments { commands } => {
+            assert_partizee_project(&context)?;
+            let history: DeploymentHistory = DeploymentHistory::for_project(&context)?;
+            match commands {
+                DeploymentsSubcommands::DeploymentsList => print_deployment_list(&history),
+                DeploymentsSubcommands::DeploymentsShow {
+                    contract,
+                    snapshot,
+                    custom_net,
+                } => {
+                    let deployment = history
+                        .latest(&contract, snapshot.as_deref(), custom_net.as_deref())
+                        .ok_or_else(|| format!("No deployment of {} recorded", contract))?;
+                    print_deployment(deployment);
+                }
+                DeploymentsSubcommands::DeploymentsAddress { contract, custom_net } => {
+                    // bare output so the address can be captured by shell scripts
+                    let deployment = history
+                        .latest(&contract, None, custom_net.as_deref())
+                        .ok_or_else(|| format!("No deployment of {} recorded", contract))?;
+                    println!("{}", deployment.address);
+                }
+                DeploymentsSubcommands::DeploymentsDiff { from, to } => {
+                    let changes = history.diff(from.as_deref(), to.as_deref())?;
+                    print_deployment_diff(&changes);
+                }
+                DeploymentsSubcommands::DeploymentsExport { format, output } => {
+                    let exported: String = history.export(ExportFormat::parse(&format)?);
+                    match output {
+                        Some(path) => {
+                            fs::write(&path, exported)
+                                .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+                            println!("Exported deployment history to {}", path);
+                        }
+                        None => print!("{}", exported),
+                    }
+                }
+            }
+        }
+        Commands::Doctor { offline } => {
+            let checks: Vec<DoctorCheck> = Doctor {
+                context: context.clone(),
+                offline,
+            }
+            .run();
+            let failed: usize = print_checks(&checks);
+            if failed > 0 {
+                return Err(format!("{} doctor check(s) failed", failed).into());
+            }
+        }
     }
     Ok(())
 }
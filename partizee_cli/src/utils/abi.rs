@@ -0,0 +1,1023 @@
+use crate::client::rpc::RpcWriter;
+use crate::utils::cryptography::address::ADDRESS_LENGTH;
+use serde_json::Value;
+
+/// Magic bytes at the start of every .abi file
+pub const ABI_HEADER: &[u8; 6] = b"PBCABI";
+/// Major version of the ABI client format this reader understands, minor versions only add types and hooks
+pub const SUPPORTED_CLIENT_MAJOR_VERSION: u8 = 5;
+/// Highest address type prefix (account, system, public, zk and governance addresses)
+pub const MAX_ADDRESS_PREFIX: u8 = 0x04;
+
+/// Kind of a function in the ABI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FnKind {
+    Init,
+    Action,
+    Callback,
+    ZkSecretInput,
+    ZkVarInputted,
+    ZkVarRejected,
+    ZkComputeComplete,
+    ZkVarOpened,
+    ZkUserVarOpened,
+    ZkAttestationComplete,
+    ZkSecretInputWithExplicitType,
+    ZkExternalEvent,
+}
+
+/// Type of a value as described by the ABI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeSpec {
+    Named(u8),
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    String,
+    Bool,
+    Address,
+    Hash,
+    PublicKey,
+    Signature,
+    BlsPublicKey,
+    BlsSignature,
+    Vec(Box<TypeSpec>),
+    Map(Box<TypeSpec>, Box<TypeSpec>),
+    Set(Box<TypeSpec>),
+    SizedByteArray(u8),
+    Option(Box<TypeSpec>),
+    AvlTreeMap(Box<TypeSpec>, Box<TypeSpec>),
+    SizedArray(Box<TypeSpec>, u8),
+}
+
+/// Named and typed field of a struct or function
+///
+/// # Fields
+/// * `name` - Field or argument name
+/// * `type_spec` - Type of the value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldAbi {
+    pub name: String,
+    pub type_spec: TypeSpec,
+}
+
+/// Struct or enum declared by the contract
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NamedTypeSpec {
+    Struct {
+        name: String,
+        fields: Vec<FieldAbi>,
+    },
+    /// Enum variants as pairs of discriminant and index of the variant's struct
+    Enum {
+        name: String,
+        variants: Vec<(u8, u8)>,
+    },
+}
+
+/// Function exposed by the contract
+///
+/// # Fields
+/// * `kind` - Init, action, callback or ZK hook
+/// * `name` - Function name
+/// * `shortname` - LEB128 encoded shortname
+/// * `arguments` - Public arguments in RPC order
+/// * `secret_argument` - Secret input of explicitly typed ZK inputs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FnAbi {
+    pub kind: FnKind,
+    pub name: String,
+    pub shortname: Vec<u8>,
+    pub arguments: Vec<FieldAbi>,
+    pub secret_argument: Option<FieldAbi>,
+}
+
+/// Parsed contents of a .abi file
+///
+/// # Fields
+/// * `binder_version` - Version of the binder the contract was built for
+/// * `client_version` - Version of the ABI format
+/// * `named_types` - Structs and enums declared by the contract
+/// * `functions` - Init, actions, callbacks and hooks
+/// * `state_type` - Type of the contract state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractAbi {
+    pub binder_version: [u8; 3],
+    pub client_version: [u8; 3],
+    pub named_types: Vec<NamedTypeSpec>,
+    pub functions: Vec<FnAbi>,
+    pub state_type: TypeSpec,
+}
+
+/// Cursor over the big-endian ABI encoding
+struct AbiReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> AbiReader<'a> {
+    fn read_bytes(&mut self, length: usize) -> Result<&'a [u8], Box<dyn std::error::Error>> {
+        if self.position + length > self.bytes.len() {
+            return Err(format!("ABI ended unexpectedly at byte {}", self.position).into());
+        }
+        let bytes: &'a [u8] = &self.bytes[self.position..self.position + length];
+        self.position += length;
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, Box<dyn std::error::Error>> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_u32(&mut self) -> Result<u32, Box<dyn std::error::Error>> {
+        Ok(u32::from_be_bytes(self.read_bytes(4)?.try_into()?))
+    }
+
+    fn read_identifier(&mut self) -> Result<String, Box<dyn std::error::Error>> {
+        let length: usize = self.read_u32()? as usize;
+        Ok(String::from_utf8(self.read_bytes(length)?.to_vec())?)
+    }
+
+    fn read_leb128(&mut self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let mut shortname: Vec<u8> = Vec::new();
+        loop {
+            let byte: u8 = self.read_u8()?;
+            shortname.push(byte);
+            if byte & 0x80 == 0 {
+                return Ok(shortname);
+            }
+            if shortname.len() > 5 {
+                return Err("ABI shortname is longer than 5 bytes".into());
+            }
+        }
+    }
+
+    fn read_list<T>(
+        &mut self,
+        read_element: impl Fn(&mut Self) -> Result<T, Box<dyn std::error::Error>>,
+    ) -> Result<Vec<T>, Box<dyn std::error::Error>> {
+        let length: u32 = self.read_u32()?;
+        (0..length).map(|_| read_element(self)).collect()
+    }
+
+    fn read_field(&mut self) -> Result<FieldAbi, Box<dyn std::error::Error>> {
+        Ok(FieldAbi {
+            name: self.read_identifier()?,
+            type_spec: self.read_type_spec()?,
+        })
+    }
+
+    fn read_type_spec(&mut self) -> Result<TypeSpec, Box<dyn std::error::Error>> {
+        let type_id: u8 = self.read_u8()?;
+        let type_spec: TypeSpec = match type_id {
+            0x00 => TypeSpec::Named(self.read_u8()?),
+            0x01 => TypeSpec::U8,
+            0x02 => TypeSpec::U16,
+            0x03 => TypeSpec::U32,
+            0x04 => TypeSpec::U64,
+            0x05 => TypeSpec::U128,
+            0x06 => TypeSpec::I8,
+            0x07 => TypeSpec::I16,
+            0x08 => TypeSpec::I32,
+            0x09 => TypeSpec::I64,
+            0x0a => TypeSpec::I128,
+            0x0b => TypeSpec::String,
+            0x0c => TypeSpec::Bool,
+            0x0d => TypeSpec::Address,
+            0x0e => TypeSpec::Vec(Box::new(self.read_type_spec()?)),
+            0x0f => TypeSpec::Map(
+                Box::new(self.read_type_spec()?),
+                Box::new(self.read_type_spec()?),
+            ),
+            0x10 => TypeSpec::Set(Box::new(self.read_type_spec()?)),
+            0x11 => TypeSpec::SizedByteArray(self.read_u8()?),
+            0x12 => TypeSpec::Option(Box::new(self.read_type_spec()?)),
+            0x13 => TypeSpec::Hash,
+            0x14 => TypeSpec::PublicKey,
+            0x15 => TypeSpec::Signature,
+            0x16 => TypeSpec::BlsPublicKey,
+            0x17 => TypeSpec::BlsSignature,
+            0x18 => TypeSpec::U256,
+            0x19 => TypeSpec::AvlTreeMap(
+                Box::new(self.read_type_spec()?),
+                Box::new(self.read_type_spec()?),
+            ),
+            0x1a => {
+                let element: TypeSpec = self.read_type_spec()?;
+                TypeSpec::SizedArray(Box::new(element), self.read_u8()?)
+            }
+            unknown => return Err(format!("Unknown ABI type id 0x{:02x}", unknown).into()),
+        };
+        Ok(type_spec)
+    }
+
+    fn read_named_type(&mut self) -> Result<NamedTypeSpec, Box<dyn std::error::Error>> {
+        match self.read_u8()? {
+            0x01 => Ok(NamedTypeSpec::Struct {
+                name: self.read_identifier()?,
+                fields: self.read_list(|reader| reader.read_field())?,
+            }),
+            0x02 => Ok(NamedTypeSpec::Enum {
+                name: self.read_identifier()?,
+                variants: self.read_list(|reader| {
+                    let discriminant: u8 = reader.read_u8()?;
+                    match reader.read_type_spec()? {
+                        TypeSpec::Named(index) => Ok((discriminant, index)),
+                        _ => Err("ABI enum variant must refer to a struct".into()),
+                    }
+                })?,
+            }),
+            unknown => Err(format!("Unknown ABI named type kind 0x{:02x}", unknown).into()),
+        }
+    }
+
+    fn read_fn(&mut self) -> Result<FnAbi, Box<dyn std::error::Error>> {
+        let kind: FnKind = match self.read_u8()? {
+            0x01 => FnKind::Init,
+            0x02 => FnKind::Action,
+            0x03 => FnKind::Callback,
+            0x10 => FnKind::ZkSecretInput,
+            0x11 => FnKind::ZkVarInputted,
+            0x12 => FnKind::ZkVarRejected,
+            0x13 => FnKind::ZkComputeComplete,
+            0x14 => FnKind::ZkVarOpened,
+            0x15 => FnKind::ZkUserVarOpened,
+            0x16 => FnKind::ZkAttestationComplete,
+            0x17 => FnKind::ZkSecretInputWithExplicitType,
+            0x18 => FnKind::ZkExternalEvent,
+            unknown => return Err(format!("Unknown ABI function kind 0x{:02x}", unknown).into()),
+        };
+        let name: String = self.read_identifier()?;
+        let shortname: Vec<u8> = self.read_leb128()?;
+        let arguments: Vec<FieldAbi> = self.read_list(|reader| reader.read_field())?;
+        let secret_argument: Option<FieldAbi> = if kind == FnKind::ZkSecretInputWithExplicitType {
+            Some(self.read_field()?)
+        } else {
+            None
+        };
+        Ok(FnAbi {
+            kind,
+            name,
+            shortname,
+            arguments,
+            secret_argument,
+        })
+    }
+}
+
+impl ContractAbi {
+    /// Parses the binary ABI format
+    ///
+    /// The SDK's `pbc_abi` crate describes and writes the ABI when a contract is compiled,
+    /// it has no reader for .abi files, so the format is read here.
+    ///
+    /// # Arguments
+    /// * `bytes` - Content of a .abi file or the ABI section of a .pbc file
+    ///
+    /// # Returns
+    /// * `Result<ContractAbi>` - Parsed ABI
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut reader = AbiReader { bytes, position: 0 };
+        if reader.read_bytes(ABI_HEADER.len())? != ABI_HEADER {
+            return Err("ContractAbi::from_bytes: Missing PBCABI header".into());
+        }
+        let binder_version: [u8; 3] = reader.read_bytes(3)?.try_into()?;
+        let client_version: [u8; 3] = reader.read_bytes(3)?.try_into()?;
+        if client_version[0] != SUPPORTED_CLIENT_MAJOR_VERSION {
+            return Err(format!(
+                "ContractAbi::from_bytes: Unsupported ABI version {}.{}.{}, expected {}.x.x",
+                client_version[0],
+                client_version[1],
+                client_version[2],
+                SUPPORTED_CLIENT_MAJOR_VERSION
+            )
+            .into());
+        }
+        let named_types: Vec<NamedTypeSpec> = reader.read_list(|reader| reader.read_named_type())?;
+        let functions: Vec<FnAbi> = reader.read_list(|reader| reader.read_fn())?;
+        let state_type: TypeSpec = reader.read_type_spec()?;
+        Ok(Self {
+            binder_version,
+            client_version,
+            named_types,
+            functions,
+            state_type,
+        })
+    }
+
+    /// The contract's `#[init]` function
+    pub fn init_function(&self) -> Result<&FnAbi, Box<dyn std::error::Error>> {
+        self.functions
+            .iter()
+            .find(|function| function.kind == FnKind::Init)
+            .ok_or_else(|| "ABI has no init function".into())
+    }
+
+    /// Looks up a function by name
+    pub fn function(&self, name: &str) -> Option<&FnAbi> {
+        self.functions.iter().find(|function| function.name == name)
+    }
+
+    /// Looks up a named type by index
+    pub fn named_type(&self, index: u8) -> Result<&NamedTypeSpec, Box<dyn std::error::Error>> {
+        self.named_types
+            .get(index as usize)
+            .ok_or_else(|| format!("ABI refers to unknown named type {}", index).into())
+    }
+
+    /// Human readable name of a type
+    ///
+    /// # Arguments
+    /// * `type_spec` - Type to describe
+    ///
+    /// # Returns
+    /// * `String` - Rust style name, e.g. `Vec<u8>`
+    pub fn type_name(&self, type_spec: &TypeSpec) -> String {
+        match type_spec {
+            TypeSpec::Named(index) => match self.named_types.get(*index as usize) {
+                Some(NamedTypeSpec::Struct { name, .. }) | Some(NamedTypeSpec::Enum { name, .. }) => {
+                    name.clone()
+                }
+                None => format!("<unknown type {}>", index),
+            },
+            TypeSpec::U8 => "u8".to_string(),
+            TypeSpec::U16 => "u16".to_string(),
+            TypeSpec::U32 => "u32".to_string(),
+            TypeSpec::U64 => "u64".to_string(),
+            TypeSpec::U128 => "u128".to_string(),
+            TypeSpec::U256 => "U256".to_string(),
+            TypeSpec::I8 => "i8".to_string(),
+            TypeSpec::I16 => "i16".to_string(),
+            TypeSpec::I32 => "i32".to_string(),
+            TypeSpec::I64 => "i64".to_string(),
+            TypeSpec::I128 => "i128".to_string(),
+            TypeSpec::String => "String".to_string(),
+            TypeSpec::Bool => "bool".to_string(),
+            TypeSpec::Address => "Address".to_string(),
+            TypeSpec::Hash => "Hash".to_string(),
+            TypeSpec::PublicKey => "PublicKey".to_string(),
+            TypeSpec::Signature => "Signature".to_string(),
+            TypeSpec::BlsPublicKey => "BlsPublicKey".to_string(),
+            TypeSpec::BlsSignature => "BlsSignature".to_string(),
+            TypeSpec::Vec(element) => format!("Vec<{}>", self.type_name(element)),
+            TypeSpec::Map(key, value) => {
+                format!("Map<{}, {}>", self.type_name(key), self.type_name(value))
+            }
+            TypeSpec::Set(element) => format!("Set<{}>", self.type_name(element)),
+            TypeSpec::SizedByteArray(length) => format!("[u8; {}]", length),
+            TypeSpec::Option(element) => format!("Option<{}>", self.type_name(element)),
+            TypeSpec::AvlTreeMap(key, value) => {
+                format!("AvlTreeMap<{}, {}>", self.type_name(key), self.type_name(value))
+            }
+            TypeSpec::SizedArray(element, length) => {
+                format!("[{}; {}]", self.type_name(element), length)
+            }
+        }
+    }
+
+    /// Serializes the RPC calling a function with arguments given as strings
+    ///
+    /// Simple types are written as plain text, e.g. `42`, `true` or a hex address,
+    /// U256 also as `0x` prefixed hex. Byte vectors and fixed size byte arrays are hex.
+    /// Options are `null` or written like their inner type. Structs, enums and other
+    /// collections are JSON.
+    ///
+    /// # Arguments
+    /// * `function` - Function to call
+    /// * `args` - One string per function argument
+    ///
+    /// # Returns
+    /// * `Result<Vec<u8>>` - Shortname followed by the serialized arguments
+    pub fn encode_call(
+        &self,
+        function: &FnAbi,
+        args: &[String],
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        if args.len() != function.arguments.len() {
+            let expected: Vec<String> = function
+                .arguments
+                .iter()
+                .map(|argument| {
+                    format!("{}: {}", argument.name, self.type_name(&argument.type_spec))
+                })
+                .collect();
+            return Err(format!(
+                "{} expects {} argument(s) ({}) but {} were given",
+                function.name,
+                function.arguments.len(),
+                expected.join(", "),
+                args.len()
+            )
+            .into());
+        }
+        let mut writer = RpcWriter::new();
+        writer.write_bytes(&function.shortname);
+        for (argument, arg) in function.arguments.iter().zip(args) {
+            self.encode_arg(&argument.type_spec, arg, &mut writer)
+                .map_err(|e| {
+                    format!(
+                        "Invalid argument {} ({}): {}",
+                        argument.name,
+                        self.type_name(&argument.type_spec),
+                        e
+                    )
+                })?;
+        }
+        Ok(writer.into_bytes())
+    }
+
+    /// Validates a single argument given as a string
+    ///
+    /// # Arguments
+    /// * `type_spec` - Expected type
+    /// * `arg` - Argument text
+    ///
+    /// # Returns
+    /// * `Result<()>` - Ok if the argument can be serialized as the type
+    pub fn validate_arg(&self, type_spec: &TypeSpec, arg: &str) -> Result<(), Box<dyn std::error::Error>> {
+        self.encode_arg(type_spec, arg, &mut RpcWriter::new())
+    }
+
+    fn encode_arg(
+        &self,
+        type_spec: &TypeSpec,
+        arg: &str,
+        writer: &mut RpcWriter,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let value: Value = match type_spec {
+            // options take null or the inner value, written the way the inner type is
+            TypeSpec::Option(element) => {
+                if arg.trim() == "null" {
+                    writer.write_u8(0);
+                } else {
+                    writer.write_u8(1);
+                    self.encode_arg(element, arg, writer)?;
+                }
+                return Ok(());
+            }
+            TypeSpec::Named(_)
+            | TypeSpec::Set(_)
+            | TypeSpec::Map(_, _)
+            | TypeSpec::AvlTreeMap(_, _)
+            | TypeSpec::SizedArray(_, _) => serde_json::from_str(arg)
+                .map_err(|e| format!("expected JSON: {}", e))?,
+            TypeSpec::Vec(element) if **element != TypeSpec::U8 => {
+                serde_json::from_str(arg).map_err(|e| format!("expected a JSON array: {}", e))?
+            }
+            _ => Value::String(arg.to_string()),
+        };
+        self.encode_value(type_spec, &value, writer)
+    }
+
+    fn encode_value(
+        &self,
+        type_spec: &TypeSpec,
+        value: &Value,
+        writer: &mut RpcWriter,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        match type_spec {
+            TypeSpec::U8 => {
+                writer.write_u8(parse_integer::<u8>(value)?);
+            }
+            TypeSpec::U16 => {
+                writer.write_bytes(&parse_integer::<u16>(value)?.to_be_bytes());
+            }
+            TypeSpec::U32 => {
+                writer.write_u32(parse_integer::<u32>(value)?);
+            }
+            TypeSpec::U64 => {
+                writer.write_bytes(&parse_integer::<u64>(value)?.to_be_bytes());
+            }
+            TypeSpec::U128 => {
+                writer.write_bytes(&parse_integer::<u128>(value)?.to_be_bytes());
+            }
+            TypeSpec::U256 => {
+                writer.write_bytes(&parse_u256(value)?);
+            }
+            TypeSpec::I8 => {
+                writer.write_bytes(&parse_integer::<i8>(value)?.to_be_bytes());
+            }
+            TypeSpec::I16 => {
+                writer.write_bytes(&parse_integer::<i16>(value)?.to_be_bytes());
+            }
+            TypeSpec::I32 => {
+                writer.write_i32(parse_integer::<i32>(value)?);
+            }
+            TypeSpec::I64 => {
+                writer.write_i64(parse_integer::<i64>(value)?);
+            }
+            TypeSpec::I128 => {
+                writer.write_bytes(&parse_integer::<i128>(value)?.to_be_bytes());
+            }
+            TypeSpec::String => {
+                let text: &str = value.as_str().ok_or("expected a string")?;
+                writer.write_string(text);
+            }
+            TypeSpec::Bool => {
+                let flag: bool = match value {
+                    Value::Bool(flag) => *flag,
+                    Value::String(text) => text
+                        .parse::<bool>()
+                        .map_err(|_| format!("expected true or false, got {}", text))?,
+                    _ => return Err("expected true or false".into()),
+                };
+                writer.write_u8(flag as u8);
+            }
+            TypeSpec::Address => {
+                writer.write_bytes(&parse_address(value)?);
+            }
+            TypeSpec::Hash => {
+                writer.write_bytes(&parse_hex(value, Some(32))?);
+            }
+            TypeSpec::PublicKey => {
+                writer.write_bytes(&parse_hex(value, Some(33))?);
+            }
+            TypeSpec::Signature => {
+                writer.write_bytes(&parse_hex(value, Some(65))?);
+            }
+            TypeSpec::BlsPublicKey => {
+                writer.write_bytes(&parse_hex(value, Some(96))?);
+            }
+            TypeSpec::BlsSignature => {
+                writer.write_bytes(&parse_hex(value, Some(48))?);
+            }
+            TypeSpec::SizedByteArray(length) => {
+                writer.write_bytes(&parse_hex(value, Some(*length as usize))?);
+            }
+            TypeSpec::Vec(element) if **element == TypeSpec::U8 && value.is_string() => {
+                writer.write_dynamic_bytes(&parse_hex(value, None)?);
+            }
+            TypeSpec::Vec(element) | TypeSpec::Set(element) => {
+                let elements: &Vec<Value> = value.as_array().ok_or("expected an array")?;
+                writer.write_i32(elements.len() as i32);
+                for element_value in elements {
+                    self.encode_value(element, element_value, writer)?;
+                }
+            }
+            TypeSpec::SizedArray(element, length) => {
+                let elements: &Vec<Value> = value.as_array().ok_or("expected an array")?;
+                if elements.len() != *length as usize {
+                    return Err(format!("expected {} elements, got {}", length, elements.len()).into());
+                }
+                for element_value in elements {
+                    self.encode_value(element, element_value, writer)?;
+                }
+            }
+            TypeSpec::Option(element) => {
+                if value.is_null() {
+                    writer.write_u8(0);
+                } else {
+                    writer.write_u8(1);
+                    self.encode_value(element, value, writer)?;
+                }
+            }
+            TypeSpec::Named(index) => self.encode_named(*index, value, writer)?,
+            TypeSpec::Map(_, _) | TypeSpec::AvlTreeMap(_, _) => {
+                return Err(format!("{} cannot be passed as an argument", self.type_name(type_spec)).into());
+            }
+        }
+        Ok(())
+    }
+
+    fn encode_named(
+        &self,
+        index: u8,
+        value: &Value,
+        writer: &mut RpcWriter,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        match self.named_type(index)? {
+            NamedTypeSpec::Struct { name, fields } => match value {
+                Value::Object(object) => {
+                    for field in fields {
+                        let field_value: &Value = object
+                            .get(&field.name)
+                            .ok_or_else(|| format!("{} is missing field {}", name, field.name))?;
+                        self.encode_value(&field.type_spec, field_value, writer)
+                            .map_err(|e| format!("{}.{}: {}", name, field.name, e))?;
+                    }
+                    Ok(())
+                }
+                Value::Array(values) if values.len() == fields.len() => {
+                    for (field, field_value) in fields.iter().zip(values) {
+                        self.encode_value(&field.type_spec, field_value, writer)
+                            .map_err(|e| format!("{}.{}: {}", name, field.name, e))?;
+                    }
+                    Ok(())
+                }
+                _ => Err(format!("expected a JSON object with the fields of {}", name).into()),
+            },
+            NamedTypeSpec::Enum { name, variants } => {
+                // enums are written as {"Variant": {..fields}}
+                let object = value
+                    .as_object()
+                    .filter(|object| object.len() == 1)
+                    .ok_or_else(|| format!("expected {{\"<variant>\": {{..}}}} for {}", name))?;
+                let (variant_name, variant_value) = object.iter().next().unwrap();
+                for (discriminant, variant_index) in variants {
+                    if let NamedTypeSpec::Struct {
+                        name: struct_name, ..
+                    } = self.named_type(*variant_index)?
+                    {
+                        if struct_name == variant_name {
+                            writer.write_u8(*discriminant);
+                            return self.encode_named(*variant_index, variant_value, writer);
+                        }
+                    }
+                }
+                Err(format!("{} has no variant {}", name, variant_name).into())
+            }
+        }
+    }
+}
+
+/// Parses an integer from a JSON number or decimal string
+fn parse_integer<T>(value: &Value) -> Result<T, Box<dyn std::error::Error>>
+where
+    T: std::str::FromStr,
+{
+    let text: String = match value {
+        Value::Number(number) => number.to_string(),
+        Value::String(text) => text.trim().to_string(),
+        _ => return Err("expected an integer".into()),
+    };
+    text.parse::<T>().map_err(|_| {
+        format!(
+            "{} is not a valid {}",
+            text,
+            std::any::type_name::<T>()
+        )
+        .into()
+    })
+}
+
+/// Parses an unsigned 256 bit integer from a decimal or `0x` prefixed hex string
+///
+/// # Arguments
+/// * `value` - JSON number or string
+///
+/// # Returns
+/// * `Result<[u8; 32]>` - Big-endian bytes of the integer
+fn parse_u256(value: &Value) -> Result<[u8; 32], Box<dyn std::error::Error>> {
+    let text: String = match value {
+        Value::Number(number) => number.to_string(),
+        Value::String(text) => text.trim().to_string(),
+        _ => return Err("expected an integer".into()),
+    };
+    let invalid = || format!("{} is not a valid U256", text);
+    let mut bytes: [u8; 32] = [0; 32];
+    if let Some(digits) = text.strip_prefix("0x") {
+        if digits.is_empty() || digits.len() > 64 {
+            return Err(invalid().into());
+        }
+        let decoded: Vec<u8> = hex::decode(format!("{:0>64}", digits)).map_err(|_| invalid())?;
+        bytes.copy_from_slice(&decoded);
+        return Ok(bytes);
+    }
+    if text.is_empty() {
+        return Err(invalid().into());
+    }
+    for character in text.chars() {
+        let digit: u32 = character.to_digit(10).ok_or_else(invalid)?;
+        // multiply by ten and add the digit, from the least significant byte up
+        let mut carry: u32 = digit;
+        for byte in bytes.iter_mut().rev() {
+            let product: u32 = *byte as u32 * 10 + carry;
+            *byte = product as u8;
+            carry = product >> 8;
+        }
+        if carry != 0 {
+            return Err(format!("{} does not fit in a U256", text).into());
+        }
+    }
+    Ok(bytes)
+}
+
+/// Parses hex encoded bytes, optionally with a required length
+fn parse_hex(value: &Value, length: Option<usize>) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let text: &str = value.as_str().ok_or("expected a hex string")?;
+    let bytes: Vec<u8> = hex::decode(text.trim().trim_start_matches("0x"))
+        .map_err(|e| format!("{} is not valid hex: {}", text, e))?;
+    if let Some(length) = length {
+        if bytes.len() != length {
+            return Err(format!("expected {} bytes, got {}", length, bytes.len()).into());
+        }
+    }
+    Ok(bytes)
+}
+
+/// Parses a hex encoded blockchain address and checks its type prefix
+fn parse_address(value: &Value) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let text: &str = value.as_str().ok_or("expected an address")?;
+    if text.trim().trim_start_matches("0x").len() != ADDRESS_LENGTH * 2 {
+        return Err(format!("address must be {} hex characters", ADDRESS_LENGTH * 2).into());
+    }
+    let bytes: Vec<u8> = parse_hex(value, Some(ADDRESS_LENGTH))?;
+    if bytes[0] > MAX_ADDRESS_PREFIX {
+        return Err(format!("unknown address type prefix {:02x}", bytes[0]).into());
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn identifier(name: &str) -> Vec<u8> {
+        let mut bytes: Vec<u8> = (name.len() as u32).to_be_bytes().to_vec();
+        bytes.extend_from_slice(name.as_bytes());
+        bytes
+    }
+
+    /// Builds the ABI of a counter contract with an `Address` init argument,
+    /// an `increment` action and a `Config { limit: u32, owner: Option<Address> }` struct
+    pub fn counter_abi_bytes() -> Vec<u8> {
+        let mut bytes: Vec<u8> = ABI_HEADER.to_vec();
+        bytes.extend_from_slice(&[10, 0, 0, 5, 4, 0]);
+        // named types: Config, State
+        bytes.extend_from_slice(&2u32.to_be_bytes());
+        bytes.push(0x01);
+        bytes.extend(identifier("Config"));
+        bytes.extend_from_slice(&2u32.to_be_bytes());
+        bytes.extend(identifier("limit"));
+        bytes.push(0x03);
+        bytes.extend(identifier("owner"));
+        bytes.extend_from_slice(&[0x12, 0x0d]);
+        bytes.push(0x01);
+        bytes.extend(identifier("ContractState"));
+        bytes.extend_from_slice(&2u32.to_be_bytes());
+        bytes.extend(identifier("counter"));
+        bytes.push(0x03);
+        bytes.extend(identifier("upgrader"));
+        bytes.push(0x0d);
+        // functions: init, increment, configure
+        bytes.extend_from_slice(&3u32.to_be_bytes());
+        bytes.push(0x01);
+        bytes.extend(identifier("initialize"));
+        bytes.extend_from_slice(&[0xff, 0xff, 0xff, 0xff, 0x0f]);
+        bytes.extend_from_slice(&1u32.to_be_bytes());
+        bytes.extend(identifier("upgrader"));
+        bytes.push(0x0d);
+        bytes.push(0x02);
+        bytes.extend(identifier("increment"));
+        bytes.push(0x01);
+        bytes.extend_from_slice(&2u32.to_be_bytes());
+        bytes.extend(identifier("amount"));
+        bytes.push(0x03);
+        bytes.extend(identifier("data"));
+        bytes.extend_from_slice(&[0x0e, 0x01]);
+        bytes.push(0x02);
+        bytes.extend(identifier("configure"));
+        bytes.push(0x02);
+        bytes.extend_from_slice(&1u32.to_be_bytes());
+        bytes.extend(identifier("config"));
+        bytes.extend_from_slice(&[0x00, 0x00]);
+        // state type
+        bytes.extend_from_slice(&[0x00, 0x01]);
+        bytes
+    }
+
+    /// Builds the ABI of a zero-knowledge voting contract with a `Ballot` enum and
+    /// `Option<Hash>` init argument, an explicitly typed secret input and ZK hooks
+    pub fn zk_voting_abi_bytes() -> Vec<u8> {
+        let mut bytes: Vec<u8> = ABI_HEADER.to_vec();
+        bytes.extend_from_slice(&[11, 0, 0, 5, 6, 0]);
+        // named types: Ballot, Open, Invite, VotingState
+        bytes.extend_from_slice(&4u32.to_be_bytes());
+        bytes.push(0x02);
+        bytes.extend(identifier("Ballot"));
+        bytes.extend_from_slice(&2u32.to_be_bytes());
+        bytes.extend_from_slice(&[0x00, 0x00, 0x01]);
+        bytes.extend_from_slice(&[0x01, 0x00, 0x02]);
+        bytes.push(0x01);
+        bytes.extend(identifier("Open"));
+        bytes.extend_from_slice(&1u32.to_be_bytes());
+        bytes.extend(identifier("deadline"));
+        bytes.push(0x04);
+        bytes.push(0x01);
+        bytes.extend(identifier("Invite"));
+        bytes.extend_from_slice(&1u32.to_be_bytes());
+        bytes.extend(identifier("voters"));
+        bytes.extend_from_slice(&[0x0e, 0x0d]);
+        bytes.push(0x01);
+        bytes.extend(identifier("VotingState"));
+        bytes.extend_from_slice(&3u32.to_be_bytes());
+        bytes.extend(identifier("owner"));
+        bytes.push(0x0d);
+        bytes.extend(identifier("tallies"));
+        bytes.extend_from_slice(&[0x19, 0x03, 0x04]);
+        bytes.extend(identifier("weights"));
+        bytes.extend_from_slice(&[0x1a, 0x02, 0x04]);
+        // functions: init, secret input, compute complete, variable opened
+        bytes.extend_from_slice(&4u32.to_be_bytes());
+        bytes.push(0x01);
+        bytes.extend(identifier("initialize"));
+        bytes.extend_from_slice(&[0xff, 0xff, 0xff, 0xff, 0x0f]);
+        bytes.extend_from_slice(&2u32.to_be_bytes());
+        bytes.extend(identifier("ballot"));
+        bytes.extend_from_slice(&[0x00, 0x00]);
+        bytes.extend(identifier("proposal"));
+        bytes.extend_from_slice(&[0x12, 0x13]);
+        bytes.push(0x17);
+        bytes.extend(identifier("cast_vote"));
+        bytes.push(0x40);
+        bytes.extend_from_slice(&0u32.to_be_bytes());
+        bytes.extend(identifier("secret_input"));
+        bytes.push(0x06);
+        bytes.push(0x13);
+        bytes.extend(identifier("sum_compute_complete"));
+        bytes.extend_from_slice(&[0xc0, 0x01]);
+        bytes.extend_from_slice(&0u32.to_be_bytes());
+        bytes.push(0x14);
+        bytes.extend(identifier("open_sum_variable"));
+        bytes.push(0x02);
+        bytes.extend_from_slice(&0u32.to_be_bytes());
+        // state type
+        bytes.extend_from_slice(&[0x00, 0x03]);
+        bytes
+    }
+
+    #[test]
+    fn test_parse_abi() {
+        let abi = ContractAbi::from_bytes(&counter_abi_bytes()).unwrap();
+        assert_eq!(abi.client_version, [5, 4, 0]);
+        assert_eq!(abi.named_types.len(), 2);
+        assert_eq!(abi.functions.len(), 3);
+        let init = abi.init_function().unwrap();
+        assert_eq!(init.name, "initialize");
+        assert_eq!(init.arguments[0].type_spec, TypeSpec::Address);
+        assert_eq!(abi.type_name(&abi.state_type), "ContractState");
+        assert_eq!(
+            abi.type_name(&abi.function("increment").unwrap().arguments[1].type_spec),
+            "Vec<u8>"
+        );
+    }
+
+    #[test]
+    fn test_parse_zk_abi() {
+        let abi = ContractAbi::from_bytes(&zk_voting_abi_bytes()).unwrap();
+        assert_eq!(abi.client_version, [5, 6, 0]);
+        let kinds: Vec<FnKind> = abi.functions.iter().map(|function| function.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                FnKind::Init,
+                FnKind::ZkSecretInputWithExplicitType,
+                FnKind::ZkComputeComplete,
+                FnKind::ZkVarOpened
+            ]
+        );
+        let cast_vote = abi.function("cast_vote").unwrap();
+        assert!(cast_vote.arguments.is_empty());
+        assert_eq!(cast_vote.secret_argument.as_ref().unwrap().type_spec, TypeSpec::I8);
+        assert_eq!(abi.function("sum_compute_complete").unwrap().shortname, vec![0xc0, 0x01]);
+        assert!(abi.function("open_sum_variable").unwrap().secret_argument.is_none());
+
+        assert_eq!(abi.type_name(&abi.state_type), "VotingState");
+        let NamedTypeSpec::Struct { fields, .. } = abi.named_type(3).unwrap() else {
+            panic!("VotingState is not a struct");
+        };
+        assert_eq!(abi.type_name(&fields[1].type_spec), "AvlTreeMap<u32, u64>");
+        assert_eq!(abi.type_name(&fields[2].type_spec), "[u16; 4]");
+        assert_eq!(
+            abi.named_type(0).unwrap(),
+            &NamedTypeSpec::Enum { name: "Ballot".to_string(), variants: vec![(0, 1), (1, 2)] }
+        );
+    }
+
+    #[test]
+    fn test_encode_zk_init() {
+        let abi = ContractAbi::from_bytes(&zk_voting_abi_bytes()).unwrap();
+        let init = abi.init_function().unwrap();
+        let rpc = abi
+            .encode_call(init, &[r#"{"Open": {"deadline": 7}}"#.to_string(), "null".to_string()])
+            .unwrap();
+        assert_eq!(hex::encode(rpc), "ffffffff0f00000000000000000700");
+
+        let rpc = abi
+            .encode_call(
+                init,
+                &[
+                    r#"{"Invite": {"voters": ["00d277aa1bf5702ab9fc690b04bd68b5a981095530"]}}"#.to_string(),
+                    "ab".repeat(32),
+                ],
+            )
+            .unwrap();
+        assert_eq!(
+            hex::encode(rpc),
+            format!("ffffffff0f010000000100d277aa1bf5702ab9fc690b04bd68b5a98109553001{}", "ab".repeat(32))
+        );
+        assert!(abi
+            .encode_call(init, &[r#"{"Close": {}}"#.to_string(), "null".to_string()])
+            .is_err());
+    }
+
+    #[test]
+    fn test_reject_unsupported_abi() {
+        // every 5.x client version shares the layout that is parsed
+        let mut bytes = counter_abi_bytes();
+        bytes[10] = 0;
+        assert!(ContractAbi::from_bytes(&bytes).is_ok());
+        bytes[9] = 3;
+        assert!(ContractAbi::from_bytes(&bytes).is_err());
+        bytes[9] = 6;
+        assert!(ContractAbi::from_bytes(&bytes).is_err());
+        assert!(ContractAbi::from_bytes(b"PBCAB").is_err());
+        assert!(ContractAbi::from_bytes(&counter_abi_bytes()[..40]).is_err());
+    }
+
+    #[test]
+    fn test_encode_init() {
+        let abi = ContractAbi::from_bytes(&counter_abi_bytes()).unwrap();
+        let init = abi.init_function().unwrap();
+        let rpc = abi
+            .encode_call(init, &["00d277aa1bf5702ab9fc690b04bd68b5a981095530".to_string()])
+            .unwrap();
+        assert_eq!(
+            hex::encode(rpc),
+            "ffffffff0f00d277aa1bf5702ab9fc690b04bd68b5a981095530"
+        );
+    }
+
+    #[test]
+    fn test_encode_rejects_invalid_arguments() {
+        let abi = ContractAbi::from_bytes(&counter_abi_bytes()).unwrap();
+        let init = abi.init_function().unwrap();
+        let error = abi.encode_call(init, &[]).unwrap_err().to_string();
+        assert!(error.contains("upgrader: Address"), "unexpected error: {}", error);
+        // wrong length
+        assert!(abi.encode_call(init, &["00d277".to_string()]).is_err());
+        // unknown address prefix
+        assert!(abi
+            .encode_call(init, &["09d277aa1bf5702ab9fc690b04bd68b5a981095530".to_string()])
+            .is_err());
+
+        let increment = abi.function("increment").unwrap();
+        assert!(abi
+            .encode_call(increment, &["4294967296".to_string(), "00".to_string()])
+            .is_err());
+        assert!(abi
+            .encode_call(increment, &["1".to_string(), "zz".to_string()])
+            .is_err());
+    }
+
+    #[test]
+    fn test_encode_option_and_u256() {
+        let abi = ContractAbi::from_bytes(&counter_abi_bytes()).unwrap();
+        let encode = |type_spec: TypeSpec, arg: &str| {
+            let mut writer = RpcWriter::new();
+            abi.encode_arg(&type_spec, arg, &mut writer)
+                .map(|_| hex::encode(writer.into_bytes()))
+        };
+        let option = |element: TypeSpec| TypeSpec::Option(Box::new(element));
+
+        assert_eq!(encode(option(TypeSpec::U64), "null").unwrap(), "00");
+        assert_eq!(encode(option(TypeSpec::U64), "5").unwrap(), "010000000000000005");
+        assert_eq!(encode(option(TypeSpec::Vec(Box::new(TypeSpec::U8))), "0xab").unwrap(), "0100000001ab");
+        assert_eq!(
+            encode(option(TypeSpec::Named(0)), r#"{"limit": 3, "owner": null}"#).unwrap(),
+            "010000000300"
+        );
+        assert_eq!(encode(option(TypeSpec::String), "none").unwrap(), "01000000046e6f6e65");
+        assert!(encode(option(TypeSpec::U64), "five").is_err());
+        assert!(encode(option(TypeSpec::Named(0)), "3").is_err());
+
+        assert_eq!(encode(TypeSpec::U256, "1").unwrap(), format!("{}01", "00".repeat(31)));
+        assert_eq!(
+            encode(TypeSpec::U256, "340282366920938463463374607431768211456").unwrap(),
+            format!("{}01{}", "00".repeat(15), "00".repeat(16))
+        );
+        let max: &str = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+        assert_eq!(encode(TypeSpec::U256, max).unwrap(), "ff".repeat(32));
+        assert!(encode(TypeSpec::U256, &format!("{}6", &max[..max.len() - 1])).is_err());
+        assert_eq!(encode(TypeSpec::U256, "0x1ff").unwrap(), format!("{}01ff", "00".repeat(30)));
+        assert!(encode(TypeSpec::U256, &format!("0x1{}", "0".repeat(64))).is_err());
+        assert!(encode(TypeSpec::U256, "-1").is_err());
+    }
+
+    #[test]
+    fn test_encode_struct_and_vec() {
+        let abi = ContractAbi::from_bytes(&counter_abi_bytes()).unwrap();
+        let increment = abi.function("increment").unwrap();
+        let rpc = abi
+            .encode_call(increment, &["2".to_string(), "0xabcd".to_string()])
+            .unwrap();
+        assert_eq!(hex::encode(rpc), "010000000200000002abcd");
+
+        let configure = abi.function("configure").unwrap();
+        let rpc = abi
+            .encode_call(configure, &[r#"{"limit": 3, "owner": null}"#.to_string()])
+            .unwrap();
+        assert_eq!(hex::encode(rpc), "020000000300");
+        assert!(abi
+            .encode_call(configure, &[r#"{"limit": 3}"#.to_string()])
+            .is_err());
+    }
+}
@@ -0,0 +1,254 @@
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+
+/// Magic bytes at the start of every .pbc file
+pub const PBC_FILE_HEADER: &[u8; 4] = b"PBSC";
+/// Section identifier of the ABI inside a .pbc file
+pub const PBC_SECTION_ABI: u8 = 0x01;
+/// Section identifier of the WASM code inside a .pbc file
+pub const PBC_SECTION_WASM: u8 = 0x02;
+/// Section identifier of the ZK WASM code inside a .pbc file
+pub const PBC_SECTION_ZKWA: u8 = 0x03;
+
+/// Kind of contract an artifact deploys as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContractKind {
+    Public,
+    ZeroKnowledge,
+}
+
+/// Compiled contract code and ABI ready to be deployed
+///
+/// # Fields
+/// * `kind` - Whether the contract is public or zero-knowledge
+/// * `contract_bytes` - WASM or ZK WASM code of the contract
+/// * `abi_bytes` - Binary ABI of the contract
+#[derive(Debug, Clone)]
+pub struct ContractArtifact {
+    pub kind: ContractKind,
+    pub contract_bytes: Vec<u8>,
+    pub abi_bytes: Vec<u8>,
+}
+
+/// Hashes identifying the code of a contract, as checked by `#[upgrade_is_allowed]`
+///
+/// # Fields
+/// * `binder` - Hash of the binder the contract runs on
+/// * `contract` - Hash of the WASM or ZK WASM code
+/// * `abi` - Hash of the binary ABI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractHashes {
+    pub binder: [u8; 32],
+    pub contract: [u8; 32],
+    pub abi: [u8; 32],
+}
+
+/// Sections of a parsed .pbc file
+///
+/// # Fields
+/// * `abi` - ABI section
+/// * `wasm` - WASM section, present for public contracts
+/// * `zkwa` - ZK WASM section, present for zero-knowledge contracts
+#[derive(Debug, Clone, Default)]
+pub struct PbcFile {
+    pub abi: Option<Vec<u8>>,
+    pub wasm: Option<Vec<u8>>,
+    pub zkwa: Option<Vec<u8>>,
+}
+
+impl PbcFile {
+    /// Parses the sections of a .pbc file
+    ///
+    /// # Arguments
+    /// * `bytes` - Content of the .pbc file
+    ///
+    /// # Returns
+    /// * `Result<PbcFile>` - Parsed sections
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
+        if bytes.len() < PBC_FILE_HEADER.len() || &bytes[..PBC_FILE_HEADER.len()] != PBC_FILE_HEADER
+        {
+            return Err("PbcFile::from_bytes: Missing PBSC header".into());
+        }
+        let mut pbc_file = PbcFile::default();
+        let mut cursor: usize = PBC_FILE_HEADER.len();
+        while cursor < bytes.len() {
+            if cursor + 5 > bytes.len() {
+                return Err("PbcFile::from_bytes: Truncated section header".into());
+            }
+            let section_id: u8 = bytes[cursor];
+            let length_bytes: [u8; 4] = bytes[cursor + 1..cursor + 5].try_into()?;
+            let length: usize = u32::from_be_bytes(length_bytes) as usize;
+            cursor += 5;
+            if cursor + length > bytes.len() {
+                return Err("PbcFile::from_bytes: Truncated section".into());
+            }
+            let section: Vec<u8> = bytes[cursor..cursor + length].to_vec();
+            cursor += length;
+            match section_id {
+                PBC_SECTION_ABI => pbc_file.abi = Some(section),
+                PBC_SECTION_WASM => pbc_file.wasm = Some(section),
+                PBC_SECTION_ZKWA => pbc_file.zkwa = Some(section),
+                // unknown sections are skipped so newer compilers stay readable
+                _ => {}
+            }
+        }
+        Ok(pbc_file)
+    }
+}
+
+impl ContractArtifact {
+    /// Loads a deployable artifact from a .pbc file
+    ///
+    /// # Arguments
+    /// * `pbc_path` - Path to the .pbc file
+    ///
+    /// # Returns
+    /// * `Result<ContractArtifact>` - Artifact with code and ABI
+    pub fn from_pbc(pbc_path: &PathBuf) -> Result<Self, Box<dyn std::error::Error>> {
+        let bytes: Vec<u8> = fs::read(pbc_path)
+            .map_err(|e| format!("Failed to read {}: {}", pbc_path.display(), e))?;
+        let pbc_file: PbcFile = PbcFile::from_bytes(&bytes)?;
+        let abi_bytes: Vec<u8> = pbc_file
+            .abi
+            .ok_or_else(|| format!("{} has no ABI section", pbc_path.display()))?;
+        match (pbc_file.wasm, pbc_file.zkwa) {
+            (_, Some(zkwa)) => Ok(Self {
+                kind: ContractKind::ZeroKnowledge,
+                contract_bytes: zkwa,
+                abi_bytes,
+            }),
+            (Some(wasm), None) => Ok(Self {
+                kind: ContractKind::Public,
+                contract_bytes: wasm,
+                abi_bytes,
+            }),
+            (None, None) => Err(format!("{} has no contract code", pbc_path.display()).into()),
+        }
+    }
+
+    /// Loads a deployable artifact from separate code and ABI files
+    ///
+    /// # Arguments
+    /// * `contract_path` - Path to the .wasm or .zkwa file
+    /// * `abi_path` - Path to the .abi file
+    ///
+    /// # Returns
+    /// * `Result<ContractArtifact>` - Artifact with code and ABI
+    pub fn from_files(
+        contract_path: &PathBuf,
+        abi_path: &PathBuf,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let kind: ContractKind = if contract_path.extension().unwrap_or_default() == "zkwa" {
+            ContractKind::ZeroKnowledge
+        } else {
+            ContractKind::Public
+        };
+        let contract_bytes: Vec<u8> = fs::read(contract_path)
+            .map_err(|e| format!("Failed to read {}: {}", contract_path.display(), e))?;
+        let abi_bytes: Vec<u8> = fs::read(abi_path)
+            .map_err(|e| format!("Failed to read {}: {}", abi_path.display(), e))?;
+        Ok(Self {
+            kind,
+            contract_bytes,
+            abi_bytes,
+        })
+    }
+
+    /// SHA-256 of the WASM or ZK WASM code
+    pub fn contract_hash(&self) -> [u8; 32] {
+        Sha256::digest(&self.contract_bytes).into()
+    }
+
+    /// SHA-256 of the binary ABI
+    pub fn abi_hash(&self) -> [u8; 32] {
+        Sha256::digest(&self.abi_bytes).into()
+    }
+}
+
+impl ContractHashes {
+    /// Hashes the code and ABI of an artifact
+    ///
+    /// # Arguments
+    /// * `binder` - Hash of the binder the artifact is deployed with
+    /// * `artifact` - Contract code and ABI
+    ///
+    /// # Returns
+    /// * `ContractHashes` - Hashes of the artifact
+    pub fn new(binder: [u8; 32], artifact: &ContractArtifact) -> Self {
+        Self {
+            binder,
+            contract: artifact.contract_hash(),
+            abi: artifact.abi_hash(),
+        }
+    }
+
+    /// Serializes the hashes the way `allow_upgrade_to` reads its `ContractHashes` argument
+    ///
+    /// # Returns
+    /// * `Vec<u8>` - Binder, contract and ABI hashes, 96 bytes
+    pub fn to_rpc(&self) -> Vec<u8> {
+        [self.binder, self.contract, self.abi].concat()
+    }
+
+    /// JSON array of the hex encoded hashes, accepted as a `ContractHashes` argument by the ABI encoder
+    pub fn to_json_arg(&self) -> String {
+        serde_json::json!([
+            hex::encode(self.binder),
+            hex::encode(self.contract),
+            hex::encode(self.abi)
+        ])
+        .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(id: u8, data: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![id];
+        bytes.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(data);
+        bytes
+    }
+
+    #[test]
+    fn test_parse_pbc_file() {
+        let mut bytes: Vec<u8> = PBC_FILE_HEADER.to_vec();
+        bytes.extend(section(PBC_SECTION_ABI, b"abi"));
+        bytes.extend(section(PBC_SECTION_WASM, b"wasm"));
+        let pbc_file = PbcFile::from_bytes(&bytes).unwrap();
+        assert_eq!(pbc_file.abi.unwrap(), b"abi");
+        assert_eq!(pbc_file.wasm.unwrap(), b"wasm");
+        assert!(pbc_file.zkwa.is_none());
+    }
+
+    #[test]
+    fn test_parse_pbc_file_rejects_bad_input() {
+        assert!(PbcFile::from_bytes(b"nope").is_err());
+        let mut truncated: Vec<u8> = PBC_FILE_HEADER.to_vec();
+        truncated.extend_from_slice(&[PBC_SECTION_ABI, 0, 0, 0, 9, 1]);
+        assert!(PbcFile::from_bytes(&truncated).is_err());
+    }
+
+    #[test]
+    fn test_contract_hashes() {
+        let artifact = ContractArtifact {
+            kind: ContractKind::Public,
+            contract_bytes: b"wasm".to_vec(),
+            abi_bytes: b"abi".to_vec(),
+        };
+        let hashes = ContractHashes::new([0x11; 32], &artifact);
+        assert_eq!(
+            hex::encode(hashes.abi),
+            "1ca34f7ff6ab35ac2696a0f9a436eb783e0906afcbe7e3c99c111517afc27dfa"
+        );
+        assert_eq!(hashes.binder, [0x11; 32]);
+        assert!(hashes.to_json_arg().starts_with(&format!("[\"{}\",", "11".repeat(32))));
+        let rpc = hashes.to_rpc();
+        assert_eq!(rpc.len(), 96);
+        assert_eq!(rpc[..32], [0x11; 32]);
+        assert_eq!(rpc[64..], hashes.abi);
+    }
+}
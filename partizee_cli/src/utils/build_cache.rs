@@ -0,0 +1,186 @@
+use crate::utils::build_report::ArtifactSizes;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::{DirEntry, WalkDir};
+
+/// File the build cache is kept in, relative to the workspace root
+pub const BUILD_CACHE_FILE: &str = "target/partizee/build-cache.json";
+
+/// Source hashes of the contracts as of their last successful build
+///
+/// # Fields
+/// * `hashes` - Hex source hash of each contract, keyed by package name
+/// * `sizes` - Artifact sizes of each contract as of the previous build
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuildCache {
+    pub hashes: HashMap<String, String>,
+    #[serde(default)]
+    pub sizes: HashMap<String, ArtifactSizes>,
+}
+
+impl BuildCache {
+    /// Loads the project's build cache
+    ///
+    /// # Arguments
+    /// * `project_root` - Root directory of project
+    ///
+    /// # Returns
+    /// * `BuildCache` - Recorded hashes, empty if there is no cache or it cannot be read
+    pub fn load(project_root: &Path) -> Self {
+        fs::read_to_string(project_root.join(BUILD_CACHE_FILE))
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// Writes the build cache
+    ///
+    /// # Arguments
+    /// * `project_root` - Root directory of project
+    ///
+    /// # Returns
+    /// * `Result<()>` - Ok if the cache was written
+    pub fn save(&self, project_root: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let path: PathBuf = project_root.join(BUILD_CACHE_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Whether a contract was last built from the given sources
+    ///
+    /// # Arguments
+    /// * `name` - Package name of the contract
+    /// * `hash` - Current source hash
+    ///
+    /// # Returns
+    /// * `bool` - True if the recorded hash matches
+    pub fn is_fresh(&self, name: &str, hash: &str) -> bool {
+        self.hashes.get(name).is_some_and(|cached| cached == hash)
+    }
+
+    /// Records a successful build
+    ///
+    /// # Arguments
+    /// * `name` - Package name of the contract
+    /// * `hash` - Source hash the contract was built from
+    pub fn record(&mut self, name: &str, hash: &str) {
+        self.hashes.insert(name.to_string(), hash.to_string());
+    }
+
+    /// Forgets a contract, so it is rebuilt next time
+    ///
+    /// # Arguments
+    /// * `name` - Package name of the contract
+    pub fn forget(&mut self, name: &str) {
+        self.hashes.remove(name);
+    }
+}
+
+/// Fingerprint of everything outside a contract crate that changes its build output
+///
+/// Covers the workspace's dependency and tool versions, the resolved Cargo.lock and
+/// the arguments the build is run with.
+///
+/// # Arguments
+/// * `project_root` - Root directory of project
+/// * `args` - Arguments passed to cargo pbc build
+///
+/// # Returns
+/// * `Vec<u8>` - Fingerprint to mix into each contract's source hash
+pub fn workspace_fingerprint(project_root: &Path, args: &[String]) -> Vec<u8> {
+    let mut fingerprint: Vec<u8> = Vec::new();
+    let workspace: Option<toml::Value> = fs::read_to_string(project_root.join("Cargo.toml"))
+        .ok()
+        .and_then(|contents| contents.parse::<toml::Value>().ok());
+    for table in ["dependencies", "metadata"] {
+        let versions: Option<String> = workspace
+            .as_ref()
+            .and_then(|workspace| workspace.get("workspace")?.get(table))
+            .map(|versions| versions.to_string());
+        fingerprint.extend(versions.unwrap_or_default().as_bytes());
+        fingerprint.push(0);
+    }
+    fingerprint.extend(fs::read(project_root.join("Cargo.lock")).unwrap_or_default());
+    fingerprint.push(0);
+    fingerprint.extend(args.join("\0").as_bytes());
+    fingerprint
+}
+
+/// Hashes the sources of a contract crate
+///
+/// Every file in the crate directory is hashed with its relative path, in path order,
+/// except for build output in `target`.
+///
+/// # Arguments
+/// * `crate_dir` - Directory holding the contract's Cargo.toml
+/// * `fingerprint` - Fingerprint of the workspace, see `workspace_fingerprint`
+///
+/// # Returns
+/// * `Result<String>` - Hex sha256 of the sources
+pub fn source_hash(crate_dir: &Path, fingerprint: &[u8]) -> Result<String, Box<dyn std::error::Error>> {
+    let mut hasher = Sha256::new();
+    hasher.update(fingerprint);
+    let files = WalkDir::new(crate_dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry: &DirEntry| !(entry.file_type().is_dir() && entry.file_name() == "target"));
+    for entry in files {
+        let entry: DirEntry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative_path: &Path = entry.path().strip_prefix(crate_dir)?;
+        let contents: Vec<u8> = fs::read(entry.path())?;
+        hasher.update(relative_path.to_string_lossy().as_bytes());
+        hasher.update((contents.len() as u64).to_le_bytes());
+        hasher.update(&contents);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_source_hash() {
+        let temp_dir = TempDir::new().unwrap();
+        let crate_dir = temp_dir.path();
+        fs::create_dir_all(crate_dir.join("src")).unwrap();
+        fs::write(crate_dir.join("Cargo.toml"), "[package]\nname = \"counter\"\n").unwrap();
+        fs::write(crate_dir.join("src/lib.rs"), "pub fn increment() {}").unwrap();
+        let hash = source_hash(crate_dir, b"sdk 1").unwrap();
+
+        fs::create_dir_all(crate_dir.join("target")).unwrap();
+        fs::write(crate_dir.join("target/counter.wasm"), "wasm").unwrap();
+        assert_eq!(source_hash(crate_dir, b"sdk 1").unwrap(), hash);
+        assert_ne!(source_hash(crate_dir, b"sdk 2").unwrap(), hash);
+
+        fs::write(crate_dir.join("src/lib.rs"), "pub fn decrement() {}").unwrap();
+        assert_ne!(source_hash(crate_dir, b"sdk 1").unwrap(), hash);
+    }
+
+    #[test]
+    fn test_build_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut cache = BuildCache::load(temp_dir.path());
+        assert!(!cache.is_fresh("counter", "aa"));
+
+        cache.record("counter", "aa");
+        cache.save(temp_dir.path()).unwrap();
+        let cache = BuildCache::load(temp_dir.path());
+        assert!(cache.is_fresh("counter", "aa"));
+        assert!(!cache.is_fresh("counter", "bb"));
+
+        let mut cache = cache;
+        cache.forget("counter");
+        assert!(!cache.is_fresh("counter", "aa"));
+    }
+}
@@ -0,0 +1,336 @@
+use crate::commands::compile::RELEASE_DIR;
+use crate::utils::artifact::ContractArtifact;
+use crate::utils::utils::{command_output, OutputStream};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// File the build metadata is written to, next to the artifacts in the release directory
+pub const BUILD_INFO_FILE: &str = "build-info.json";
+/// Crate features cargo pbc enables when a contract declares them
+pub const PBC_BUILD_FEATURES: [&str; 2] = ["abi", "plus_metadata"];
+/// Workspace dependency whose git tag is recorded as the SDK version
+const SDK_CRATE: &str = "pbc_contract_common";
+/// Extensions of the artifacts that are hashed
+const HASHED_EXTENSIONS: [&str; 4] = ["pbc", "wasm", "abi", "zkwa"];
+
+/// How the artifacts in the release directory were produced, keyed by package name
+///
+/// Contracts skipped by the build cache keep the entry of the build that produced them.
+///
+/// # Fields
+/// * `contracts` - Build metadata of each contract
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuildInfo {
+    pub contracts: BTreeMap<String, ContractBuildInfo>,
+}
+
+/// Tools and sources a build ran with
+///
+/// Every value is None if it could not be determined.
+///
+/// # Fields
+/// * `rustc` - Output of `rustc --version`
+/// * `cargo_partisia_contract` - Output of `cargo pbc --version`
+/// * `sdk_tag` - Git tag of the contract SDK in the workspace dependencies
+/// * `git_commit` - Commit of the project the build ran on
+/// * `git_dirty` - Whether the project had uncommitted changes
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuildEnvironment {
+    pub rustc: Option<String>,
+    pub cargo_partisia_contract: Option<String>,
+    pub sdk_tag: Option<String>,
+    pub git_commit: Option<String>,
+    pub git_dirty: Option<bool>,
+}
+
+/// Build metadata of one contract
+///
+/// # Fields
+/// * `built_at` - Unix timestamp of the build
+/// * `environment` - Tools and sources the contract was built with
+/// * `features` - Crate features enabled by cargo pbc
+/// * `contract_hash` - Hex hash of the contract code, as identified on chain
+/// * `artifacts` - Hex SHA-256 of each artifact, keyed by file name
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContractBuildInfo {
+    pub built_at: u64,
+    #[serde(flatten)]
+    pub environment: BuildEnvironment,
+    pub features: Vec<String>,
+    pub contract_hash: Option<String>,
+    pub artifacts: BTreeMap<String, String>,
+}
+
+/// Reference from a deployment to the build that produced its code
+///
+/// # Fields
+/// * `path` - build-info.json the build is recorded in, relative to the project root
+/// * `built_at` - Unix timestamp of the build, identifying the entry
+/// * `git_commit` - Commit the contract was built from
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BuildInfoLink {
+    pub path: PathBuf,
+    pub built_at: u64,
+    pub git_commit: Option<String>,
+}
+
+impl BuildInfo {
+    /// Loads the build metadata of a project
+    ///
+    /// # Arguments
+    /// * `project_root` - Root directory of project
+    ///
+    /// # Returns
+    /// * `BuildInfo` - Recorded builds, empty if there is no build-info.json or it cannot be read
+    pub fn load(project_root: &Path) -> Self {
+        fs::read_to_string(project_root.join(RELEASE_DIR).join(BUILD_INFO_FILE))
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// Writes build-info.json to the release directory
+    ///
+    /// # Arguments
+    /// * `project_root` - Root directory of project
+    ///
+    /// # Returns
+    /// * `Result<()>` - Ok if the file was written
+    pub fn save(&self, project_root: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let release_dir: PathBuf = project_root.join(RELEASE_DIR);
+        fs::create_dir_all(&release_dir)?;
+        fs::write(release_dir.join(BUILD_INFO_FILE), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Records the contracts that were just built
+    ///
+    /// # Arguments
+    /// * `project_root` - Root directory of project
+    /// * `contracts` - Package name and Cargo.toml of each rebuilt contract
+    pub fn record(&mut self, project_root: &Path, contracts: &[(String, PathBuf)]) {
+        if contracts.is_empty() {
+            return;
+        }
+        let environment: BuildEnvironment = BuildEnvironment::detect(project_root);
+        let built_at: u64 = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+        let release_dir: PathBuf = project_root.join(RELEASE_DIR);
+        for (name, manifest_path) in contracts {
+            let artifacts: BTreeMap<String, String> = hash_artifacts(&release_dir, name);
+            // files that are not contract crates leave no artifacts under their name
+            if artifacts.is_empty() {
+                continue;
+            }
+            self.contracts.insert(
+                name.clone(),
+                ContractBuildInfo {
+                    built_at,
+                    environment: environment.clone(),
+                    features: enabled_features(manifest_path),
+                    contract_hash: read_contract_hash(&release_dir, name),
+                    artifacts,
+                },
+            );
+        }
+    }
+
+    /// Links a deployed artifact to the build that produced it
+    ///
+    /// # Arguments
+    /// * `project_root` - Root directory of project
+    /// * `name` - Package name of the contract
+    /// * `artifact` - Artifact that was deployed
+    ///
+    /// # Returns
+    /// * `Option<BuildInfoLink>` - Link, None if the artifact was not produced by a recorded build
+    pub fn link(project_root: &Path, name: &str, artifact: &ContractArtifact) -> Option<BuildInfoLink> {
+        let build_info: BuildInfo = Self::load(project_root);
+        let contract: &ContractBuildInfo = build_info
+            .contracts
+            .iter()
+            .find(|(recorded, _)| recorded.eq_ignore_ascii_case(name))
+            .map(|(_, contract)| contract)?;
+        // an artifact from elsewhere, or rebuilt since, is not described by the entry
+        if contract.contract_hash.as_deref() != Some(hex::encode(artifact.contract_hash()).as_str()) {
+            return None;
+        }
+        Some(BuildInfoLink {
+            path: Path::new(RELEASE_DIR).join(BUILD_INFO_FILE),
+            built_at: contract.built_at,
+            git_commit: contract.environment.git_commit.clone(),
+        })
+    }
+}
+
+impl BuildEnvironment {
+    /// Detects the tools and sources of a build
+    ///
+    /// # Arguments
+    /// * `project_root` - Root directory of project, tools are run there so toolchain overrides apply
+    ///
+    /// # Returns
+    /// * `BuildEnvironment` - What could be detected
+    pub fn detect(project_root: &Path) -> Self {
+        let git_status: Option<String> =
+            command_output(project_root, "git", &["status", "--porcelain"], OutputStream::Stdout);
+        Self {
+            rustc: command_output(project_root, "rustc", &["--version"], OutputStream::Stdout),
+            cargo_partisia_contract: command_output(
+                project_root,
+                "cargo",
+                &["pbc", "--version"],
+                OutputStream::Stdout,
+            ),
+            sdk_tag: sdk_tag(&project_root.join("Cargo.toml")),
+            git_commit: command_output(project_root, "git", &["rev-parse", "HEAD"], OutputStream::Stdout),
+            git_dirty: git_status.map(|status| !status.is_empty()),
+        }
+    }
+}
+
+/// Reads the git tag of the contract SDK from the workspace dependencies
+///
+/// # Arguments
+/// * `manifest_path` - Workspace Cargo.toml
+///
+/// # Returns
+/// * `Option<String>` - Tag of `pbc_contract_common`, e.g. `v.16.94.0`
+fn sdk_tag(manifest_path: &Path) -> Option<String> {
+    let manifest: toml::Value = fs::read_to_string(manifest_path).ok()?.parse().ok()?;
+    manifest
+        .get("workspace")?
+        .get("dependencies")?
+        .get(SDK_CRATE)?
+        .get("tag")?
+        .as_str()
+        .map(|tag| tag.to_string())
+}
+
+/// Features of a contract crate that cargo pbc enables
+///
+/// # Arguments
+/// * `manifest_path` - Contract's Cargo.toml
+///
+/// # Returns
+/// * `Vec<String>` - The `PBC_BUILD_FEATURES` the crate declares
+fn enabled_features(manifest_path: &Path) -> Vec<String> {
+    let manifest: Option<toml::Value> = fs::read_to_string(manifest_path)
+        .ok()
+        .and_then(|contents| contents.parse().ok());
+    let declared: Option<&toml::Value> = manifest.as_ref().and_then(|manifest| manifest.get("features"));
+    PBC_BUILD_FEATURES
+        .iter()
+        .filter(|feature| declared.is_some_and(|declared| declared.get(**feature).is_some()))
+        .map(|feature| feature.to_string())
+        .collect()
+}
+
+/// Hashes the code identifying a contract on chain
+///
+/// # Arguments
+/// * `release_dir` - Directory cargo pbc writes artifacts to
+/// * `name` - Package name of the contract
+///
+/// # Returns
+/// * `Option<String>` - Hex hash of the code in the .pbc, None if there is no readable .pbc
+fn read_contract_hash(release_dir: &Path, name: &str) -> Option<String> {
+    let pbc_path: PathBuf = release_dir.join(format!("{}.pbc", name.replace('-', "_")));
+    ContractArtifact::from_pbc(&pbc_path)
+        .ok()
+        .map(|artifact| hex::encode(artifact.contract_hash()))
+}
+
+/// Hashes every artifact of a contract
+///
+/// # Arguments
+/// * `release_dir` - Directory cargo pbc writes artifacts to
+/// * `name` - Package name of the contract
+///
+/// # Returns
+/// * `BTreeMap<String, String>` - Hex SHA-256 of each artifact that exists, keyed by file name
+fn hash_artifacts(release_dir: &Path, name: &str) -> BTreeMap<String, String> {
+    let stem: String = name.replace('-', "_");
+    HASHED_EXTENSIONS
+        .iter()
+        .filter_map(|extension| {
+            let file_name: String = format!("{}.{}", stem, extension);
+            let bytes: Vec<u8> = fs::read(release_dir.join(&file_name)).ok()?;
+            Some((file_name, hex::encode(Sha256::digest(&bytes))))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::artifact::{PBC_FILE_HEADER, PBC_SECTION_ABI, PBC_SECTION_WASM};
+    use crate::utils::utils::setup_test_environment;
+
+    fn pbc_bytes(wasm: &[u8], abi: &[u8]) -> Vec<u8> {
+        let mut bytes: Vec<u8> = PBC_FILE_HEADER.to_vec();
+        for (section, content) in [(PBC_SECTION_ABI, abi), (PBC_SECTION_WASM, wasm)] {
+            bytes.push(section);
+            bytes.extend((content.len() as u32).to_be_bytes());
+            bytes.extend(content);
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_record_and_link_build_info() {
+        let (_temp_dir, temp_path) = setup_test_environment();
+        let release_dir: PathBuf = temp_path.join(RELEASE_DIR);
+        fs::create_dir_all(&release_dir).unwrap();
+        fs::write(release_dir.join("counter.pbc"), pbc_bytes(b"wasm", b"abi")).unwrap();
+        fs::write(release_dir.join("counter.wasm"), b"wasm").unwrap();
+        let manifest_path: PathBuf = temp_path.join("rust/contracts/counter/Cargo.toml");
+        fs::create_dir_all(manifest_path.parent().unwrap()).unwrap();
+        fs::write(
+            &manifest_path,
+            "[package]\nname = \"counter\"\n\n[features]\nabi = []\nplus_metadata = []\n",
+        )
+        .unwrap();
+
+        let mut build_info: BuildInfo = BuildInfo::load(&temp_path);
+        build_info.record(&temp_path, &[("counter".to_string(), manifest_path)]);
+        build_info.save(&temp_path).unwrap();
+
+        let loaded: BuildInfo = BuildInfo::load(&temp_path);
+        assert_eq!(loaded, build_info);
+        let counter: &ContractBuildInfo = &loaded.contracts["counter"];
+        assert_eq!(counter.features, vec!["abi", "plus_metadata"]);
+        assert_eq!(counter.artifacts["counter.wasm"], hex::encode(Sha256::digest(b"wasm")));
+        assert!(counter.artifacts.contains_key("counter.pbc"));
+
+        let artifact: ContractArtifact = ContractArtifact::from_pbc(&release_dir.join("counter.pbc")).unwrap();
+        let link: BuildInfoLink = BuildInfo::link(&temp_path, "counter", &artifact).unwrap();
+        assert_eq!(link.path, Path::new(RELEASE_DIR).join(BUILD_INFO_FILE));
+        assert_eq!(link.built_at, counter.built_at);
+
+        let rebuilt: ContractArtifact = ContractArtifact {
+            contract_bytes: b"changed".to_vec(),
+            ..artifact
+        };
+        assert_eq!(BuildInfo::link(&temp_path, "counter", &rebuilt), None);
+    }
+
+    #[test]
+    fn test_sdk_tag() {
+        let (_temp_dir, temp_path) = setup_test_environment();
+        let manifest_path: PathBuf = temp_path.join("Cargo.toml");
+        fs::write(
+            &manifest_path,
+            "[workspace]\nmembers = []\n\n[workspace.dependencies]\npbc_contract_common = { git = \"https://gitlab.com/partisiablockchain/language/contract-sdk.git\", tag = \"v.16.94.0\" }\n",
+        )
+        .unwrap();
+        assert_eq!(sdk_tag(&manifest_path), Some("v.16.94.0".to_string()));
+        assert_eq!(BuildEnvironment::detect(&temp_path).sdk_tag, Some("v.16.94.0".to_string()));
+    }
+}
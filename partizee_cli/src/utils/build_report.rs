@@ -0,0 +1,268 @@
+use crate::utils::abi::{ContractAbi, FnKind};
+use crate::utils::diagnostics::{render_grouped, Diagnostic};
+use crate::utils::manifest::SizeBudget;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Sizes in bytes of the artifacts built for a contract, None if the artifact was not built
+///
+/// # Fields
+/// * `wasm` - Size of the .wasm
+/// * `pbc` - Size of the .pbc
+/// * `abi` - Size of the .abi
+/// * `zkwa` - Size of the .zkwa
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ArtifactSizes {
+    pub wasm: Option<u64>,
+    pub pbc: Option<u64>,
+    pub abi: Option<u64>,
+    pub zkwa: Option<u64>,
+}
+
+/// Line of the build report
+///
+/// # Fields
+/// * `name` - Package name of the contract
+/// * `status` - `cached`, `rebuilt` or `failed`
+/// * `sizes` - Sizes of the artifacts in the release directory
+/// * `size_change` - Change of the deployed size since the previous build, in bytes
+/// * `actions` - Number of actions in the ABI
+/// * `build_ms` - Milliseconds the build of this contract took, None if it was cached or built with the workspace
+/// * `over_budget` - Artifacts that exceed the contract's size budget
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ContractReport {
+    pub name: String,
+    pub status: String,
+    pub sizes: ArtifactSizes,
+    pub size_change: Option<i64>,
+    pub actions: Option<usize>,
+    pub build_ms: Option<u64>,
+    pub over_budget: Vec<String>,
+}
+
+/// Report of a `partizee compile` run
+///
+/// # Fields
+/// * `contracts` - One line per contract
+/// * `total_ms` - Milliseconds the whole compilation took
+/// * `diagnostics` - Errors and warnings of the compiler, per crate
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct BuildReport {
+    pub contracts: Vec<ContractReport>,
+    pub total_ms: u64,
+    pub diagnostics: BTreeMap<String, Vec<Diagnostic>>,
+}
+
+impl ArtifactSizes {
+    /// Reads the artifact sizes of a contract
+    ///
+    /// # Arguments
+    /// * `release_dir` - Directory cargo pbc writes artifacts to
+    /// * `name` - Package name of the contract
+    ///
+    /// # Returns
+    /// * `ArtifactSizes` - Size of each artifact that exists
+    pub fn read(release_dir: &Path, name: &str) -> Self {
+        let stem: String = name.replace('-', "_");
+        let size = |extension: &str| -> Option<u64> {
+            fs::metadata(release_dir.join(format!("{}.{}", stem, extension)))
+                .ok()
+                .map(|metadata| metadata.len())
+        };
+        Self {
+            wasm: size("wasm"),
+            pbc: size("pbc"),
+            abi: size("abi"),
+            zkwa: size("zkwa"),
+        }
+    }
+
+    /// Bytes sent when deploying, the .pbc if built, otherwise the code and ABI
+    pub fn deployed(&self) -> u64 {
+        match self.pbc {
+            Some(pbc) => pbc,
+            None => self.wasm.or(self.zkwa).unwrap_or(0) + self.abi.unwrap_or(0),
+        }
+    }
+
+    /// Checks the sizes against a budget
+    ///
+    /// # Arguments
+    /// * `budget` - Maximum size per artifact
+    ///
+    /// # Returns
+    /// * `Vec<String>` - One description per artifact over budget
+    pub fn over_budget(&self, budget: &SizeBudget) -> Vec<String> {
+        [
+            ("wasm", self.wasm, budget.wasm),
+            ("pbc", self.pbc, budget.pbc),
+            ("abi", self.abi, budget.abi),
+            ("zkwa", self.zkwa, budget.zkwa),
+        ]
+        .into_iter()
+        .filter_map(|(extension, size, limit)| match (size, limit) {
+            (Some(size), Some(limit)) if size > limit => {
+                Some(format!(".{} is {} bytes, {} over its budget of {}", extension, size, size - limit, limit))
+            }
+            _ => None,
+        })
+        .collect()
+    }
+}
+
+/// Counts the actions in a contract's ABI
+///
+/// # Arguments
+/// * `release_dir` - Directory cargo pbc writes artifacts to
+/// * `name` - Package name of the contract
+///
+/// # Returns
+/// * `Option<usize>` - Number of actions, None if the ABI is missing or cannot be parsed
+pub fn count_actions(release_dir: &Path, name: &str) -> Option<usize> {
+    let abi_bytes: Vec<u8> = fs::read(release_dir.join(format!("{}.abi", name.replace('-', "_")))).ok()?;
+    let abi: ContractAbi = ContractAbi::from_bytes(&abi_bytes).ok()?;
+    Some(
+        abi.functions
+            .iter()
+            .filter(|function| function.kind == FnKind::Action)
+            .count(),
+    )
+}
+
+impl BuildReport {
+    /// Names of the contracts whose build failed
+    pub fn failed_contracts(&self) -> Vec<String> {
+        self.contracts
+            .iter()
+            .filter(|contract| contract.status == "failed")
+            .map(|contract| contract.name.clone())
+            .collect()
+    }
+
+    /// Descriptions of every artifact over its budget
+    pub fn budget_violations(&self) -> Vec<String> {
+        self.contracts
+            .iter()
+            .flat_map(|contract| {
+                contract
+                    .over_budget
+                    .iter()
+                    .map(move |violation| format!("{}: {}", contract.name, violation))
+            })
+            .collect()
+    }
+
+    /// Renders the report as a table, one row per contract, followed by the compiler's diagnostics
+    ///
+    /// # Returns
+    /// * `String` - Printable table
+    pub fn render_table(&self) -> String {
+        let size = |size: Option<u64>| size.map(|size| size.to_string()).unwrap_or("-".to_string());
+        let mut lines: Vec<String> = vec![format!(
+            "{:<24} {:<8} {:>10} {:>10} {:>8} {:>10} {:>9} {:>8} {:>9}",
+            "CONTRACT", "STATUS", "WASM", "PBC", "ABI", "ZKWA", "CHANGE", "ACTIONS", "TIME"
+        )];
+        for contract in self.contracts.iter() {
+            let status: String = match contract.over_budget.is_empty() {
+                true => contract.status.clone(),
+                false => format!("{}!", contract.status),
+            };
+            lines.push(format!(
+                "{:<24} {:<8} {:>10} {:>10} {:>8} {:>10} {:>9} {:>8} {:>9}",
+                contract.name,
+                status,
+                size(contract.sizes.wasm),
+                size(contract.sizes.pbc),
+                size(contract.sizes.abi),
+                size(contract.sizes.zkwa),
+                contract
+                    .size_change
+                    .map(|change| format!("{:+}", change))
+                    .unwrap_or("new".to_string()),
+                contract
+                    .actions
+                    .map(|actions| actions.to_string())
+                    .unwrap_or("-".to_string()),
+                contract
+                    .build_ms
+                    .map(|build_ms| format!("{:.1}s", build_ms as f64 / 1000.0))
+                    .unwrap_or("-".to_string()),
+            ));
+        }
+        let diagnostics: String = render_grouped(&self.diagnostics);
+        if !diagnostics.is_empty() {
+            lines.insert(0, format!("{}\n", diagnostics));
+        }
+        lines.push(format!("Compiled in {:.1}s", self.total_ms as f64 / 1000.0));
+        lines.extend(self.budget_violations().into_iter().map(|violation| format!("❌ {}", violation)));
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_artifact_sizes() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("counter_v1.wasm"), vec![0; 300]).unwrap();
+        fs::write(temp_dir.path().join("counter_v1.abi"), vec![0; 20]).unwrap();
+        let sizes = ArtifactSizes::read(temp_dir.path(), "counter-v1");
+        assert_eq!(sizes.wasm, Some(300));
+        assert_eq!(sizes.pbc, None);
+        assert_eq!(sizes.deployed(), 320);
+
+        let budget = SizeBudget {
+            wasm: Some(256),
+            abi: Some(20),
+            ..SizeBudget::default()
+        };
+        assert_eq!(
+            sizes.over_budget(&budget),
+            vec![".wasm is 300 bytes, 44 over its budget of 256".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_render_report() {
+        let report = BuildReport {
+            contracts: vec![ContractReport {
+                name: "counter".to_string(),
+                status: "rebuilt".to_string(),
+                sizes: ArtifactSizes {
+                    pbc: Some(1200),
+                    ..ArtifactSizes::default()
+                },
+                size_change: Some(-40),
+                actions: Some(2),
+                build_ms: Some(1500),
+                over_budget: vec![".pbc is 1200 bytes, 200 over its budget of 1000".to_string()],
+            }],
+            total_ms: 2000,
+            diagnostics: BTreeMap::new(),
+        };
+        let table = report.render_table();
+        assert!(table.contains("rebuilt!"));
+        assert!(table.contains("-40"));
+        assert!(table.contains("1.5s"));
+        assert_eq!(report.budget_violations(), vec!["counter: .pbc is 1200 bytes, 200 over its budget of 1000"]);
+        assert!(report.failed_contracts().is_empty());
+        let json: serde_json::Value = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["contracts"][0]["sizes"]["pbc"], 1200);
+
+        let failed = ContractReport {
+            name: "token".to_string(),
+            status: "failed".to_string(),
+            ..report.contracts[0].clone()
+        };
+        let report = BuildReport {
+            contracts: vec![report.contracts[0].clone(), failed],
+            ..report
+        };
+        assert_eq!(report.failed_contracts(), vec!["token"]);
+    }
+}
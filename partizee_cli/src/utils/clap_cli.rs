@@ -12,6 +12,12 @@ use clap::{Args, Parser, Subcommand};
 pub struct Arguments {
     #[clap(subcommand)]
     pub commands: Commands,
+    #[clap(
+        help = "run against the project in this directory instead of the current one",
+        long = "project-dir",
+        global = true
+    )]
+    pub project_dir: Option<String>,
 }
 #[derive(Subcommand)]
 pub enum Commands {
@@ -69,6 +75,38 @@ pub enum Commands {
             allow_hyphen_values = true
             )]
         additional_args: Option<Vec<String>>,
+        #[clap(
+            help = "rebuild every contract, even those unchanged since their last build",
+            long = "force"
+        )]
+        force: bool,
+        #[clap(
+            help = "number of contracts built at the same time, defaults to the number of CPUs",
+            short = 'j',
+            long = "jobs"
+        )]
+        jobs: Option<usize>,
+        #[clap(
+            help = "format of the build report and compiler diagnostics",
+            long = "output",
+            default_value = "table",
+            value_parser = ["table", "json"]
+        )]
+        output: String,
+        #[clap(
+            help = "keep running and rebuild the contracts that change",
+            short = 'w',
+            long = "watch"
+        )]
+        watch: bool,
+        #[clap(
+            help = "with --watch, redeploy rebuilt contracts to testnet and regenerate the frontend bindings",
+            long = "deploy",
+            requires = "watch"
+        )]
+        deploy: bool,
+        #[clap(help = "path to the account --deploy deploys from", long = "account", requires = "deploy")]
+        pk_path: Option<String>,
     },
     #[clap(about = "deploy your dapp")]
     Deploy {
@@ -100,6 +138,187 @@ pub enum Commands {
         deploy_args: Option<Vec<String>>,
         #[clap(help = "path to the account", short = 'a', long = "account")]
         pk_path: Option<String>,
+        #[clap(
+            help = "path to the deployment manifest, defaults to partizee.toml in the project root",
+            short = 'm',
+            long = "manifest"
+        )]
+        manifest: Option<String>,
+        #[clap(
+            help = "gas limit per contract, e.g. --gas MyContract=2000000, defaults to an estimate from the artifact size",
+            short = 'g',
+            long = "gas",
+            num_args = 1..,
+        )]
+        gas: Option<Vec<String>>,
+        #[clap(
+            help = "validate the deployment, estimate gas and check the balance without submitting anything",
+            long = "dry-run"
+        )]
+        dry_run: bool,
+        #[clap(
+            help = "resume a deploy run, skipping the contracts that already succeeded",
+            short = 'r',
+            long = "resume"
+        )]
+        resume: Option<String>,
+        #[clap(
+            help = "send to mainnet without typing the confirmation, the safety checks still apply",
+            long = "confirm-mainnet"
+        )]
+        confirm_mainnet: bool,
+    },
+    #[clap(about = "invoke an action on a deployed contract")]
+    Call {
+        #[clap(help = "name or address of the deployed contract")]
+        contract: String,
+        #[clap(help = "name of the action to invoke")]
+        action: String,
+        #[clap(
+            help = "action arguments in the order of the ABI",
+            num_args = 0..,
+            allow_hyphen_values = true
+        )]
+        args: Vec<String>,
+        #[clap(
+            help = "network of the contract, defaults to the network it was recorded on",
+            short = 'c',
+            long = "chain"
+        )]
+        custom_net: Option<String>,
+        #[clap(help = "path to the account", short = 'a', long = "account")]
+        pk_path: Option<String>,
+        #[clap(help = "gas limit of the transaction", short = 'g', long = "gas")]
+        gas: Option<i64>,
+        #[clap(
+            help = "wait until the transaction and its events have been finalized",
+            short = 'w',
+            long = "wait"
+        )]
+        wait: bool,
+        #[clap(
+            help = "send to mainnet without typing the confirmation, the safety checks still apply",
+            long = "confirm-mainnet"
+        )]
+        confirm_mainnet: bool,
+    },
+
+    #[clap(about = "print the decoded state of a deployed contract")]
+    State {
+        #[clap(help = "name or address of the deployed contract")]
+        contract: String,
+        #[clap(
+            help = "network of the contract, defaults to the network it was recorded on",
+            short = 'c',
+            long = "chain"
+        )]
+        custom_net: Option<String>,
+        #[clap(help = "read the state as of this block time", long = "at-block")]
+        at_block: Option<i64>,
+        #[clap(
+            help = "print a nested value, e.g. --field upgradable_to.upgrade_rpc",
+            short = 'f',
+            long = "field"
+        )]
+        field: Option<String>,
+    },
+
+    #[clap(about = "show the execution status of a transaction and the events it spawned")]
+    Tx {
+        #[clap(help = "hex encoded transaction hash")]
+        hash: String,
+        #[clap(help = "network of the transaction, defaults to testnet", short = 'c', long = "chain")]
+        custom_net: Option<String>,
+        #[clap(
+            help = "shard the transaction was sent to, every shard is searched if omitted",
+            short = 's',
+            long = "shard"
+        )]
+        shard: Option<String>,
+        #[clap(
+            help = "wait until the transaction and its events have been finalized",
+            short = 'w',
+            long = "wait"
+        )]
+        wait: bool,
+        #[clap(
+            help = "seconds to wait before giving up",
+            short = 't',
+            long = "timeout",
+            default_value_t = 180
+        )]
+        timeout: u64,
+    },
+
+    #[clap(about = "check that deployed contracts match the local build")]
+    Verify {
+        #[clap(
+            help = "name or address of a deployed contract, every contract in deployment-latest.json if omitted"
+        )]
+        contract: Option<String>,
+        #[clap(help = "only verify deployments on this network", short = 'c', long = "chain")]
+        custom_net: Option<String>,
+    },
+
+    #[clap(about = "print the contract hashes of a compiled contract, as taken by allow_upgrade_to")]
+    Hash {
+        #[clap(help = "name of the compiled contract")]
+        contract: String,
+        #[clap(help = "hex binder hash, keeps the computation offline", long = "binder-hash")]
+        binder_hash: Option<String>,
+        #[clap(
+            help = "name or address of a deployed contract to read the binder hash from, defaults to <CONTRACT>",
+            long = "binder-from"
+        )]
+        binder_from: Option<String>,
+        #[clap(
+            help = "network the binder hash is read from, defaults to the network the contract was recorded on",
+            short = 'c',
+            long = "chain"
+        )]
+        custom_net: Option<String>,
+    },
+
+    #[clap(about = "upgrade a deployed contract to new code")]
+    Upgrade {
+        #[clap(help = "name or address of the deployed contract")]
+        contract: String,
+        #[clap(help = "contract whose compiled artifact replaces the deployed code", long = "to")]
+        target: String,
+        #[clap(
+            help = "network of the deployment, defaults to the network it was recorded on",
+            short = 'c',
+            long = "chain"
+        )]
+        custom_net: Option<String>,
+        #[clap(help = "path to the account", short = 'a', long = "account")]
+        pk_path: Option<String>,
+        #[clap(
+            help = "send allow_upgrade_to with the new contract hashes before upgrading",
+            long = "allow-upgrade"
+        )]
+        allow_upgrade: bool,
+        #[clap(
+            help = "hex encoded arguments of the new contract's #[upgrade] function",
+            long = "upgrade-rpc"
+        )]
+        upgrade_rpc: Option<String>,
+        #[clap(
+            help = "gas limit of the upgrade, defaults to an estimate from the artifact size",
+            short = 'g',
+            long = "gas"
+        )]
+        gas: Option<i64>,
+        #[clap(
+            help = "hex binder hash, defaults to the binder of the deployed contract",
+            long = "binder-hash"
+        )]
+        binder_hash: Option<String>,
+        #[clap(
+            help = "send to mainnet without typing the confirmation, the safety checks still apply",
+            long = "confirm-mainnet"
+        )]
+        confirm_mainnet: bool,
     },
 
     #[clap(about = "create a new account")]
@@ -107,6 +326,18 @@ pub enum Commands {
         #[clap(subcommand)]
         commands: ProfileSubcommands,
     },
+
+    #[clap(about = "browse deployment history")]
+    Deployments {
+        #[clap(subcommand)]
+        commands: DeploymentsSubcommands,
+    },
+
+    #[clap(about = "check the toolchain, keys and network configuration")]
+    Doctor {
+        #[clap(help = "skip contacting the network endpoints", long = "offline")]
+        offline: bool,
+    },
 }
 
 #[derive(Args, Debug)]
@@ -149,3 +380,57 @@ pub enum ProfileSubcommands {
         shared_args: ProfileSharedArgs,
     },
 }
+
+#[derive(Subcommand)]
+pub enum DeploymentsSubcommands {
+    #[clap(about = "list every recorded deployment", name = "list")]
+    DeploymentsList,
+    #[clap(about = "show a deployment in detail", name = "show")]
+    DeploymentsShow {
+        #[clap(help = "name of the deployed contract")]
+        contract: String,
+        #[clap(
+            help = "snapshot to read from, defaults to the newest deployment of the contract",
+            short = 's',
+            long = "snapshot"
+        )]
+        snapshot: Option<String>,
+        #[clap(
+            help = "network of the contract, defaults to its newest deployment on any network",
+            short = 'c',
+            long = "chain"
+        )]
+        custom_net: Option<String>,
+    },
+    #[clap(about = "print the latest address of a contract", name = "address")]
+    DeploymentsAddress {
+        #[clap(help = "name of the deployed contract")]
+        contract: String,
+        #[clap(
+            help = "network of the contract, defaults to its newest deployment on any network",
+            short = 'c',
+            long = "chain"
+        )]
+        custom_net: Option<String>,
+    },
+    #[clap(about = "compare the contracts deployed as of two snapshots", name = "diff")]
+    DeploymentsDiff {
+        #[clap(help = "older snapshot, defaults to the one before the newer snapshot")]
+        from: Option<String>,
+        #[clap(help = "newer snapshot, defaults to latest")]
+        to: Option<String>,
+    },
+    #[clap(about = "export deployment history", name = "export")]
+    DeploymentsExport {
+        #[clap(
+            help = "output format",
+            short = 'f',
+            long = "format",
+            default_value = "json",
+            value_parser = ["json", "csv", "markdown"]
+        )]
+        format: String,
+        #[clap(help = "file to write to, defaults to stdout", short = 'o', long = "output")]
+        output: Option<String>,
+    },
+}
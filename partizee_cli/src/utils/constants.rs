@@ -18,10 +18,36 @@ pub const ETH_MAINNET_ADDRESS: &str = "00000000000000000000000000000000000000000
 pub const USDC_MAINNET_ADDRESS: &str = "000000000000000000000000000000000000000000";
 
 // rpc endpoings
-#[allow(dead_code)]
 pub const TESTNET_RPC_ENDPOINT: &str = "https://node1.testnet.partisiablockchain.com";
-#[allow(dead_code)]
 pub const MAINNET_RPC_ENDPOINT: &str = "https://rpc.mainnet.partisia.io";
 
-#[allow(dead_code)]
 pub const SHARDS: [&str; 3] = ["Shard0", "Shard1", "Shard2"];
+
+// chain ids used when signing transactions
+pub const TESTNET_CHAIN_ID: &str = "Partisia Blockchain Testnet";
+pub const MAINNET_CHAIN_ID: &str = "Partisia Blockchain";
+
+// system contracts
+pub const PUB_DEPLOY_ADDRESS: &str = "0197a0e238e924025bad144aa0c4913e46308f9a4d";
+pub const ZK_DEPLOY_ADDRESS: &str = "018bc1ccbb672b87710327713c97d43204905082cb";
+
+// transaction defaults
+pub const TRANSACTION_VALIDITY_MS: i64 = 180_000;
+pub const DEFAULT_ACTION_GAS: i64 = 100_000;
+pub const TRANSACTION_POLL_INTERVAL_MS: u64 = 1_000;
+
+// compile --watch, files are polled and have to be unchanged for the debounce before a rebuild
+pub const WATCH_POLL_INTERVAL_MS: u64 = 500;
+pub const WATCH_DEBOUNCE_MS: u64 = 300;
+
+// deploy gas estimation, network fee per byte sent plus the deploy contract and init execution
+pub const DEPLOY_BASE_GAS: i64 = 500_000;
+pub const DEPLOY_GAS_PER_BYTE: i64 = 20;
+pub const INIT_BASE_GAS: i64 = 250_000;
+pub const ZK_NODE_ALLOCATION_GAS: i64 = 2_000_000;
+pub const GAS_ESTIMATE_MARGIN_PERCENT: i64 = 20;
+
+// toolchain partizee doctor expects, matching install.sh
+pub const REQUIRED_RUST_VERSION: &str = "1.86";
+pub const REQUIRED_JAVA_VERSION: u64 = 17;
+pub const WASM_TARGET: &str = "wasm32-unknown-unknown";
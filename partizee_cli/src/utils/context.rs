@@ -0,0 +1,116 @@
+use crate::utils::fs_nav::find_workspace_root;
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// Directory a command works on, every search for the project starts here
+///
+/// The CLI never changes its working directory, `--project-dir` only moves this context.
+///
+/// # Fields
+/// * `project_dir` - Absolute directory the project is searched from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectContext {
+    pub project_dir: PathBuf,
+}
+
+impl Default for ProjectContext {
+    /// Creates a context for the current directory
+    fn default() -> Self {
+        Self::at(&env::current_dir().unwrap_or(PathBuf::from(".")))
+    }
+}
+
+impl ProjectContext {
+    /// Creates the context from the `--project-dir` argument
+    ///
+    /// # Arguments
+    /// * `project_dir` - Directory given on the command line, relative paths are taken from the current directory
+    ///
+    /// # Returns
+    /// * `Result<ProjectContext>` - Context, Error if the directory does not exist
+    pub fn new(project_dir: Option<&str>) -> Result<Self, Box<dyn std::error::Error>> {
+        let current_dir: PathBuf = env::current_dir()?;
+        let project_dir: PathBuf = match project_dir {
+            Some(project_dir) => current_dir.join(project_dir),
+            None => current_dir,
+        };
+        if !project_dir.is_dir() {
+            return Err(format!("Project directory {} does not exist", project_dir.display()).into());
+        }
+        Ok(Self::at(&project_dir))
+    }
+
+    /// Creates a context for a known directory
+    ///
+    /// # Arguments
+    /// * `project_dir` - Directory the project is searched from
+    ///
+    /// # Returns
+    /// * `ProjectContext` - Context for the directory
+    pub fn at(project_dir: &Path) -> Self {
+        Self {
+            project_dir: project_dir.canonicalize().unwrap_or(project_dir.to_path_buf()),
+        }
+    }
+
+    /// Finds the workspace root of the project
+    ///
+    /// # Returns
+    /// * `Option<PathBuf>` - Path to workspace root if found, None otherwise
+    pub fn workspace_root(&self) -> Option<PathBuf> {
+        find_workspace_root(&self.project_dir)
+    }
+
+    /// Finds the workspace root of the project, for commands that need one
+    ///
+    /// # Returns
+    /// * `Result<PathBuf>` - Path to workspace root, Error if the directory is not in a Partizee project
+    pub fn require_workspace_root(&self) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        self.workspace_root().ok_or_else(|| {
+            format!("{} is not in a partizee project", self.project_dir.display()).into()
+        })
+    }
+
+    /// Directory to search for project files, the workspace root or else the project directory
+    ///
+    /// # Returns
+    /// * `PathBuf` - Directory to start searching from
+    pub fn search_root(&self) -> PathBuf {
+        self.workspace_root().unwrap_or(self.project_dir.clone())
+    }
+
+    /// Resolves a path given relative to the project directory
+    ///
+    /// # Arguments
+    /// * `path` - Absolute path or path relative to the project directory
+    ///
+    /// # Returns
+    /// * `PathBuf` - Absolute path
+    pub fn resolve(&self, path: &Path) -> PathBuf {
+        self.project_dir.join(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::utils::setup_test_environment;
+
+    #[test]
+    fn test_project_context() {
+        let (_temp_dir, temp_path) = setup_test_environment();
+        let context: ProjectContext = ProjectContext::at(&temp_path.join("rust/contracts"));
+        let root: PathBuf = temp_path.canonicalize().unwrap();
+        assert_eq!(context.workspace_root(), Some(root.clone()));
+        assert_eq!(context.search_root(), root);
+        assert_eq!(context.resolve(Path::new("counter")), root.join("rust/contracts/counter"));
+
+        let absolute: PathBuf = temp_path.join("Cargo.toml");
+        assert_eq!(context.resolve(&absolute), absolute);
+        assert!(ProjectContext::new(Some(&temp_path.join("missing").display().to_string())).is_err());
+        assert_eq!(
+            ProjectContext::new(Some(&temp_path.display().to_string())).unwrap().project_dir,
+            root
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use k256::{elliptic_curve::sec1::ToEncodedPoint, SecretKey};
+use sha2::{Digest, Sha256};
+
+/// Address type prefix for user accounts
+pub const ACCOUNT_ADDRESS_PREFIX: u8 = 0x00;
+/// Length of a blockchain address in bytes (prefix + 20 byte identifier)
+pub const ADDRESS_LENGTH: usize = 21;
+
+/// Parses a hex encoded secp256k1 private key
+///
+/// # Arguments
+/// * `private_key` - 64 character hex string
+///
+/// # Returns
+/// * `Result<SecretKey>` - Parsed secret key if the hex is a valid scalar
+pub fn secret_key_from_hex(private_key: &str) -> Result<SecretKey, Box<dyn std::error::Error>> {
+    let key_bytes: Vec<u8> = hex::decode(private_key.trim())
+        .map_err(|e| format!("secret_key_from_hex: Invalid hex in private key: {}", e))?;
+    if key_bytes.len() != 32 {
+        return Err("secret_key_from_hex: Private key must be 32 bytes".into());
+    }
+    Ok(SecretKey::from_slice(&key_bytes)?)
+}
+
+/// Derives the account address of a secret key
+///
+/// The address is the account prefix followed by the last 20 bytes of the
+/// sha256 hash of the uncompressed public key
+///
+/// # Arguments
+/// * `secret_key` - Key to derive the address for
+///
+/// # Returns
+/// * `[u8; ADDRESS_LENGTH]` - Raw address bytes
+pub fn address_from_secret_key(secret_key: &SecretKey) -> [u8; ADDRESS_LENGTH] {
+    let public_key = secret_key.public_key().to_encoded_point(false);
+    let hash = Sha256::digest(public_key.as_bytes());
+
+    let mut address = [0u8; ADDRESS_LENGTH];
+    address[0] = ACCOUNT_ADDRESS_PREFIX;
+    address[1..].copy_from_slice(&hash[hash.len() - (ADDRESS_LENGTH - 1)..]);
+    address
+}
+
+/// Derives the hex encoded account address of a hex encoded private key
+///
+/// # Arguments
+/// * `private_key` - 64 character hex string
+///
+/// # Returns
+/// * `Result<String>` - 42 character hex address
+pub fn address_from_private_key_hex(private_key: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let secret_key: SecretKey = secret_key_from_hex(private_key)?;
+    Ok(hex::encode(address_from_secret_key(&secret_key)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address_from_private_key_hex() {
+        let address = address_from_private_key_hex(
+            "9c1a15a50a4f978f0085bd747b9da360cc0fbf5f1d0744e040873aeba46b37b0",
+        )
+        .unwrap();
+        assert_eq!(address, "00d277aa1bf5702ab9fc690b04bd68b5a981095530");
+    }
+
+    #[test]
+    fn test_address_has_account_prefix() {
+        let address = address_from_private_key_hex(
+            "0000000000000000000000000000000000000000000000000000000000000001",
+        )
+        .unwrap();
+        assert_eq!(address.len(), ADDRESS_LENGTH * 2);
+        assert!(address.starts_with("00"));
+    }
+
+    #[test]
+    fn test_invalid_private_key() {
+        assert!(address_from_private_key_hex("not hex").is_err());
+        assert!(address_from_private_key_hex("00").is_err());
+        // zero is not a valid secp256k1 scalar
+        assert!(address_from_private_key_hex(
+            "0000000000000000000000000000000000000000000000000000000000000000"
+        )
+        .is_err());
+    }
+}
@@ -1,3 +1,4 @@
+pub mod address;
 pub mod bip32;
 pub mod bip39;
 pub mod bip44;
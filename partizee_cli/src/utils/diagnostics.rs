@@ -0,0 +1,281 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Argument that makes cargo print its messages as JSON lines on stdout
+pub const CARGO_MESSAGE_FORMAT: &str = "--message-format=json";
+
+/// Location in a source file, lines and columns start at 1
+///
+/// # Fields
+/// * `file` - Path of the file, relative to the workspace root
+/// * `line` - First line of the span
+/// * `column` - First column of the span
+/// * `end_line` - Last line of the span
+/// * `end_column` - Column after the end of the span
+/// * `label` - Explanation attached to the span
+/// * `source` - Source text of the first line of the span
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub file: String,
+    pub line: u64,
+    pub column: u64,
+    pub end_line: u64,
+    pub end_column: u64,
+    pub label: Option<String>,
+    pub source: Option<String>,
+}
+
+/// Change the compiler proposes to fix a diagnostic
+///
+/// # Fields
+/// * `message` - What the change does
+/// * `span` - Code that is replaced
+/// * `replacement` - Code to put in its place
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub message: String,
+    pub span: Span,
+    pub replacement: String,
+}
+
+/// Error or warning reported by the compiler
+///
+/// # Fields
+/// * `crate_name` - Package the diagnostic was reported for
+/// * `level` - `error` or `warning`
+/// * `message` - Main message
+/// * `code` - Error or lint code, e.g. E0308
+/// * `span` - Primary location of the diagnostic, None if it has no location
+/// * `notes` - Notes and help messages without a code change
+/// * `suggestions` - Code changes the compiler proposes
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub crate_name: String,
+    pub level: String,
+    pub message: String,
+    pub code: Option<String>,
+    pub span: Option<Span>,
+    pub notes: Vec<String>,
+    pub suggestions: Vec<Suggestion>,
+}
+
+/// Parses a line of cargo's JSON output
+///
+/// # Arguments
+/// * `line` - Line printed by cargo with `--message-format=json`
+///
+/// # Returns
+/// * `Option<Diagnostic>` - The diagnostic, None for other messages and for the compiler's summaries
+pub fn parse_cargo_message(line: &str) -> Option<Diagnostic> {
+    let value: Value = serde_json::from_str(line).ok()?;
+    if value["reason"] != "compiler-message" {
+        return None;
+    }
+    let message: &Value = &value["message"];
+    let level: &str = message["level"].as_str()?;
+    let text: &str = message["message"].as_str()?;
+    // the summaries repeat what the diagnostics before them already said
+    let summary: bool = text.starts_with("aborting due to")
+        || (text.ends_with("emitted") && text.contains("warning"))
+        || level == "failure-note";
+    if summary || !(level == "error" || level == "warning") {
+        return None;
+    }
+
+    let mut notes: Vec<String> = Vec::new();
+    let mut suggestions: Vec<Suggestion> = Vec::new();
+    for child in message["children"].as_array().into_iter().flatten() {
+        let child_message: String = child["message"].as_str().unwrap_or_default().to_string();
+        let replacements: Vec<Suggestion> = child["spans"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|span| {
+                Some(Suggestion {
+                    message: child_message.clone(),
+                    replacement: span["suggested_replacement"].as_str()?.to_string(),
+                    span: parse_span(span)?,
+                })
+            })
+            .collect();
+        match replacements.is_empty() {
+            true => notes.push(format!("{}: {}", child["level"].as_str().unwrap_or("note"), child_message)),
+            false => suggestions.extend(replacements),
+        }
+    }
+
+    Some(Diagnostic {
+        crate_name: package_name(&value),
+        level: level.to_string(),
+        message: text.to_string(),
+        code: message["code"]["code"].as_str().map(|code| code.to_string()),
+        span: message["spans"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|span| span["is_primary"] == true)
+            .and_then(parse_span),
+        notes,
+        suggestions,
+    })
+}
+
+/// Parses a span of a compiler message
+fn parse_span(span: &Value) -> Option<Span> {
+    Some(Span {
+        file: span["file_name"].as_str()?.to_string(),
+        line: span["line_start"].as_u64()?,
+        column: span["column_start"].as_u64()?,
+        end_line: span["line_end"].as_u64()?,
+        end_column: span["column_end"].as_u64()?,
+        label: span["label"].as_str().map(|label| label.to_string()),
+        source: span["text"][0]["text"].as_str().map(|text| text.to_string()),
+    })
+}
+
+/// Package name of a cargo message, from its package id
+///
+/// Handles both the `name version (source)` ids of older cargo versions and the
+/// `source#name@version` ids of newer ones, where the name is left out if it matches the directory.
+fn package_name(value: &Value) -> String {
+    let package_id: &str = value["package_id"].as_str().unwrap_or_default();
+    let name: Option<&str> = match package_id.split_once('#') {
+        Some((source, version)) => match version.split_once('@') {
+            Some((name, _)) => Some(name),
+            None => source.rsplit('/').next(),
+        },
+        None => package_id.split_whitespace().next(),
+    };
+    name.filter(|name| !name.is_empty())
+        .or(value["target"]["name"].as_str())
+        .unwrap_or("unknown")
+        .to_string()
+}
+
+impl Diagnostic {
+    /// Renders the diagnostic the way rustc does, with its location, span and suggestions
+    ///
+    /// # Returns
+    /// * `String` - Printable diagnostic
+    pub fn render(&self) -> String {
+        let mut lines: Vec<String> = vec![match self.code.as_ref() {
+            Some(code) => format!("{}[{}]: {}", self.level, code, self.message),
+            None => format!("{}: {}", self.level, self.message),
+        }];
+        if let Some(span) = self.span.as_ref() {
+            lines.push(format!("  --> {}:{}:{}", span.file, span.line, span.column));
+            if let Some(source) = span.source.as_ref() {
+                let gutter: String = " ".repeat(span.line.to_string().len());
+                // multi-line spans are underlined to the end of their first line
+                let end_column: u64 = match span.end_line == span.line {
+                    true => span.end_column,
+                    false => source.chars().count() as u64 + 1,
+                };
+                let underline: String = format!(
+                    "{}{}",
+                    " ".repeat(span.column.saturating_sub(1) as usize),
+                    "^".repeat(end_column.saturating_sub(span.column).max(1) as usize)
+                );
+                lines.push(format!("{} |", gutter));
+                lines.push(format!("{} | {}", span.line, source));
+                lines.push(format!("{} | {} {}", gutter, underline, span.label.clone().unwrap_or_default()).trim_end().to_string());
+            }
+        }
+        lines.extend(self.notes.iter().map(|note| format!("  = {}", note)));
+        lines.extend(self.suggestions.iter().map(|suggestion| {
+            format!(
+                "  = suggestion: {}: replace {}:{}:{} with `{}`",
+                suggestion.message, suggestion.span.file, suggestion.span.line, suggestion.span.column, suggestion.replacement
+            )
+        }));
+        lines.join("\n")
+    }
+}
+
+/// Groups diagnostics by the crate they were reported for, dropping duplicates
+///
+/// Crates shared by contracts built in parallel report the same diagnostics once per build.
+///
+/// # Arguments
+/// * `diagnostics` - Diagnostics of every build
+///
+/// # Returns
+/// * `BTreeMap<String, Vec<Diagnostic>>` - Diagnostics per crate, in the order they were reported
+pub fn group_by_crate(diagnostics: Vec<Diagnostic>) -> BTreeMap<String, Vec<Diagnostic>> {
+    let mut grouped: BTreeMap<String, Vec<Diagnostic>> = BTreeMap::new();
+    for diagnostic in diagnostics {
+        let crate_diagnostics: &mut Vec<Diagnostic> = grouped.entry(diagnostic.crate_name.clone()).or_default();
+        if !crate_diagnostics.contains(&diagnostic) {
+            crate_diagnostics.push(diagnostic);
+        }
+    }
+    grouped
+}
+
+/// Renders grouped diagnostics, each crate under a heading counting its errors and warnings
+///
+/// # Arguments
+/// * `grouped` - Diagnostics per crate
+///
+/// # Returns
+/// * `String` - Printable diagnostics, empty if there are none
+pub fn render_grouped(grouped: &BTreeMap<String, Vec<Diagnostic>>) -> String {
+    let mut sections: Vec<String> = Vec::new();
+    for (crate_name, diagnostics) in grouped.iter().filter(|(_, diagnostics)| !diagnostics.is_empty()) {
+        let errors: usize = diagnostics.iter().filter(|diagnostic| diagnostic.level == "error").count();
+        let warnings: usize = diagnostics.len() - errors;
+        let mut section: Vec<String> = vec![format!("{}: {} error(s), {} warning(s)", crate_name, errors, warnings)];
+        section.extend(diagnostics.iter().map(|diagnostic| diagnostic.render()));
+        sections.push(section.join("\n\n"));
+    }
+    sections.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MISMATCHED_TYPES: &str = r#"{"reason":"compiler-message","package_id":"path+file:///work/rust/contracts/counter#0.1.0","manifest_path":"/work/rust/contracts/counter/Cargo.toml","target":{"name":"counter"},"message":{"message":"mismatched types","code":{"code":"E0308"},"level":"error","spans":[{"file_name":"rust/contracts/counter/src/lib.rs","line_start":12,"line_end":12,"column_start":19,"column_end":22,"is_primary":true,"label":"expected `u32`, found `&str`","suggested_replacement":null,"text":[{"text":"    state.count = \"1\";","highlight_start":19,"highlight_end":22}]}],"children":[{"message":"try using a conversion method","level":"help","spans":[{"file_name":"rust/contracts/counter/src/lib.rs","line_start":12,"line_end":12,"column_start":19,"column_end":22,"is_primary":true,"label":null,"suggested_replacement":"1","text":[]}],"children":[]},{"message":"expected due to the type of this field","level":"note","spans":[],"children":[]}],"rendered":"error[E0308]: mismatched types"}}"#;
+
+    #[test]
+    fn test_parse_cargo_message() {
+        let diagnostic: Diagnostic = parse_cargo_message(MISMATCHED_TYPES).unwrap();
+        assert_eq!(diagnostic.crate_name, "counter");
+        assert_eq!(diagnostic.code.as_deref(), Some("E0308"));
+        let span: &Span = diagnostic.span.as_ref().unwrap();
+        assert_eq!((span.line, span.column, span.end_column), (12, 19, 22));
+        assert_eq!(diagnostic.suggestions[0].replacement, "1");
+        assert_eq!(diagnostic.notes, vec!["note: expected due to the type of this field".to_string()]);
+
+        let rendered: String = diagnostic.render();
+        assert!(rendered.starts_with("error[E0308]: mismatched types\n  --> rust/contracts/counter/src/lib.rs:12:19"));
+        assert!(rendered.contains("12 |     state.count = \"1\";\n   |                   ^^^ expected `u32`, found `&str`"));
+        assert!(rendered.contains("= suggestion: try using a conversion method: replace rust/contracts/counter/src/lib.rs:12:19 with `1`"));
+
+        let summary: &str = r#"{"reason":"compiler-message","package_id":"counter 0.1.0 (path+file:///work)","target":{"name":"counter"},"message":{"message":"aborting due to 1 previous error","code":null,"level":"error","spans":[],"children":[]}}"#;
+        assert_eq!(parse_cargo_message(summary), None);
+        assert_eq!(parse_cargo_message(r#"{"reason":"build-finished","success":false}"#), None);
+        assert_eq!(parse_cargo_message("   Compiling counter v0.1.0"), None);
+    }
+
+    #[test]
+    fn test_group_by_crate() {
+        let diagnostic: Diagnostic = parse_cargo_message(MISMATCHED_TYPES).unwrap();
+        let unused: Diagnostic = Diagnostic {
+            crate_name: "averageSalary".to_string(),
+            level: "warning".to_string(),
+            message: "unused variable: `sum`".to_string(),
+            code: Some("unused_variables".to_string()),
+            span: None,
+            notes: Vec::new(),
+            suggestions: Vec::new(),
+        };
+        let grouped = group_by_crate(vec![diagnostic.clone(), unused, diagnostic]);
+        assert_eq!(grouped.keys().collect::<Vec<_>>(), vec!["averageSalary", "counter"]);
+        assert_eq!(grouped["counter"].len(), 1);
+        let rendered: String = render_grouped(&grouped);
+        assert!(rendered.starts_with("averageSalary: 0 error(s), 1 warning(s)\n\nwarning[unused_variables]: unused variable: `sum`"));
+        assert!(rendered.contains("counter: 1 error(s), 0 warning(s)"));
+    }
+}
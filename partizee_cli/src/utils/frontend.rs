@@ -0,0 +1,163 @@
+use crate::commands::deployments::DeploymentHistory;
+use crate::utils::pbc_commands::pbc_abi_codegen;
+use crate::utils::utils::get_chain_id;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Frontend directory of a scaffolded project
+pub const FRONTEND_DIR_NAME: &str = "frontend";
+/// Directory inside the frontend the generated files are written to
+pub const GENERATED_DIR: &str = "src/generated";
+/// Generated module with the deployed contract addresses
+pub const DEPLOYMENTS_TS_FILE: &str = "deployments.ts";
+
+/// Renders `deployments.ts`
+///
+/// # Arguments
+/// * `network` - Network the addresses are deployed on
+/// * `addresses` - Map of contract names to addresses
+///
+/// # Returns
+/// * `Result<String>` - TypeScript module source, an error if the network's chain id cannot be resolved
+pub fn render_deployments_ts(
+    network: &str,
+    addresses: &BTreeMap<String, String>,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let mut source: String = String::new();
+    source.push_str("// This file is generated by `partizee deploy`, do not edit it by hand.\n");
+    source.push_str(&format!("export const NETWORK = {:?};\n", network));
+    source.push_str(&format!("export const CHAIN_ID = {:?};\n\n", get_chain_id(network)?));
+    source.push_str("export const DEPLOYMENTS: Record<string, string> = {\n");
+    for (name, address) in addresses {
+        source.push_str(&format!("  {:?}: {:?},\n", name, address));
+    }
+    source.push_str("};\n");
+    Ok(source)
+}
+
+/// Writes the deployed addresses and ABI bindings into the project's frontend
+///
+/// Every contract whose latest deployment is on `network` is listed in
+/// `frontend/src/generated/deployments.ts`. Bindings are generated for the contracts
+/// deployed in this run, a failing binding is reported but does not fail the deploy.
+///
+/// # Arguments
+/// * `project_root` - Root directory of project
+/// * `history` - Deployment history including the run that just finished
+/// * `network` - Network that was deployed to
+/// * `abis` - Names and ABI bytes of the contracts deployed in this run
+///
+/// # Returns
+/// * `Result<Option<PathBuf>>` - Path of deployments.ts, None if the project has no frontend
+pub fn generate_frontend_bindings(
+    project_root: &Path,
+    history: &DeploymentHistory,
+    network: &str,
+    abis: &[(String, Vec<u8>)],
+) -> Result<Option<PathBuf>, Box<dyn std::error::Error>> {
+    let frontend_dir: PathBuf = project_root.join(FRONTEND_DIR_NAME);
+    if !frontend_dir.is_dir() {
+        return Ok(None);
+    }
+    let generated_dir: PathBuf = frontend_dir.join(GENERATED_DIR);
+    fs::create_dir_all(&generated_dir)?;
+
+    // later records override earlier ones, names keep the casing of the latest deployment
+    let mut latest: BTreeMap<String, (String, String)> = BTreeMap::new();
+    for (_, deployment) in history.records() {
+        if deployment.network == network {
+            latest.insert(
+                deployment.name.to_lowercase(),
+                (deployment.name.clone(), deployment.address.clone()),
+            );
+        }
+    }
+    let addresses: BTreeMap<String, String> = latest.into_values().collect();
+    let deployments_path: PathBuf = generated_dir.join(DEPLOYMENTS_TS_FILE);
+    fs::write(&deployments_path, render_deployments_ts(network, &addresses)?)?;
+
+    for (name, abi_bytes) in abis {
+        let binding_path: PathBuf = generated_dir.join(format!("{}.ts", name));
+        let result = tempfile::Builder::new()
+            .suffix(".abi")
+            .tempfile()
+            .map_err(|e| e.into())
+            .and_then(|mut abi_file| {
+                abi_file.write_all(abi_bytes)?;
+                pbc_abi_codegen(abi_file.path(), &binding_path)
+            });
+        if let Err(e) = result {
+            eprintln!("⚠️ Failed to generate ABI bindings for {}: {}", name, e);
+        }
+    }
+    Ok(Some(deployments_path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::deploy::{Deployment, LATEST_DEPLOYMENT_FILE};
+    use tempfile::TempDir;
+
+    fn deployment(name: &str, network: &str, address: &str) -> Deployment {
+        Deployment {
+            name: name.to_string(),
+            network: network.to_string(),
+            address: address.to_string(),
+            transaction_hash: String::new(),
+            args: Vec::new(),
+            timestamp: "1700000000".to_string(),
+            code_version: None,
+            build_info: None,
+        }
+    }
+
+    #[test]
+    fn test_render_deployments_ts() {
+        let addresses = BTreeMap::from([
+            ("counterV1".to_string(), "02aa".to_string()),
+            ("counterV2".to_string(), "02bb".to_string()),
+        ]);
+        let source = render_deployments_ts("testnet", &addresses).unwrap();
+        assert!(source.contains("export const NETWORK = \"testnet\";"));
+        assert!(source.contains("export const CHAIN_ID = \"Partisia Blockchain Testnet\";"));
+        assert!(source.contains("  \"counterV1\": \"02aa\",\n  \"counterV2\": \"02bb\",\n"));
+    }
+
+    #[test]
+    fn test_generate_frontend_bindings() {
+        let temp_dir = TempDir::new().unwrap();
+        let deployments_dir = temp_dir.path().join("target/deployments");
+        fs::create_dir_all(&deployments_dir).unwrap();
+        fs::write(
+            deployments_dir.join("deployment-1700000000.json"),
+            serde_json::to_string(&[
+                deployment("counterV1", "testnet", "02old"),
+                deployment("counterV2", "mainnet", "02main"),
+            ])
+            .unwrap(),
+        )
+        .unwrap();
+        fs::write(
+            deployments_dir.join(LATEST_DEPLOYMENT_FILE),
+            serde_json::to_string(&[deployment("counterV1", "testnet", "02new")]).unwrap(),
+        )
+        .unwrap();
+        let history = DeploymentHistory::load(&deployments_dir).unwrap();
+
+        // projects without a frontend are left alone
+        assert!(generate_frontend_bindings(temp_dir.path(), &history, "testnet", &[])
+            .unwrap()
+            .is_none());
+
+        fs::create_dir_all(temp_dir.path().join(FRONTEND_DIR_NAME)).unwrap();
+        let path = generate_frontend_bindings(temp_dir.path(), &history, "testnet", &[])
+            .unwrap()
+            .unwrap();
+        let source = fs::read_to_string(path).unwrap();
+        assert!(source.contains("\"counterV1\": \"02new\""));
+        assert!(!source.contains("counterV2"));
+    }
+}
@@ -1,6 +1,5 @@
 use std::collections::HashSet;
-use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{mpsc, Arc};
 use std::thread;
@@ -12,10 +11,13 @@ use walkdir::WalkDir;
 /// Uses multithreading to speed up the process of searching nearby directories
 /// Searches up to 3 parent directories deep
 /// 
+/// # Arguments
+/// * `start` - Directory to start searching from
+/// 
 /// # Returns
 /// * `Option<PathBuf>` - Path to workspace root if found, None otherwise
-pub fn find_workspace_root() -> Option<PathBuf> {
-    let mut current_folder: PathBuf = env::current_dir().ok()?;
+pub fn find_workspace_root(start: &Path) -> Option<PathBuf> {
+    let mut current_folder: PathBuf = start.to_path_buf();
     for _ in 0..3 {
         let entries: Vec<_> = WalkDir::new(&current_folder)
             .max_depth(5)
@@ -135,7 +137,11 @@ pub fn find_files_with_extension(starting_path: &PathBuf, extension: &str) -> Ve
         for entry in WalkDir::new(&current_path).max_depth(5) {
             if let Ok(entry) = entry {
                 if entry.path().is_dir() {
-                    let files = entry.path().read_dir().unwrap().flatten();
+                    // directories we may not read are skipped instead of aborting the search
+                    let files = match entry.path().read_dir() {
+                        Ok(files) => files.flatten(),
+                        Err(_) => continue,
+                    };
                     for file in files {
                         let path = file.path();
                         if path.extension().unwrap_or_default() == extension {
@@ -172,7 +178,11 @@ pub fn find_paths_with_name(starting_path: &PathBuf, name: &str) -> Vec<PathBuf>
         for entry in WalkDir::new(&current_path).max_depth(5) {
             if let Ok(entry) = entry {
                 if entry.path().is_dir() {
-                    let files = entry.path().read_dir().unwrap().flatten();
+                    // directories we may not read are skipped instead of aborting the search
+                    let files = match entry.path().read_dir() {
+                        Ok(files) => files.flatten(),
+                        Err(_) => continue,
+                    };
                     for file in files {
                         let path = file.path();
                         if path.file_name().unwrap().to_str().unwrap().to_lowercase().contains(&name_lowercase) {
@@ -196,11 +206,13 @@ pub fn find_paths_with_name(starting_path: &PathBuf, name: &str) -> Vec<PathBuf>
 /// Gets all contract names from compiled contract files
 /// Searches for .pbc, .zkwa, and .wasm files in the release directory
 /// 
+/// # Arguments
+/// * `start` - Directory to start searching for the project from
+/// 
 /// # Returns
 /// * `Option<Vec<String>>` - Vector of unique contract names if found, None if no contracts exist
-pub fn get_all_contract_names() -> Option<Vec<String>> {
-    let root_path: PathBuf =
-        find_workspace_root().unwrap_or(PathBuf::from(env::current_dir().unwrap()));
+pub fn get_all_contract_names(start: &Path) -> Option<Vec<String>> {
+    let root_path: PathBuf = find_workspace_root(start).unwrap_or(start.to_path_buf());
     let path: Option<PathBuf> = find_dir(&root_path, "wasm32-unknown-unknown/release");
     if path.is_none() {
         return None;
@@ -245,11 +257,13 @@ pub fn get_all_contract_names() -> Option<Vec<String>> {
 /// Finds all .pk (private key) files in the workspace and parent directories
 /// Searches up to 5 parent directories deep if no files found in workspace
 /// 
+/// # Arguments
+/// * `start` - Directory to start searching for the project from
+/// 
 /// # Returns
 /// * `Vec<PathBuf>` - Vector of paths to .pk files, with duplicates removed
-pub fn get_pk_files() -> Vec<PathBuf> {
-    let root_path: PathBuf =
-        find_workspace_root().unwrap_or(PathBuf::from(env::current_dir().unwrap()));
+pub fn get_pk_files(start: &Path) -> Vec<PathBuf> {
+    let root_path: PathBuf = find_workspace_root(start).unwrap_or(start.to_path_buf());
     let mut pk_files_vec: Vec<PathBuf> = find_files_with_extension(&root_path, "pk");
     if pk_files_vec.is_empty() {
         let mut depth = 0;
@@ -308,23 +322,20 @@ mod tests {
     use crate::utils::utils::setup_test_environment;
     use std::time::Instant;
 
-    fn cleanup(original_dir: PathBuf) {
-        std::env::set_current_dir(original_dir).unwrap();
-    }
     #[test]
     fn test_find_workspace_root() {
-        let (_, temp_path, original_dir) = setup_test_environment();
-        // set the current directory to mock project
-        std::env::set_current_dir(temp_path.join("rust/contracts")).unwrap();
+        let (_temp_dir, temp_path) = setup_test_environment();
+        // search from inside the mock project
+        let start: PathBuf = temp_path.join("rust/contracts");
 
         let iterations = 1000;
-        let start = Instant::now();
+        let start_time = Instant::now();
 
         for _ in 0..iterations {
-            let _ = find_workspace_root();
+            let _ = find_workspace_root(&start);
         }
 
-        let duration = start.elapsed();
+        let duration = start_time.elapsed();
         let avg_duration = duration.as_micros() as f64 / iterations as f64;
 
         println!(
@@ -333,17 +344,15 @@ mod tests {
         );
         println!("Total time for {} iterations: {:.2?}", iterations, duration);
         assert_eq!(
-            find_workspace_root().unwrap().exists(),
+            find_workspace_root(&start).unwrap().exists(),
             true,
             "root path does not exist"
         );
-        cleanup(original_dir);
     }
 
     #[test]
     fn test_get_all_contract_names() {
-        let (_, temp_path, original_dir) = setup_test_environment();
-        let _ = std::env::set_current_dir(&temp_path);
+        let (_temp_dir, temp_path) = setup_test_environment();
         // create a mock pbc file
         let pbc_file = temp_path.join("target/wasm32-unknown-unknown/release/counter.pbc");
         let _ = std::fs::write(&pbc_file, "");
@@ -351,9 +360,8 @@ mod tests {
         let zkwa_file = temp_path.join("target/wasm32-unknown-unknown/release/counter.zkwa");
         let _ = std::fs::write(&zkwa_file, "");
         // create a mock wasm file
-        let contract_names = get_all_contract_names();
+        let contract_names = get_all_contract_names(&temp_path);
 
-        cleanup(original_dir);
         assert_eq!(
             contract_names.as_ref().unwrap().len(),
             1,
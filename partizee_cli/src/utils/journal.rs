@@ -0,0 +1,405 @@
+use crate::client::transaction::SentTransaction;
+use crate::commands::deploy::{find_deployments_dir, Deployment};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Directory inside `target/deployments` the run journals are written to
+pub const RUNS_DIR_NAME: &str = "runs";
+/// Lock file preventing concurrent deploys of the same project
+pub const DEPLOY_LOCK_FILE: &str = "deploy.lock";
+
+/// Outcome of a contract within a deploy run
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ContractStatus {
+    Pending,
+    Sent,
+    Succeeded,
+    Failed,
+}
+
+/// Journal entry of a single contract
+///
+/// # Fields
+/// * `name` - Name of the contract
+/// * `status` - Whether the contract has been deployed
+/// * `deployment` - Deployment record once the deploy transaction was sent
+/// * `destination_shard` - Shard the deploy transaction was routed to, if reported by the node
+/// * `error` - Error of the last failed attempt
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ContractRecord {
+    pub name: String,
+    pub status: ContractStatus,
+    #[serde(default)]
+    pub deployment: Option<Deployment>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destination_shard: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// Journal of a deploy run, rewritten after every contract
+///
+/// # Fields
+/// * `run_id` - Identifier passed to `partizee deploy --resume`
+/// * `network` - Network the run deploys to
+/// * `deployer` - Address of the deploying account
+/// * `started_at` - Unix timestamp the run was started at
+/// * `contracts` - Status of every contract in the run
+/// * `path` - File the journal is written to
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DeployJournal {
+    pub run_id: String,
+    pub network: String,
+    pub deployer: String,
+    pub started_at: String,
+    pub contracts: Vec<ContractRecord>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+/// Exclusive lock on deploying a project, released when dropped
+///
+/// # Fields
+/// * `path` - Path of the lock file
+#[derive(Debug)]
+pub struct DeployLock {
+    path: PathBuf,
+}
+
+impl DeployJournal {
+    /// Starts the journal of a new run
+    ///
+    /// # Arguments
+    /// * `deployments_dir` - The project's `target/deployments` directory
+    /// * `network` - Network the run deploys to
+    /// * `deployer` - Address of the deploying account
+    /// * `names` - Contracts in deployment order
+    ///
+    /// # Returns
+    /// * `Result<DeployJournal>` - Journal written to `runs/<run id>.json`
+    pub fn create(
+        deployments_dir: &Path,
+        network: &str,
+        deployer: &str,
+        names: &[String],
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        let run_id: String = now.as_millis().to_string();
+        let journal = Self {
+            path: deployments_dir
+                .join(RUNS_DIR_NAME)
+                .join(format!("{}.json", run_id)),
+            run_id,
+            network: network.to_string(),
+            deployer: deployer.to_string(),
+            started_at: now.as_secs().to_string(),
+            contracts: names
+                .iter()
+                .map(|name| ContractRecord {
+                    name: name.clone(),
+                    status: ContractStatus::Pending,
+                    deployment: None,
+                    destination_shard: None,
+                    error: None,
+                })
+                .collect(),
+        };
+        journal.save()?;
+        Ok(journal)
+    }
+
+    /// Loads the journal of an earlier run
+    ///
+    /// # Arguments
+    /// * `deployments_dir` - The project's `target/deployments` directory
+    /// * `run_id` - Identifier of the run
+    ///
+    /// # Returns
+    /// * `Result<DeployJournal>` - Journal of the run
+    pub fn load(deployments_dir: &Path, run_id: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let path: PathBuf = deployments_dir
+            .join(RUNS_DIR_NAME)
+            .join(format!("{}.json", run_id));
+        let contents: String = fs::read_to_string(&path)
+            .map_err(|_| format!("No deploy run {} found in {}", run_id, path.display()))?;
+        let mut journal: Self = serde_json::from_str(&contents)
+            .map_err(|e| format!("Invalid journal {}: {}", path.display(), e))?;
+        journal.path = path;
+        Ok(journal)
+    }
+
+    /// Loads the journal of an earlier run of the project
+    ///
+    /// # Arguments
+    /// * `project_root` - Root directory of project
+    /// * `run_id` - Identifier of the run
+    ///
+    /// # Returns
+    /// * `Result<DeployJournal>` - Journal of the run
+    pub fn find(project_root: &PathBuf, run_id: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let deployments_dir: PathBuf = find_deployments_dir(project_root)
+            .ok_or_else(|| format!("No deploy run {} found, nothing has been deployed", run_id))?;
+        Self::load(&deployments_dir, run_id)
+    }
+
+    /// Names of the contracts in the run, in deployment order
+    pub fn contract_names(&self) -> Vec<String> {
+        self.contracts.iter().map(|record| record.name.clone()).collect()
+    }
+
+    /// Deployment of a contract that already succeeded in this run
+    pub fn succeeded(&self, name: &str) -> Option<&Deployment> {
+        self.record(name)
+            .filter(|record| record.status == ContractStatus::Succeeded)
+            .and_then(|record| record.deployment.as_ref())
+    }
+
+    /// Deploy transaction sent for a contract whose outcome was not recorded as a success
+    /// 
+    /// # Arguments
+    /// * `name` - Name of the contract
+    /// 
+    /// # Returns
+    /// * `Option<(Deployment, SentTransaction)>` - Deployment journaled when the transaction was sent and the transaction
+    pub fn sent(&self, name: &str) -> Option<(Deployment, SentTransaction)> {
+        let record: &ContractRecord = self
+            .record(name)
+            .filter(|record| record.status != ContractStatus::Succeeded)?;
+        let deployment: &Deployment = record.deployment.as_ref()?;
+        Some((
+            deployment.clone(),
+            SentTransaction {
+                transaction_hash: deployment.transaction_hash.clone(),
+                destination_shard: record.destination_shard.clone(),
+            },
+        ))
+    }
+
+    /// Deployments of every contract that succeeded, in deployment order
+    pub fn deployments(&self) -> Vec<Deployment> {
+        self.contracts
+            .iter()
+            .filter(|record| record.status == ContractStatus::Succeeded)
+            .filter_map(|record| record.deployment.clone())
+            .collect()
+    }
+
+    /// Names of the contracts that failed
+    pub fn failed(&self) -> Vec<String> {
+        self.contracts
+            .iter()
+            .filter(|record| record.status == ContractStatus::Failed)
+            .map(|record| record.name.clone())
+            .collect()
+    }
+
+    /// Records a successful deployment and writes the journal
+    pub fn record_success(
+        &mut self,
+        name: &str,
+        deployment: Deployment,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let record: &mut ContractRecord = self.record_mut(name);
+        record.status = ContractStatus::Succeeded;
+        record.deployment = Some(deployment);
+        record.error = None;
+        self.save()
+    }
+
+    /// Records a sent deploy transaction before its outcome is known and writes the journal
+    pub fn record_sent(
+        &mut self,
+        name: &str,
+        deployment: Deployment,
+        destination_shard: Option<String>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let record: &mut ContractRecord = self.record_mut(name);
+        record.status = ContractStatus::Sent;
+        record.deployment = Some(deployment);
+        record.destination_shard = destination_shard;
+        record.error = None;
+        self.save()
+    }
+
+    /// Records a failed deployment and writes the journal
+    /// 
+    /// A deploy transaction sent by the attempt stays journaled, its outcome may still be unknown.
+    pub fn record_failure(&mut self, name: &str, error: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let record: &mut ContractRecord = self.record_mut(name);
+        record.status = ContractStatus::Failed;
+        record.error = Some(error.to_string());
+        self.save()
+    }
+
+    fn record(&self, name: &str) -> Option<&ContractRecord> {
+        self.contracts
+            .iter()
+            .find(|record| record.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Entry of a contract, added as pending if a resumed run did not include it yet
+    fn record_mut(&mut self, name: &str) -> &mut ContractRecord {
+        let index: usize = match self
+            .contracts
+            .iter()
+            .position(|record| record.name.eq_ignore_ascii_case(name))
+        {
+            Some(index) => index,
+            None => {
+                self.contracts.push(ContractRecord {
+                    name: name.to_string(),
+                    status: ContractStatus::Pending,
+                    deployment: None,
+                    destination_shard: None,
+                    error: None,
+                });
+                self.contracts.len() - 1
+            }
+        };
+        &mut self.contracts[index]
+    }
+
+    /// Writes the journal, replacing the previous version atomically
+    fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let temporary_path: PathBuf = self.path.with_extension("json.tmp");
+        fs::write(&temporary_path, serde_json::to_string_pretty(self)?)?;
+        fs::rename(&temporary_path, &self.path)
+            .map_err(|e| format!("Failed to write journal {}: {}", self.path.display(), e).into())
+    }
+}
+
+impl DeployLock {
+    /// Takes the deploy lock of a project
+    ///
+    /// # Arguments
+    /// * `deployments_dir` - The project's `target/deployments` directory
+    ///
+    /// # Returns
+    /// * `Result<DeployLock>` - Lock, Error if another deploy holds it or the lock file cannot be created
+    pub fn acquire(deployments_dir: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        fs::create_dir_all(deployments_dir)?;
+        let path: PathBuf = deployments_dir.join(DEPLOY_LOCK_FILE);
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .map_err(|e| -> Box<dyn std::error::Error> {
+                if e.kind() != ErrorKind::AlreadyExists {
+                    return format!("Failed to create {}: {}", path.display(), e).into();
+                }
+                let holder: String = fs::read_to_string(&path).unwrap_or_default();
+                format!(
+                    "Another deploy is running for this project ({}). If it crashed, remove {}",
+                    holder.trim(),
+                    path.display()
+                )
+                .into()
+            })?;
+        writeln!(file, "pid {}", std::process::id())?;
+        Ok(Self { path })
+    }
+}
+
+impl Drop for DeployLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn deployment(name: &str) -> Deployment {
+        Deployment {
+            name: name.to_string(),
+            network: "testnet".to_string(),
+            address: "02aa".to_string(),
+            transaction_hash: "bb".to_string(),
+            args: Vec::new(),
+            timestamp: "1700000000".to_string(),
+            code_version: None,
+            build_info: None,
+        }
+    }
+
+    #[test]
+    fn test_journal_is_written_after_each_contract() {
+        let temp_dir = TempDir::new().unwrap();
+        let names: Vec<String> = vec!["counterV1".into(), "counterV2".into()];
+        let mut journal = DeployJournal::create(temp_dir.path(), "testnet", "00d2", &names).unwrap();
+        journal.record_success("counterV1", deployment("counterV1")).unwrap();
+        journal.record_failure("counterV2", "out of gas").unwrap();
+
+        let loaded = DeployJournal::load(temp_dir.path(), &journal.run_id).unwrap();
+        assert_eq!(loaded.contract_names(), names);
+        assert_eq!(loaded.succeeded("counterv1").unwrap().address, "02aa");
+        assert!(loaded.succeeded("counterV2").is_none());
+        assert_eq!(loaded.failed(), vec!["counterV2"]);
+        assert_eq!(loaded.contracts[1].error.as_deref(), Some("out of gas"));
+        assert_eq!(loaded.deployments().len(), 1);
+        assert!(DeployJournal::load(temp_dir.path(), "missing").is_err());
+    }
+
+    #[test]
+    fn test_resumed_run_adds_new_contracts() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut journal =
+            DeployJournal::create(temp_dir.path(), "testnet", "00d2", &["a".to_string()]).unwrap();
+        journal.record_success("b", deployment("b")).unwrap();
+        assert_eq!(journal.contract_names(), vec!["a", "b"]);
+        assert_eq!(journal.contracts[0].status, ContractStatus::Pending);
+    }
+
+    #[test]
+    fn test_sent_transaction_survives_a_failed_wait() {
+        let temp_dir = TempDir::new().unwrap();
+        let names: Vec<String> = vec!["counterV1".into(), "counterV2".into()];
+        let mut journal = DeployJournal::create(temp_dir.path(), "testnet", "00d2", &names).unwrap();
+        assert!(journal.sent("counterV1").is_none());
+
+        journal
+            .record_sent("counterV1", deployment("counterV1"), Some("Shard1".to_string()))
+            .unwrap();
+        journal
+            .record_failure("counterV1", "Transaction bb was not executed on Shard1 within 180 seconds")
+            .unwrap();
+
+        // a resumed run finds the transaction and address of the interrupted attempt
+        let loaded = DeployJournal::load(temp_dir.path(), &journal.run_id).unwrap();
+        let (sent_deployment, sent) = loaded.sent("counterv1").unwrap();
+        assert_eq!(sent_deployment.address, "02aa");
+        assert_eq!(sent.transaction_hash, "bb");
+        assert_eq!(sent.destination_shard.as_deref(), Some("Shard1"));
+        assert_eq!(loaded.failed(), vec!["counterV1"]);
+        assert!(loaded.succeeded("counterV1").is_none());
+        assert!(loaded.deployments().is_empty());
+
+        // once it succeeded the transaction is no longer checked
+        journal.record_success("counterV1", deployment("counterV1")).unwrap();
+        assert!(journal.sent("counterV1").is_none());
+    }
+
+    #[test]
+    fn test_deploy_lock_is_exclusive() {
+        let temp_dir = TempDir::new().unwrap();
+        let lock = DeployLock::acquire(temp_dir.path()).unwrap();
+        let error = DeployLock::acquire(temp_dir.path()).unwrap_err().to_string();
+        assert!(
+            error.contains(&format!("pid {}", std::process::id())),
+            "unexpected error: {}",
+            error
+        );
+        drop(lock);
+        assert!(!temp_dir.path().join(DEPLOY_LOCK_FILE).exists());
+        assert!(DeployLock::acquire(temp_dir.path()).is_ok());
+    }
+}
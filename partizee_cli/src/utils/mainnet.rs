@@ -0,0 +1,334 @@
+use crate::client::transaction::TransactionClient;
+use crate::commands::user_profile::Profile;
+use crate::commands::verify::{load_verifications, VerificationRecord};
+use crate::utils::artifact::ContractArtifact;
+use crate::utils::constants::{MAINNET_CHAIN_ID, TESTNET_CHAIN_ID};
+use crate::utils::menus::confirm_mainnet_menu;
+use crate::utils::utils::{block_on, get_chain_id, get_rpc_endpoint, known_chain_id};
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use walkdir::{DirEntry, WalkDir};
+
+/// Directories that are not searched for private keys, they are generated or not part of the tree
+const SKIPPED_DIRS: [&str; 3] = [".git", "target", "node_modules"];
+
+/// Files larger than this are not searched for private keys
+const MAX_SCANNED_FILE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Checks that must pass before transactions are sent to a mainnet-class network
+///
+/// # Fields
+/// * `network` - Network the transactions are sent to
+/// * `confirmed` - Whether `--confirm-mainnet` was given, otherwise the user has to type the confirmation
+/// * `account` - Account signing the transactions
+/// * `estimated_gas` - Gas the transactions are estimated to need
+/// * `artifacts` - Name and artifact of every contract whose code is sent
+pub struct MainnetGuard<'a> {
+    pub network: &'a str,
+    pub confirmed: bool,
+    pub account: &'a Profile,
+    pub estimated_gas: i64,
+    pub artifacts: Vec<(&'a str, &'a ContractArtifact)>,
+}
+
+/// Whether a network is mainnet-class, i.e. its transactions are signed for the mainnet chain id
+///
+/// # Arguments
+/// * `network` - Network name
+///
+/// # Returns
+/// * `Result<bool>` - True for mainnet, an error if the chain id cannot be resolved
+pub fn is_mainnet(network: &str) -> Result<bool, Box<dyn std::error::Error>> {
+    Ok(get_chain_id(network)? == MAINNET_CHAIN_ID)
+}
+
+impl MainnetGuard<'_> {
+    /// Refuses to continue on mainnet unless every check passes and the user confirmed
+    ///
+    /// Does nothing on other networks.
+    ///
+    /// # Arguments
+    /// * `project_root` - Root directory of project
+    ///
+    /// # Returns
+    /// * `Result<()>` - Ok if the transactions may be sent
+    pub fn enforce(&self, project_root: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+        if !is_mainnet(self.network)? {
+            return Ok(());
+        }
+        let problems: Vec<String> = self.problems(project_root);
+        if !problems.is_empty() {
+            return Err(format!(
+                "Refusing to send transactions to {}:\n  - {}",
+                self.network,
+                problems.join("\n  - ")
+            )
+            .into());
+        }
+        if self.confirmed {
+            return Ok(());
+        }
+        if !std::io::stdin().is_terminal() {
+            return Err(format!(
+                "Refusing to send transactions to {} without confirmation, pass --confirm-mainnet",
+                self.network
+            )
+            .into());
+        }
+        match confirm_mainnet_menu(self.network, self.estimated_gas)? {
+            true => Ok(()),
+            false => Err("Mainnet transactions were not confirmed".into()),
+        }
+    }
+
+    /// Runs the checks that do not need the user
+    ///
+    /// # Arguments
+    /// * `project_root` - Root directory of project
+    ///
+    /// # Returns
+    /// * `Vec<String>` - One description per failed check, empty if all passed
+    pub fn problems(&self, project_root: &PathBuf) -> Vec<String> {
+        let mut problems: Vec<String> = Vec::new();
+
+        // the balance is fetched once more here, mainnet must not rely on an earlier warning
+        let balance: Result<i64, Box<dyn std::error::Error>> =
+            get_rpc_endpoint(self.network).and_then(|endpoint| {
+                let client: TransactionClient = TransactionClient::new(&endpoint, MAINNET_CHAIN_ID);
+                block_on(client.get_gas_balance(&self.account.address))?
+            });
+        match balance {
+            Ok(balance) if balance >= self.estimated_gas => {}
+            Ok(balance) => problems.push(format!(
+                "balance of {} gas does not cover the estimated {} gas",
+                balance, self.estimated_gas
+            )),
+            Err(e) => problems.push(format!("could not check the balance of {}: {}", self.account.address, e)),
+        }
+
+        problems.extend(unverified_artifacts(&load_verifications(project_root), &self.artifacts));
+        if let Some(exposure) = key_exposure(project_root, self.account) {
+            problems.push(exposure);
+        }
+        problems
+    }
+}
+
+/// Finds the artifacts that do not match a verified testnet deployment
+///
+/// # Arguments
+/// * `records` - Verifications recorded by `partizee verify`
+/// * `artifacts` - Name and artifact of every contract whose code is sent
+///
+/// # Returns
+/// * `Vec<String>` - One description per unverified artifact
+pub fn unverified_artifacts(
+    records: &[VerificationRecord],
+    artifacts: &[(&str, &ContractArtifact)],
+) -> Vec<String> {
+    artifacts
+        .iter()
+        .filter(|(_, artifact)| {
+            let contract_hash: String = hex::encode(artifact.contract_hash());
+            let abi_hash: String = hex::encode(artifact.abi_hash());
+            !records.iter().any(|record| {
+                known_chain_id(&record.network) == Some(TESTNET_CHAIN_ID)
+                    && record.contract_hash == contract_hash
+                    && record.abi_hash == abi_hash
+            })
+        })
+        .map(|(name, _)| {
+            format!(
+                "{} does not match a verified testnet deployment, deploy it to testnet and run partizee verify",
+                name
+            )
+        })
+        .collect()
+}
+
+/// Looks for the account's private key in the project, including its git history
+///
+/// # Arguments
+/// * `project_root` - Root directory of project
+/// * `account` - Account whose key is looked for
+///
+/// # Returns
+/// * `Option<String>` - Where the key was found, None if it was never written to the project
+pub fn key_exposure(project_root: &Path, account: &Profile) -> Option<String> {
+    let private_key: String = account.private_key.trim().to_lowercase();
+    if private_key.is_empty() {
+        return None;
+    }
+    let root: PathBuf = project_root.canonicalize().unwrap_or(project_root.to_path_buf());
+    if let Ok(pk_path) = account.path_to_pk.canonicalize() {
+        if pk_path.starts_with(&root) {
+            return Some(format!(
+                "the key file {} is inside the project, use a profile kept outside of it",
+                pk_path.display()
+            ));
+        }
+    }
+
+    let tree_file: Option<PathBuf> = WalkDir::new(&root)
+        .into_iter()
+        .filter_entry(|entry: &DirEntry| {
+            !(entry.file_type().is_dir() && SKIPPED_DIRS.iter().any(|dir| entry.file_name() == *dir))
+        })
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| {
+            entry
+                .metadata()
+                .map(|metadata| metadata.len() <= MAX_SCANNED_FILE_BYTES)
+                .unwrap_or(false)
+        })
+        .find(|entry| {
+            fs::read(entry.path())
+                .map(|contents| String::from_utf8_lossy(&contents).to_lowercase().contains(&private_key))
+                .unwrap_or(false)
+        })
+        .map(|entry| entry.into_path());
+    if let Some(file) = tree_file {
+        return Some(format!("the private key is written in {}", file.display()));
+    }
+
+    // the history is scanned here rather than with git log -S, which would put the key on the command line
+    let history: Output = Command::new("git")
+        .arg("-C")
+        .arg(&root)
+        .args(["log", "--all", "-p", "--no-color", "--format=commit %h"])
+        .output()
+        .ok()?;
+    if !history.status.success() {
+        return None;
+    }
+    let mut commit: &str = "";
+    for line in String::from_utf8_lossy(&history.stdout).lines() {
+        if let Some(hash) = line.strip_prefix("commit ") {
+            commit = hash;
+        } else if line.to_lowercase().contains(&private_key) {
+            return Some(format!(
+                "the private key was committed to the project's git history in {}, it has to be considered leaked",
+                commit
+            ));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::transaction::tests::start_mock_node;
+    use crate::utils::artifact::ContractKind;
+    use tempfile::tempdir;
+
+    fn profile(path_to_pk: PathBuf) -> Profile {
+        Profile {
+            network: "mainnet".to_string(),
+            address: "00d277aa1bf5702ab9fc690b04bd68b5a981095530".to_string(),
+            private_key: "ab".repeat(32),
+            path_to_pk,
+        }
+    }
+
+    #[test]
+    fn test_is_mainnet() {
+        assert!(is_mainnet("mainnet").unwrap());
+        assert!(!is_mainnet("testnet").unwrap());
+        assert!(is_mainnet("").is_err());
+    }
+
+    #[test]
+    fn test_unverified_artifacts() {
+        let artifact = ContractArtifact {
+            kind: ContractKind::Public,
+            contract_bytes: b"wasm".to_vec(),
+            abi_bytes: b"abi".to_vec(),
+        };
+        let record = |network: &str| VerificationRecord {
+            name: "counter".to_string(),
+            network: network.to_string(),
+            address: "02".repeat(21),
+            contract_hash: hex::encode(artifact.contract_hash()),
+            abi_hash: hex::encode(artifact.abi_hash()),
+            timestamp: "0".to_string(),
+        };
+        let artifacts: Vec<(&str, &ContractArtifact)> = vec![("counter", &artifact)];
+
+        assert!(unverified_artifacts(&[record("testnet")], &artifacts).is_empty());
+        // a mainnet verification does not count as testing the code
+        assert_eq!(unverified_artifacts(&[record("mainnet")], &artifacts).len(), 1);
+        let changed = VerificationRecord {
+            abi_hash: "00".repeat(32),
+            ..record("testnet")
+        };
+        assert!(unverified_artifacts(&[changed], &artifacts)[0].starts_with("counter does not match"));
+    }
+
+    #[test]
+    fn test_enforce_refuses_mainnet() {
+        let project = tempdir().unwrap();
+        let pk_path: PathBuf = project.path().join("account.pk");
+        fs::write(&pk_path, "ab".repeat(32)).unwrap();
+        let account: Profile = profile(pk_path);
+        let testnet = MainnetGuard {
+            network: "testnet",
+            confirmed: true,
+            account: &account,
+            estimated_gas: 10,
+            artifacts: Vec::new(),
+        };
+        assert!(testnet.enforce(&project.path().to_path_buf()).is_ok());
+
+        // a node reporting the mainnet chain id is mainnet-class
+        let (endpoint, handle) = start_mock_node(vec![
+            format!(r#"{{"chainId": "{}"}}"#, MAINNET_CHAIN_ID),
+            r#"{"nonce": 1, "account": {"accountCoins": [{"balance": "5"}]}}"#.to_string(),
+            r#"{"coins": {"coins": [{"symbol": "TEST_COIN", "conversionRate": {"numerator": 1, "denominator": 1}}]}}"#
+                .to_string(),
+        ]);
+        let mainnet = MainnetGuard {
+            network: &endpoint,
+            ..testnet
+        };
+        // confirming does not override a failed check, the caller must exit with the error
+        let refusal: String = mainnet
+            .enforce(&project.path().to_path_buf())
+            .unwrap_err()
+            .to_string();
+        assert!(refusal.starts_with(&format!("Refusing to send transactions to {}", endpoint)));
+        assert!(refusal.contains("balance of 5 gas does not cover the estimated 10 gas"), "unexpected refusal: {}", refusal);
+        assert!(refusal.contains("inside the project"));
+
+        let requests = handle.join().unwrap();
+        assert!(requests[0].starts_with("GET /chain "));
+        assert!(requests[1].starts_with(&format!("GET /chain/accounts/{}", account.address)));
+        assert!(requests[2].starts_with("GET /chain/accountPlugin/global"));
+    }
+
+    #[test]
+    fn test_key_exposure() {
+        let project = tempdir().unwrap();
+        let outside = tempdir().unwrap();
+        let pk_path: PathBuf = outside.path().join("account.pk");
+        fs::write(&pk_path, "ab".repeat(32)).unwrap();
+        fs::create_dir_all(project.path().join("target")).unwrap();
+        fs::write(project.path().join("target/account.pk"), "ab".repeat(32)).unwrap();
+        fs::write(project.path().join("README.md"), "no keys here").unwrap();
+        assert_eq!(key_exposure(project.path(), &profile(pk_path.clone())), None);
+
+        fs::write(project.path().join(".env"), format!("PRIVATE_KEY={}", "AB".repeat(32))).unwrap();
+        assert!(key_exposure(project.path(), &profile(pk_path))
+            .unwrap()
+            .contains(".env"));
+
+        let inside: PathBuf = project.path().join("account.pk");
+        fs::write(&inside, "ab".repeat(32)).unwrap();
+        assert!(key_exposure(project.path(), &profile(inside))
+            .unwrap()
+            .contains("inside the project"));
+    }
+}
@@ -0,0 +1,313 @@
+use crate::commands::deploy::DeployConfigs;
+use crate::utils::constants::DEFAULT_NETWORK;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File name of the deployment manifest in the project root
+pub const MANIFEST_FILE_NAME: &str = "partizee.toml";
+
+/// Declarative deployment manifest read from `partizee.toml`
+///
+/// # Fields
+/// * `deploy` - Defaults applied to every contract
+/// * `contracts` - Per contract deployment settings keyed by contract name
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeployManifest {
+    #[serde(default)]
+    pub deploy: ManifestDefaults,
+    #[serde(default)]
+    pub contracts: BTreeMap<String, ContractManifest>,
+}
+
+/// Defaults of the `[deploy]` table
+///
+/// # Fields
+/// * `network` - Network to deploy to
+/// * `profile` - Path to the deployer's .pk file, relative to the project root
+/// * `gas` - Gas limit for each deployment
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ManifestDefaults {
+    pub network: Option<String>,
+    pub profile: Option<PathBuf>,
+    pub gas: Option<i64>,
+}
+
+/// Settings of a `[contracts.<name>]` table
+///
+/// # Fields
+/// * `artifact` - Path to the .pbc, .wasm or .zkwa file, relative to the project root
+/// * `network` - Network override for this contract
+/// * `gas` - Gas limit override for this contract
+/// * `args` - Initialization arguments
+/// * `profile` - Deployer profile override for this contract
+/// * `budget` - Maximum artifact sizes, checked by `partizee compile`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContractManifest {
+    pub artifact: Option<PathBuf>,
+    pub network: Option<String>,
+    pub gas: Option<i64>,
+    #[serde(default)]
+    pub args: Vec<String>,
+    pub profile: Option<PathBuf>,
+    pub budget: Option<SizeBudget>,
+}
+
+/// Settings of a `[contracts.<name>.budget]` table, sizes in bytes
+///
+/// # Fields
+/// * `wasm` - Maximum size of the .wasm
+/// * `pbc` - Maximum size of the .pbc
+/// * `abi` - Maximum size of the .abi
+/// * `zkwa` - Maximum size of the .zkwa
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SizeBudget {
+    pub wasm: Option<u64>,
+    pub pbc: Option<u64>,
+    pub abi: Option<u64>,
+    pub zkwa: Option<u64>,
+}
+
+impl DeployManifest {
+    /// Parses a manifest from TOML
+    ///
+    /// # Arguments
+    /// * `contents` - TOML source
+    ///
+    /// # Returns
+    /// * `Result<DeployManifest>` - Parsed manifest
+    pub fn parse(contents: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        toml::from_str(contents).map_err(|e| format!("Invalid {}: {}", MANIFEST_FILE_NAME, e).into())
+    }
+
+    /// Loads the manifest of a project
+    ///
+    /// # Arguments
+    /// * `project_root` - Root of the partizee project
+    /// * `manifest_path` - Optional explicit manifest path, must exist if given
+    ///
+    /// # Returns
+    /// * `Result<Option<DeployManifest>>` - Manifest if one is present
+    pub fn find(
+        project_root: &Path,
+        manifest_path: Option<&PathBuf>,
+    ) -> Result<Option<Self>, Box<dyn std::error::Error>> {
+        let path: PathBuf = match manifest_path {
+            Some(path) => {
+                if !path.is_file() {
+                    return Err(format!("Manifest not found: {}", path.display()).into());
+                }
+                path.clone()
+            }
+            None => project_root.join(MANIFEST_FILE_NAME),
+        };
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let contents: String = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Ok(Some(Self::parse(&contents)?))
+    }
+
+    /// Names of all contracts declared in the manifest
+    pub fn contract_names(&self) -> Vec<String> {
+        self.contracts.keys().cloned().collect()
+    }
+
+    /// Builds deployment configurations from the manifest
+    ///
+    /// Values passed on the command line take precedence over the manifest.
+    /// Contracts sharing a network and profile are grouped into one configuration.
+    ///
+    /// # Arguments
+    /// * `overrides` - Configuration from command line flags
+    /// * `project_root` - Root the manifest paths are relative to
+    ///
+    /// # Returns
+    /// * `Vec<DeployConfigs>` - One configuration per network and profile
+    pub fn deploy_configs(&self, overrides: &DeployConfigs, project_root: &Path) -> Vec<DeployConfigs> {
+        let selected_names: Vec<String> = if overrides.contract_names.is_empty() {
+            self.contract_names()
+        } else {
+            overrides.contract_names.clone()
+        };
+        let cli_args: HashMap<String, Vec<String>> =
+            overrides.deployer_args.clone().unwrap_or_default();
+        let cli_gas: HashMap<String, i64> = overrides.gas_limits.clone().unwrap_or_default();
+
+        let mut groups: BTreeMap<(String, Option<PathBuf>), DeployConfigs> = BTreeMap::new();
+        for name in selected_names {
+            let contract: ContractManifest = self.contract(&name).cloned().unwrap_or_default();
+            let network: String = overrides
+                .network
+                .clone()
+                .or(contract.network.clone())
+                .or(self.deploy.network.clone())
+                .unwrap_or(DEFAULT_NETWORK.to_string());
+            let profile: Option<PathBuf> = overrides.path_to_pk.clone().or(contract
+                .profile
+                .as_ref()
+                .or(self.deploy.profile.as_ref())
+                .map(|profile| project_root.join(profile)));
+
+            let group: &mut DeployConfigs = groups
+                .entry((network.clone(), profile.clone()))
+                .or_insert_with(|| DeployConfigs {
+                    contract_names: Vec::new(),
+                    network: Some(network),
+                    deployer_args: Some(HashMap::new()),
+                    path_to_pk: profile,
+                    artifacts: Some(HashMap::new()),
+                    gas_limits: Some(HashMap::new()),
+                });
+
+            let name_lowercase: String = name.to_lowercase();
+            let args: Vec<String> = cli_args
+                .get(&name_lowercase)
+                .cloned()
+                .unwrap_or(contract.args.clone());
+            if !args.is_empty() {
+                group
+                    .deployer_args
+                    .get_or_insert_with(HashMap::new)
+                    .insert(name_lowercase.clone(), args);
+            }
+            if let Some(artifact) = contract.artifact.as_ref() {
+                group
+                    .artifacts
+                    .get_or_insert_with(HashMap::new)
+                    .insert(name_lowercase.clone(), project_root.join(artifact));
+            }
+            let gas: Option<i64> = cli_gas
+                .get(&name_lowercase)
+                .copied()
+                .or(contract.gas)
+                .or(self.deploy.gas);
+            if let Some(gas) = gas {
+                group
+                    .gas_limits
+                    .get_or_insert_with(HashMap::new)
+                    .insert(name_lowercase.clone(), gas);
+            }
+            group.contract_names.push(name);
+        }
+        groups.into_values().collect()
+    }
+
+    /// Size budget of a contract, if one is configured
+    pub fn budget(&self, name: &str) -> Option<&SizeBudget> {
+        self.contract(name)?.budget.as_ref()
+    }
+
+    /// Looks up a contract case insensitively
+    fn contract(&self, name: &str) -> Option<&ContractManifest> {
+        self.contracts
+            .iter()
+            .find(|(contract_name, _)| contract_name.eq_ignore_ascii_case(name))
+            .map(|(_, contract)| contract)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+[deploy]
+network = "testnet"
+profile = "00d277aa1bf5702ab9fc690b04bd68b5a981095530.pk"
+gas = 2000000
+
+[contracts.counterV1]
+artifact = "target/wasm32-unknown-unknown/release/counterV1.pbc"
+args = ["00d277aa1bf5702ab9fc690b04bd68b5a981095530"]
+
+[contracts.counterV2]
+network = "mainnet"
+gas = 5000000
+
+[contracts.counterV2.budget]
+wasm = 65536
+"#;
+
+    fn no_overrides() -> DeployConfigs {
+        DeployConfigs {
+            contract_names: Vec::new(),
+            network: None,
+            deployer_args: None,
+            path_to_pk: None,
+            artifacts: None,
+            gas_limits: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = DeployManifest::parse(MANIFEST).unwrap();
+        assert_eq!(manifest.deploy.network.as_deref(), Some("testnet"));
+        assert_eq!(manifest.contract_names(), vec!["counterV1", "counterV2"]);
+        assert_eq!(manifest.contracts["counterV1"].args.len(), 1);
+        assert_eq!(manifest.contracts["counterV2"].gas, Some(5000000));
+        assert_eq!(manifest.budget("counterv2").unwrap().wasm, Some(65536));
+        assert_eq!(manifest.budget("counterV1"), None);
+    }
+
+    #[test]
+    fn test_invalid_manifest() {
+        assert!(DeployManifest::parse("[contracts.counter]\ngas = \"lots\"").is_err());
+    }
+
+    #[test]
+    fn test_manifest_groups_by_network() {
+        let manifest = DeployManifest::parse(MANIFEST).unwrap();
+        let root = PathBuf::from("/project");
+        let configs = manifest.deploy_configs(&no_overrides(), &root);
+        assert_eq!(configs.len(), 2);
+
+        let mainnet = configs
+            .iter()
+            .find(|config| config.network.as_deref() == Some("mainnet"))
+            .unwrap();
+        assert_eq!(mainnet.contract_names, vec!["counterV2"]);
+        assert_eq!(mainnet.gas_limits.as_ref().unwrap()["counterv2"], 5000000);
+
+        let testnet = configs
+            .iter()
+            .find(|config| config.network.as_deref() == Some("testnet"))
+            .unwrap();
+        assert_eq!(
+            testnet.artifacts.as_ref().unwrap()["counterv1"],
+            root.join("target/wasm32-unknown-unknown/release/counterV1.pbc")
+        );
+        assert_eq!(testnet.gas_limits.as_ref().unwrap()["counterv1"], 2000000);
+        assert_eq!(
+            testnet.path_to_pk,
+            Some(root.join("00d277aa1bf5702ab9fc690b04bd68b5a981095530.pk"))
+        );
+    }
+
+    #[test]
+    fn test_cli_overrides_manifest() {
+        let manifest = DeployManifest::parse(MANIFEST).unwrap();
+        let mut overrides = no_overrides();
+        overrides.network = Some("testnet".to_string());
+        overrides.contract_names = vec!["counterV1".to_string()];
+        overrides.deployer_args = Some(HashMap::from([(
+            "counterv1".to_string(),
+            vec!["override".to_string()],
+        )]));
+        overrides.path_to_pk = Some(PathBuf::from("/keys/other.pk"));
+        overrides.gas_limits = Some(HashMap::from([("counterv1".to_string(), 900000)]));
+
+        let configs = manifest.deploy_configs(&overrides, Path::new("/project"));
+        assert_eq!(configs.len(), 1);
+        assert_eq!(configs[0].contract_names, vec!["counterV1"]);
+        assert_eq!(configs[0].path_to_pk, Some(PathBuf::from("/keys/other.pk")));
+        assert_eq!(
+            configs[0].deployer_args.as_ref().unwrap()["counterv1"],
+            vec!["override".to_string()]
+        );
+        assert_eq!(configs[0].gas_limits.as_ref().unwrap()["counterv1"], 900000);
+    }
+}
@@ -1,8 +1,11 @@
 use crate::commands::compile::ProjectCompiler;
-use crate::commands::deploy::DeployConfigs;
+use crate::commands::deploy::{find_contract_abi, DeployConfigs};
 use crate::commands::new::ProjectConfig;
 use crate::commands::user_profile::{Profile, ProfileConfig};
+use crate::utils::abi::{ContractAbi, FnAbi, TypeSpec};
+use crate::utils::context::ProjectContext;
 use crate::utils::fs_nav::{get_all_contract_names, get_pk_files};
+use crate::utils::placeholders::preview_placeholders;
 use crate::utils::utils::assert_partizee_project;
 use cliclack::{clear_screen, confirm, input, intro, outro, select, multiselect};
 use std::collections::HashMap;
@@ -15,12 +18,14 @@ pub const DELIM: &str = "==================================================";
 /// # Arguments
 /// * `name` - Optional project name. If None, user will be prompted
 /// * `output_dir` - Optional output directory. If None, user will be prompted
+/// * `zero_knowledge` - Scaffold a zero-knowledge contract. If false, user will be asked
 /// 
 /// # Returns
 /// * `Result<ProjectConfig>` - Project configuration with name and output directory
 pub fn new_project_menu(
     name: Option<String>,
     output_dir: Option<String>,
+    zero_knowledge: bool,
 ) -> Result<ProjectConfig, Box<dyn std::error::Error>> {
     clear_screen()?;
     intro(DELIM)?;
@@ -60,11 +65,16 @@ pub fn new_project_menu(
             None
         };
     };
+    let zero_knowledge: bool = zero_knowledge
+        || confirm("Would you like to include a zero-knowledge contract?")
+            .initial_value(false)
+            .interact()?;
     outro("Project configuration complete!")?;
 
     Ok(ProjectConfig {
         name: name,
         output_dir: output_directory,
+        zero_knowledge,
     })
 }
 
@@ -78,7 +88,7 @@ pub fn new_project_menu(
 pub fn compile_menu(
     config: ProjectCompiler,
 ) -> Result<ProjectCompiler, Box<dyn std::error::Error>> {
-    assert_partizee_project()?;
+    assert_partizee_project(&config.context)?;
     let mut build_args_vec: Vec<String> = Vec::new();
     let mut additional_args_vec: Vec<String> = Vec::new();
     let mut files_vec: Vec<String> = Vec::new();
@@ -106,11 +116,14 @@ pub fn compile_menu(
         .interact()?;
         if use_file_menu {
             loop {
+                // paths are taken from the project directory, like the compiler does
+                let context: ProjectContext = config.context.clone();
                 let file_to_compile: String =
                     input("Enter the path to a Cargo.toml of the contract to compile")
                         .placeholder("contracts/counter/Cargo.toml")
-                        .validate(|input: &String| {
-                            let path = std::path::Path::new(input);
+                        .validate(move |input: &String| {
+                            let path = context.resolve(std::path::Path::new(input));
+                            let path = path.as_path();
                             if !path.exists() {
                                 Err("File does not exist")
                             } else if !path.is_file() {
@@ -264,6 +277,10 @@ pub fn compile_menu(
         path: config.path,
         build_args,
         additional_args,
+        force: config.force,
+        jobs: config.jobs,
+        json: config.json,
+        context: config.context,
     })
 }
 
@@ -271,10 +288,14 @@ pub fn compile_menu(
 /// 
 /// # Arguments
 /// * `config` - Initial deployment configuration
+/// * `context` - Project the contracts are deployed from
 /// 
 /// # Returns
 /// * `Result<DeployConfigs>` - Updated deployment configuration with network, contracts and args
-pub fn deploy_menu(config: DeployConfigs) -> Result<DeployConfigs, Box<dyn std::error::Error>> {
+pub fn deploy_menu(
+    config: DeployConfigs,
+    context: &ProjectContext,
+) -> Result<DeployConfigs, Box<dyn std::error::Error>> {
     clear_screen()?;
     intro(DELIM)?;
     intro("Partizee - Deploy a Partisia Blockchain project")?;
@@ -302,7 +323,7 @@ pub fn deploy_menu(config: DeployConfigs) -> Result<DeployConfigs, Box<dyn std::
     intro(format!("Select contracts to deploy{:?}", config.contract_names))?;
     if config.contract_names.is_empty() {
 
-            custom_names = select_contracts_menu()?;
+            custom_names = select_contracts_menu(context)?;
        
     } else {
         custom_names = config.contract_names;
@@ -310,14 +331,14 @@ pub fn deploy_menu(config: DeployConfigs) -> Result<DeployConfigs, Box<dyn std::
 
     // get deployer args for each contract
     for name in custom_names.iter() {
-        let deployer_args: Vec<String> = get_deployer_args(name);
+        let deployer_args: Vec<String> = get_deployer_args(context, name);
         deployer_args_mapping.insert(name.to_lowercase(), deployer_args);
     }
 
     if config.path_to_pk.is_some() {
         path_to_pk = config.path_to_pk;
     } else {
-        let selected_account: PathBuf = select_pk_menu()?;
+        let selected_account: PathBuf = select_pk_menu(context)?;
         path_to_pk = Some(selected_account);
     }
     let deployer_args: Option<HashMap<String, Vec<String>>> = if deployer_args_mapping.len() > 0 {
@@ -331,19 +352,94 @@ pub fn deploy_menu(config: DeployConfigs) -> Result<DeployConfigs, Box<dyn std::
         contract_names: custom_names,
         deployer_args: deployer_args,
         path_to_pk: path_to_pk,
+        artifacts: config.artifacts,
+        gas_limits: config.gas_limits,
     })
 }
 
 /// Prompts user for deployer arguments for a specific contract
 /// 
+/// When the contract has been compiled, one value is requested per `#[init]` parameter
+/// and validated against the parameter's ABI type.
+/// 
 /// # Arguments
+/// * `context` - Project the contract is compiled in
 /// * `contract_name` - Name of the contract to get arguments for
 /// 
 /// # Returns
 /// * `Vec<String>` - List of deployer arguments
-fn get_deployer_args(contract_name: &str) -> Vec<String> {
+fn get_deployer_args(context: &ProjectContext, contract_name: &str) -> Vec<String> {
+    match find_contract_abi(context, contract_name) {
+        Some(abi) => get_typed_deployer_args(contract_name, &abi),
+        None => get_untyped_deployer_args(contract_name),
+    }
+}
+
+/// Prompts for each `#[init]` parameter by name and type
+/// 
+/// # Arguments
+/// * `contract_name` - Name of the contract to get arguments for
+/// * `abi` - ABI of the contract
+/// 
+/// # Returns
+/// * `Vec<String>` - One validated argument per init parameter
+fn get_typed_deployer_args(contract_name: &str, abi: &ContractAbi) -> Vec<String> {
+    let init: FnAbi = match abi.init_function() {
+        Ok(init) => init.clone(),
+        Err(_) => return Vec::new(),
+    };
+    if !init.arguments.is_empty() {
+        intro(format!("Initialization arguments for {}", contract_name)).unwrap();
+    }
     let mut deployer_args_vec: Vec<String> = Vec::new();
-    let add_deployer_args = confirm(format!("Does the {} contract need deployer arguments? \n Please enter them one at a time in the order needed for initialization)", contract_name))
+    for argument in init.arguments {
+        let type_name: String = abi.type_name(&argument.type_spec);
+        let validation_abi: ContractAbi = abi.clone();
+        let deployer_arg: String = input(format!("Enter {} ({})", argument.name, type_name))
+            .placeholder(argument_placeholder(&argument.type_spec))
+            .validate(move |input: &String| {
+                preview_placeholders(&[input.trim().to_string()])
+                    .and_then(|preview| validation_abi.validate_arg(&argument.type_spec, &preview[0]))
+                    .map_err(|e| e.to_string())
+            })
+            .interact()
+            .unwrap();
+        deployer_args_vec.push(deployer_arg.trim().to_string());
+    }
+    deployer_args_vec
+}
+
+/// Example input shown for a parameter type
+fn argument_placeholder(type_spec: &TypeSpec) -> &'static str {
+    match type_spec {
+        TypeSpec::Address => "00d277aa1bf5702ab9fc690b04bd68b5a981095530 or ${profile.address}",
+        TypeSpec::Bool => "true",
+        TypeSpec::String => "text",
+        TypeSpec::Vec(element) if **element == TypeSpec::U8 => "hex bytes, e.g. 0a0b",
+        TypeSpec::Option(_) => "null or a value",
+        TypeSpec::Named(_) | TypeSpec::Vec(_) | TypeSpec::Set(_) | TypeSpec::SizedArray(_, _) => {
+            "JSON value"
+        }
+        TypeSpec::Hash
+        | TypeSpec::PublicKey
+        | TypeSpec::Signature
+        | TypeSpec::BlsPublicKey
+        | TypeSpec::BlsSignature
+        | TypeSpec::SizedByteArray(_) => "hex bytes",
+        _ => "0",
+    }
+}
+
+/// Prompts for free-form arguments when no ABI is available
+/// 
+/// # Arguments
+/// * `contract_name` - Name of the contract to get arguments for
+/// 
+/// # Returns
+/// * `Vec<String>` - List of deployer arguments
+fn get_untyped_deployer_args(contract_name: &str) -> Vec<String> {
+    let mut deployer_args_vec: Vec<String> = Vec::new();
+    let add_deployer_args = confirm(format!("No ABI found for {}, compile it to get typed prompts. Does the contract need deployer arguments? \n Please enter them one at a time in the order needed for initialization)", contract_name))
         .initial_value(false)
         .interact().unwrap();
 
@@ -395,11 +491,35 @@ pub fn force_new_wallet_menu() -> Result<bool, Box<dyn std::error::Error>> {
     return Ok(force_create.unwrap());
 }
 
+/// Interactive menu asking the user to type the network name before mainnet transactions are sent
+/// 
+/// # Arguments
+/// * `network` - Network the transactions are sent to
+/// * `estimated_gas` - Gas the transactions are estimated to need
+/// 
+/// # Returns
+/// * `Result<bool>` - True if the user typed the network name
+pub fn confirm_mainnet_menu(network: &str, estimated_gas: i64) -> Result<bool, Box<dyn std::error::Error>> {
+    intro(DELIM)?;
+    intro(format!("Partizee - Sending transactions to {}", network))?;
+    intro(DELIM)?;
+    let typed: String = input(format!(
+        "This spends up to {} gas of real funds. Type {} to continue",
+        estimated_gas, network
+    ))
+    .placeholder(network)
+    .interact()?;
+    Ok(typed.trim() == network)
+}
+
 /// Interactive menu for selecting contracts to deploy
 /// 
+/// # Arguments
+/// * `context` - Project the contracts are compiled in
+/// 
 /// # Returns
 /// * `Result<Vec<String>>` - List of selected contract names
-pub fn select_contracts_menu() -> Result<Vec<String>, Box<dyn std::error::Error>> {
+pub fn select_contracts_menu(context: &ProjectContext) -> Result<Vec<String>, Box<dyn std::error::Error>> {
     clear_screen()?;
     intro(DELIM)?;
     intro("Partizee - Select contracts")?;
@@ -409,7 +529,7 @@ pub fn select_contracts_menu() -> Result<Vec<String>, Box<dyn std::error::Error>
     )
     .initial_value(false)
     .interact();
-    let all_contract_names: Vec<String> = get_all_contract_names().unwrap_or(Vec::new());
+    let all_contract_names: Vec<String> = get_all_contract_names(&context.project_dir).unwrap_or(Vec::new());
     if select_contracts.unwrap_or(false) {
         
         let mut all_contracts_tuples: Vec<(String, String, String)> = all_contract_names
@@ -461,9 +581,12 @@ pub fn create_new_wallet_menu() -> Result<String, Box<dyn std::error::Error>> {
 
 /// Interactive menu for creating a custom profile
 /// 
+/// # Arguments
+/// * `context` - Project new key files are written to
+/// 
 /// # Returns
 /// * `Result<Profile>` - New profile with custom settings
-pub fn custom_profile_menu() -> Result<Profile, Box<dyn std::error::Error>> {
+pub fn custom_profile_menu(context: &ProjectContext) -> Result<Profile, Box<dyn std::error::Error>> {
     clear_screen()?;
     intro(DELIM)?;
     intro("Partizee - Create a new account with custom settings")?;
@@ -512,7 +635,7 @@ pub fn custom_profile_menu() -> Result<Profile, Box<dyn std::error::Error>> {
             path_to_pk: pathbuf_to_pk,
         };
         // check if private key is valid for the address
-        let account: Profile = Profile::new(account_config).unwrap();
+        let account: Profile = Profile::new(account_config, context).unwrap();
         return Ok(account);
     }
     let account_config: ProfileConfig = ProfileConfig {
@@ -521,7 +644,7 @@ pub fn custom_profile_menu() -> Result<Profile, Box<dyn std::error::Error>> {
         private_key: account_private_key_input,
         path_to_pk: pathbuf_to_pk,
     };
-    let account: Profile = Profile::new(account_config).unwrap();
+    let account: Profile = Profile::new(account_config, context).unwrap();
     Ok(account)
 }
 
@@ -618,9 +741,12 @@ fn input_optional(
 
 /// Interactive menu for creating a new profile
 /// 
+/// # Arguments
+/// * `context` - Project new key files are written to
+/// 
 /// # Returns
 /// * `Result<Profile>` - New profile with selected settings
-pub fn create_new_profile_menu() -> Result<Profile, Box<dyn std::error::Error>> {
+pub fn create_new_profile_menu(context: &ProjectContext) -> Result<Profile, Box<dyn std::error::Error>> {
     clear_screen()?;
     intro(DELIM)?;
     intro("Partizee - Create a new account")?;
@@ -639,10 +765,10 @@ pub fn create_new_profile_menu() -> Result<Profile, Box<dyn std::error::Error>>
     match create_new {
         Ok(account_option) => match account_option {
             "default testnet" => {
-                return Ok(Profile::default());
+                return Ok(Profile::for_project(context));
             }
             "custom" => {
-                return custom_profile_menu();
+                return custom_profile_menu(context);
             }
             "cancel" => {
                 panic!("Cancelling account creation");
@@ -659,9 +785,12 @@ pub fn create_new_profile_menu() -> Result<Profile, Box<dyn std::error::Error>>
 
 /// Interactive menu for selecting a private key file
 /// 
+/// # Arguments
+/// * `context` - Project the .pk files are searched from
+/// 
 /// # Returns
 /// * `Result<PathBuf>` - Path to selected private key file
-pub fn select_pk_menu() -> Result<PathBuf, Box<dyn std::error::Error>> {
+pub fn select_pk_menu(context: &ProjectContext) -> Result<PathBuf, Box<dyn std::error::Error>> {
     // clear screen
     clear_screen()?;
     intro(DELIM)?;
@@ -674,7 +803,7 @@ pub fn select_pk_menu() -> Result<PathBuf, Box<dyn std::error::Error>> {
             .interact();
     if select_account.unwrap() {
         // open menu to select an account
-        let account_files: Vec<PathBuf> = get_pk_files();
+        let account_files: Vec<PathBuf> = get_pk_files(&context.project_dir);
         if account_files.is_empty() {
             return Err("No account files found".into());
         } else {
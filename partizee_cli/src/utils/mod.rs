@@ -1,7 +1,20 @@
+pub mod abi;
+pub mod artifact;
+pub mod build_cache;
+pub mod build_info;
+pub mod build_report;
 pub mod clap_cli;
 pub mod constants;
+pub mod context;
 pub mod cryptography;
+pub mod diagnostics;
+pub mod frontend;
 pub mod fs_nav;
+pub mod journal;
+pub mod mainnet;
+pub mod manifest;
 pub mod menus;
 pub mod pbc_commands;
+pub mod placeholders;
+pub mod state;
 pub mod utils;
@@ -1,6 +1,7 @@
 use crate::utils::fs_nav::id_pbc_path;
 use crate::utils::menus::force_new_wallet_menu;
 use crate::utils::utils::{print_error, print_output};
+use std::path::Path;
 use std::process::Command;
 
 pub fn pbc_create_new_account(network: &str) -> Result<(), Box<dyn std::error::Error>> {
@@ -62,3 +63,31 @@ pub fn pbc_create_new_wallet(network: &str) -> Result<String, Box<dyn std::error
         return Err("I didn't expect to get here.  I mean, the id_pbc file exists or it doesnt.  There shouldn't be a third option.".into());
     }
 }
+
+/// Generates TypeScript bindings for a contract ABI with `cargo pbc abi codegen`
+///
+/// # Arguments
+/// * `abi_path` - Path to the .abi file
+/// * `output_path` - Path of the .ts file to write
+///
+/// # Returns
+/// * `Result<()>` - Ok if the bindings were written
+pub fn pbc_abi_codegen(abi_path: &Path, output_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let output = Command::new("cargo")
+        .arg("pbc")
+        .arg("abi")
+        .arg("codegen")
+        .arg("--ts")
+        .arg(abi_path)
+        .arg(output_path)
+        .output()
+        .map_err(|e| format!("Failed to run cargo pbc abi codegen: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "cargo pbc abi codegen failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+    Ok(())
+}
@@ -0,0 +1,375 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Reference name that resolves to the deployer's own address
+pub const PROFILE_REFERENCE: &str = "profile";
+/// Stand-in address used when validating arguments that contain placeholders
+pub const PREVIEW_ADDRESS: &str = "000000000000000000000000000000000000000000";
+
+/// Piece of a deployment argument, either literal text or a `${<name>.address}` placeholder
+#[derive(Debug, Clone, PartialEq)]
+enum ArgToken<'a> {
+    Text(&'a str),
+    Reference { placeholder: &'a str, name: String },
+}
+
+/// Splits an argument into literal text and `${<name>.address}` placeholders
+///
+/// # Arguments
+/// * `arg` - Deployment argument
+///
+/// # Returns
+/// * `Result<Vec<ArgToken>>` - Tokens in order of appearance, Error on a malformed placeholder
+fn tokenize(arg: &str) -> Result<Vec<ArgToken<'_>>, Box<dyn std::error::Error>> {
+    let mut tokens: Vec<ArgToken> = Vec::new();
+    let mut rest: &str = arg;
+    while let Some(start) = rest.find("${") {
+        if start > 0 {
+            tokens.push(ArgToken::Text(&rest[..start]));
+        }
+        let after_start: &str = &rest[start + 2..];
+        let end: usize = after_start
+            .find('}')
+            .ok_or_else(|| format!("Unterminated placeholder in argument: {}", arg))?;
+        let placeholder: &str = &after_start[..end];
+        let name: &str = placeholder.strip_suffix(".address").ok_or_else(|| {
+            format!(
+                "Unsupported placeholder ${{{}}}, expected ${{<contract>.address}}",
+                placeholder
+            )
+        })?;
+        if name.is_empty() {
+            return Err(format!("Empty placeholder in argument: {}", arg).into());
+        }
+        tokens.push(ArgToken::Reference {
+            placeholder,
+            name: name.to_lowercase(),
+        });
+        rest = &after_start[end + 1..];
+    }
+    if !rest.is_empty() {
+        tokens.push(ArgToken::Text(rest));
+    }
+    Ok(tokens)
+}
+
+/// Extracts the names referenced by `${<name>.address}` placeholders in an argument
+///
+/// # Arguments
+/// * `arg` - Deployment argument
+///
+/// # Returns
+/// * `Result<Vec<String>>` - Lowercase referenced names in order of appearance
+pub fn find_references(arg: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    Ok(tokenize(arg)?
+        .into_iter()
+        .filter_map(|token| match token {
+            ArgToken::Reference { name, .. } => Some(name),
+            ArgToken::Text(_) => None,
+        })
+        .collect())
+}
+
+/// Replaces every placeholder in the arguments with the referenced address
+///
+/// # Arguments
+/// * `args` - Deployment arguments
+/// * `addresses` - Map of lowercase names to addresses, including `profile`
+///
+/// # Returns
+/// * `Result<Vec<String>>` - Arguments with placeholders substituted
+pub fn substitute_placeholders(
+    args: &[String],
+    addresses: &HashMap<String, String>,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let mut substituted: Vec<String> = Vec::new();
+    for arg in args {
+        let mut value: String = String::new();
+        for token in tokenize(arg)? {
+            match token {
+                ArgToken::Text(text) => value.push_str(text),
+                ArgToken::Reference { placeholder, name } => {
+                    let address: &String = addresses.get(&name).ok_or_else(|| {
+                        format!("No address available for placeholder ${{{}}}", placeholder)
+                    })?;
+                    value.push_str(address);
+                }
+            }
+        }
+        substituted.push(value);
+    }
+    Ok(substituted)
+}
+
+/// Replaces every placeholder with a stand-in account address
+///
+/// Used to validate arguments before the referenced contracts are deployed.
+///
+/// # Arguments
+/// * `args` - Deployment arguments
+///
+/// # Returns
+/// * `Result<Vec<String>>` - Arguments with placeholders replaced by the stand-in address
+pub fn preview_placeholders(args: &[String]) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let mut addresses: HashMap<String, String> = HashMap::new();
+    for arg in args {
+        for reference in find_references(arg)? {
+            addresses.insert(reference, PREVIEW_ADDRESS.to_string());
+        }
+    }
+    substitute_placeholders(args, &addresses)
+}
+
+/// Orders contracts so that every contract is deployed after the contracts it references
+///
+/// References to `profile` or to contracts outside `names` do not constrain the order.
+///
+/// # Arguments
+/// * `names` - Lowercase names of the contracts to deploy
+/// * `args` - Map of lowercase contract names to their deployment arguments
+///
+/// # Returns
+/// * `Result<Vec<String>>` - Names in deployment order, Error if the references form a cycle
+pub fn deployment_order(
+    names: &[String],
+    args: &HashMap<String, Vec<String>>,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let name_set: BTreeSet<String> = names.iter().cloned().collect();
+    // contract -> contracts it depends on
+    let mut dependencies: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for name in &name_set {
+        let mut depends_on: BTreeSet<String> = BTreeSet::new();
+        for arg in args.get(name).map(|args| args.as_slice()).unwrap_or(&[]) {
+            for reference in find_references(arg)? {
+                if reference == *name {
+                    return Err(format!("Contract {} references its own address", name).into());
+                }
+                if reference != PROFILE_REFERENCE && name_set.contains(&reference) {
+                    depends_on.insert(reference);
+                }
+            }
+        }
+        dependencies.insert(name.clone(), depends_on);
+    }
+
+    topological_order(dependencies).map_err(|cycle| {
+        format!(
+            "Deploy arguments contain a reference cycle between: {}",
+            cycle.join(", ")
+        )
+        .into()
+    })
+}
+
+/// Contracts a deploy group deploys to one network from one profile
+///
+/// # Fields
+/// * `network` - Network the group deploys to
+/// * `names` - Lowercase names of the group's contracts
+/// * `args` - Map of lowercase contract names to their deployment arguments
+#[derive(Debug, Clone)]
+pub struct DeployGroup<'a> {
+    pub network: &'a str,
+    pub names: Vec<String>,
+    pub args: &'a HashMap<String, Vec<String>>,
+}
+
+/// Orders deploy groups so that every group is deployed after the groups whose contracts it references
+///
+/// A reference only constrains the order when the referenced contract is deployed by
+/// another group to the same network.
+///
+/// # Arguments
+/// * `groups` - Deploy groups of one run
+///
+/// # Returns
+/// * `Result<Vec<usize>>` - Indexes of the groups in deployment order, Error if groups reference each other
+pub fn group_order(groups: &[DeployGroup]) -> Result<Vec<usize>, Box<dyn std::error::Error>> {
+    // group -> groups it depends on
+    let mut dependencies: BTreeMap<usize, BTreeSet<usize>> = BTreeMap::new();
+    for (index, group) in groups.iter().enumerate() {
+        let mut depends_on: BTreeSet<usize> = BTreeSet::new();
+        for name in &group.names {
+            for arg in group.args.get(name).map(|args| args.as_slice()).unwrap_or(&[]) {
+                for reference in find_references(arg)? {
+                    if reference == PROFILE_REFERENCE || group.names.contains(&reference) {
+                        continue;
+                    }
+                    depends_on.extend(groups.iter().enumerate().filter_map(|(other, other_group)| {
+                        (other_group.network == group.network
+                            && other_group.names.contains(&reference))
+                        .then_some(other)
+                    }));
+                }
+            }
+        }
+        dependencies.insert(index, depends_on);
+    }
+
+    topological_order(dependencies).map_err(|cycle| {
+        let contracts: Vec<String> = cycle
+            .iter()
+            .map(|index| format!("[{}] on {}", groups[*index].names.join(", "), groups[*index].network))
+            .collect();
+        format!(
+            "Deploy groups reference each other's contracts: {}",
+            contracts.join(" and ")
+        )
+        .into()
+    })
+}
+
+/// Orders keys so that every key comes after the keys it depends on
+///
+/// # Arguments
+/// * `dependencies` - Map of every key to the keys it depends on
+///
+/// # Returns
+/// * `Result<Vec<K>, Vec<K>>` - Keys in dependency order, Error with the keys left in a cycle
+fn topological_order<K: Ord + Clone>(
+    mut dependencies: BTreeMap<K, BTreeSet<K>>,
+) -> Result<Vec<K>, Vec<K>> {
+    let mut order: Vec<K> = Vec::new();
+    while !dependencies.is_empty() {
+        let ready: Vec<K> = dependencies
+            .iter()
+            .filter(|(_, depends_on)| depends_on.is_empty())
+            .map(|(key, _)| key.clone())
+            .collect();
+        if ready.is_empty() {
+            return Err(dependencies.keys().cloned().collect());
+        }
+        for key in ready {
+            dependencies.remove(&key);
+            for depends_on in dependencies.values_mut() {
+                depends_on.remove(&key);
+            }
+            order.push(key);
+        }
+    }
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args_map(entries: &[(&str, &[&str])]) -> HashMap<String, Vec<String>> {
+        entries
+            .iter()
+            .map(|(name, args)| {
+                (
+                    name.to_string(),
+                    args.iter().map(|arg| arg.to_string()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_find_references() {
+        assert_eq!(
+            find_references("${counterV1.address}").unwrap(),
+            vec!["counterv1".to_string()]
+        );
+        assert_eq!(
+            find_references("x${profile.address}y${a.address}").unwrap(),
+            vec!["profile".to_string(), "a".to_string()]
+        );
+        assert!(find_references("plain").unwrap().is_empty());
+        assert!(find_references("${counterV1}").is_err());
+        assert!(find_references("${counterV1.address").is_err());
+    }
+
+    #[test]
+    fn test_substitute_placeholders() {
+        let addresses: HashMap<String, String> = HashMap::from([
+            ("counterv1".to_string(), "02aa".to_string()),
+            ("profile".to_string(), "00bb".to_string()),
+        ]);
+        let args: Vec<String> = vec![
+            "${counterV1.address}".to_string(),
+            "${profile.address}".to_string(),
+            "5".to_string(),
+        ];
+        assert_eq!(
+            substitute_placeholders(&args, &addresses).unwrap(),
+            vec!["02aa", "00bb", "5"]
+        );
+        assert!(substitute_placeholders(&["${missing.address}".to_string()], &addresses).is_err());
+        assert!(substitute_placeholders(&["${counterV1}".to_string()], &addresses).is_err());
+        assert_eq!(
+            substitute_placeholders(&["0x${counterV1.address}!".to_string()], &addresses).unwrap(),
+            vec!["0x02aa!"]
+        );
+    }
+
+    #[test]
+    fn test_preview_placeholders() {
+        let args: Vec<String> = vec!["${counterV1.address}".to_string(), "5".to_string()];
+        assert_eq!(
+            preview_placeholders(&args).unwrap(),
+            vec![PREVIEW_ADDRESS, "5"]
+        );
+        assert!(preview_placeholders(&["${counterV1}".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_deployment_order() {
+        let names: Vec<String> = vec!["counterv3".into(), "counterv2".into(), "counterv1".into()];
+        let args = args_map(&[
+            ("counterv3", &["${counterV2.address}"]),
+            ("counterv2", &["${counterV1.address}", "${profile.address}"]),
+            ("counterv1", &["${profile.address}"]),
+        ]);
+        assert_eq!(
+            deployment_order(&names, &args).unwrap(),
+            vec!["counterv1", "counterv2", "counterv3"]
+        );
+    }
+
+    #[test]
+    fn test_deployment_order_rejects_cycles() {
+        let names: Vec<String> = vec!["a".into(), "b".into(), "c".into()];
+        let args = args_map(&[
+            ("a", &["${b.address}"]),
+            ("b", &["${a.address}"]),
+        ]);
+        let error = deployment_order(&names, &args).unwrap_err().to_string();
+        assert!(error.contains("a, b"), "unexpected error: {}", error);
+
+        let args = args_map(&[("a", &["${a.address}"])]);
+        assert!(deployment_order(&names, &args).is_err());
+    }
+
+    #[test]
+    fn test_group_order_spans_profiles_on_one_network() {
+        // two profiles deploy to testnet, the first group references the second group's contract
+        let token_args = args_map(&[("swap", &["${token.address}", "${profile.address}"])]);
+        let no_args: HashMap<String, Vec<String>> = HashMap::new();
+        let groups: Vec<DeployGroup> = vec![
+            DeployGroup { network: "testnet", names: vec!["swap".into()], args: &token_args },
+            DeployGroup { network: "testnet", names: vec!["token".into()], args: &no_args },
+            DeployGroup { network: "mainnet", names: vec!["token".into()], args: &no_args },
+        ];
+        assert_eq!(group_order(&groups).unwrap(), vec![1, 2, 0]);
+
+        // a contract of the same name on another network does not order the groups
+        let groups: Vec<DeployGroup> = vec![
+            DeployGroup { network: "mainnet", names: vec!["swap".into()], args: &token_args },
+            DeployGroup { network: "testnet", names: vec!["token".into()], args: &no_args },
+        ];
+        assert_eq!(group_order(&groups).unwrap(), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_group_order_rejects_cycles() {
+        let first_args = args_map(&[("a", &["${b.address}"])]);
+        let second_args = args_map(&[("b", &["${a.address}"])]);
+        let groups: Vec<DeployGroup> = vec![
+            DeployGroup { network: "testnet", names: vec!["a".into()], args: &first_args },
+            DeployGroup { network: "testnet", names: vec!["b".into()], args: &second_args },
+        ];
+        let error = group_order(&groups).unwrap_err().to_string();
+        assert!(error.contains("[a] on testnet and [b] on testnet"), "unexpected error: {}", error);
+    }
+}
@@ -0,0 +1,272 @@
+use crate::utils::abi::{ContractAbi, NamedTypeSpec, TypeSpec};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+/// Serialized key and value pairs of each AVL tree, keyed by tree id
+pub type AvlTrees = HashMap<i32, Vec<(Vec<u8>, Vec<u8>)>>;
+
+/// Cursor over the little-endian state serialization
+struct StateReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> StateReader<'a> {
+    fn read_bytes(&mut self, length: usize) -> Result<&'a [u8], Box<dyn std::error::Error>> {
+        if self.position + length > self.bytes.len() {
+            return Err(format!("State ended unexpectedly at byte {}", self.position).into());
+        }
+        let bytes: &'a [u8] = &self.bytes[self.position..self.position + length];
+        self.position += length;
+        Ok(bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], Box<dyn std::error::Error>> {
+        Ok(self.read_bytes(N)?.try_into()?)
+    }
+
+    fn read_length(&mut self) -> Result<usize, Box<dyn std::error::Error>> {
+        Ok(u32::from_le_bytes(self.read_array()?) as usize)
+    }
+
+    fn read_hex(&mut self, length: usize) -> Result<Value, Box<dyn std::error::Error>> {
+        Ok(Value::String(hex::encode(self.read_bytes(length)?)))
+    }
+}
+
+/// Decodes serialized contract state with the contract's ABI
+///
+/// Integers up to 64 bits are JSON numbers, larger integers are strings. Addresses,
+/// hashes and byte vectors are hex strings and enums are written as `{"Variant": {..}}`.
+///
+/// # Arguments
+/// * `abi` - ABI of the contract
+/// * `state` - Serialized state
+/// * `avl_trees` - Contents of the contract's AVL trees
+///
+/// # Returns
+/// * `Result<Value>` - State as JSON
+pub fn decode_state(
+    abi: &ContractAbi,
+    state: &[u8],
+    avl_trees: &AvlTrees,
+) -> Result<Value, Box<dyn std::error::Error>> {
+    let mut reader = StateReader {
+        bytes: state,
+        position: 0,
+    };
+    let value: Value = decode_value(abi, &abi.state_type, &mut reader, avl_trees)?;
+    if reader.position != state.len() {
+        return Err(format!(
+            "State has {} bytes left after decoding, the ABI does not match the deployed contract",
+            state.len() - reader.position
+        )
+        .into());
+    }
+    Ok(value)
+}
+
+fn decode_value(
+    abi: &ContractAbi,
+    type_spec: &TypeSpec,
+    reader: &mut StateReader,
+    avl_trees: &AvlTrees,
+) -> Result<Value, Box<dyn std::error::Error>> {
+    let value: Value = match type_spec {
+        TypeSpec::U8 => json!(reader.read_array::<1>()?[0]),
+        TypeSpec::U16 => json!(u16::from_le_bytes(reader.read_array()?)),
+        TypeSpec::U32 => json!(u32::from_le_bytes(reader.read_array()?)),
+        TypeSpec::U64 => json!(u64::from_le_bytes(reader.read_array()?)),
+        TypeSpec::U128 => json!(u128::from_le_bytes(reader.read_array()?).to_string()),
+        TypeSpec::U256 => {
+            let mut bytes: [u8; 32] = reader.read_array()?;
+            bytes.reverse();
+            json!(format!("0x{}", hex::encode(bytes)))
+        }
+        TypeSpec::I8 => json!(i8::from_le_bytes(reader.read_array()?)),
+        TypeSpec::I16 => json!(i16::from_le_bytes(reader.read_array()?)),
+        TypeSpec::I32 => json!(i32::from_le_bytes(reader.read_array()?)),
+        TypeSpec::I64 => json!(i64::from_le_bytes(reader.read_array()?)),
+        TypeSpec::I128 => json!(i128::from_le_bytes(reader.read_array()?).to_string()),
+        TypeSpec::String => {
+            let length: usize = reader.read_length()?;
+            json!(String::from_utf8(reader.read_bytes(length)?.to_vec())?)
+        }
+        TypeSpec::Bool => json!(reader.read_array::<1>()?[0] != 0),
+        TypeSpec::Address => reader.read_hex(21)?,
+        TypeSpec::Hash => reader.read_hex(32)?,
+        TypeSpec::PublicKey => reader.read_hex(33)?,
+        TypeSpec::Signature => reader.read_hex(65)?,
+        TypeSpec::BlsPublicKey => reader.read_hex(96)?,
+        TypeSpec::BlsSignature => reader.read_hex(48)?,
+        TypeSpec::SizedByteArray(length) => reader.read_hex(*length as usize)?,
+        TypeSpec::Vec(element) if **element == TypeSpec::U8 => {
+            let length: usize = reader.read_length()?;
+            reader.read_hex(length)?
+        }
+        TypeSpec::Vec(element) | TypeSpec::Set(element) => {
+            let length: usize = reader.read_length()?;
+            let elements: Vec<Value> = (0..length)
+                .map(|_| decode_value(abi, element, reader, avl_trees))
+                .collect::<Result<_, _>>()?;
+            Value::Array(elements)
+        }
+        TypeSpec::SizedArray(element, length) => {
+            let elements: Vec<Value> = (0..*length)
+                .map(|_| decode_value(abi, element, reader, avl_trees))
+                .collect::<Result<_, _>>()?;
+            Value::Array(elements)
+        }
+        TypeSpec::Map(key, value) => {
+            let length: usize = reader.read_length()?;
+            let mut entries: Vec<(Value, Value)> = Vec::new();
+            for _ in 0..length {
+                let decoded_key: Value = decode_value(abi, key, reader, avl_trees)?;
+                entries.push((decoded_key, decode_value(abi, value, reader, avl_trees)?));
+            }
+            map_to_json(entries)
+        }
+        TypeSpec::AvlTreeMap(key, value) => {
+            let tree_id: i32 = i32::from_le_bytes(reader.read_array()?);
+            match avl_trees.get(&tree_id) {
+                Some(tree) => {
+                    let mut entries: Vec<(Value, Value)> = Vec::new();
+                    for (key_bytes, value_bytes) in tree {
+                        entries.push((
+                            decode_standalone(abi, key, key_bytes, avl_trees)?,
+                            decode_standalone(abi, value, value_bytes, avl_trees)?,
+                        ));
+                    }
+                    map_to_json(entries)
+                }
+                None => json!({ "avlTreeId": tree_id }),
+            }
+        }
+        TypeSpec::Option(element) => match reader.read_array::<1>()?[0] {
+            0 => Value::Null,
+            _ => decode_value(abi, element, reader, avl_trees)?,
+        },
+        TypeSpec::Named(index) => match abi.named_type(*index)? {
+            NamedTypeSpec::Struct { name, fields } => {
+                let mut object: Map<String, Value> = Map::new();
+                for field in fields {
+                    let field_value: Value = decode_value(abi, &field.type_spec, reader, avl_trees)
+                        .map_err(|e| format!("{}.{}: {}", name, field.name, e))?;
+                    object.insert(field.name.clone(), field_value);
+                }
+                Value::Object(object)
+            }
+            NamedTypeSpec::Enum { name, variants } => {
+                let discriminant: u8 = reader.read_array::<1>()?[0];
+                let (_, variant_index) = variants
+                    .iter()
+                    .find(|(variant, _)| *variant == discriminant)
+                    .ok_or_else(|| format!("{} has no variant {}", name, discriminant))?;
+                let variant_name: String = match abi.named_type(*variant_index)? {
+                    NamedTypeSpec::Struct { name, .. } | NamedTypeSpec::Enum { name, .. } => {
+                        name.clone()
+                    }
+                };
+                let variant_value: Value =
+                    decode_value(abi, &TypeSpec::Named(*variant_index), reader, avl_trees)?;
+                json!({ variant_name: variant_value })
+            }
+        },
+    };
+    Ok(value)
+}
+
+/// Decodes a value stored on its own, such as an AVL tree key
+fn decode_standalone(
+    abi: &ContractAbi,
+    type_spec: &TypeSpec,
+    bytes: &[u8],
+    avl_trees: &AvlTrees,
+) -> Result<Value, Box<dyn std::error::Error>> {
+    let mut reader = StateReader { bytes, position: 0 };
+    decode_value(abi, type_spec, &mut reader, avl_trees)
+}
+
+/// Map entries as a JSON object, keys that are not strings are written as compact JSON
+fn map_to_json(entries: Vec<(Value, Value)>) -> Value {
+    let object: Map<String, Value> = entries
+        .into_iter()
+        .map(|(key, value)| match key {
+            Value::String(key) => (key, value),
+            key => (key.to_string(), value),
+        })
+        .collect();
+    Value::Object(object)
+}
+
+/// Picks a nested value out of decoded state
+///
+/// # Arguments
+/// * `state` - Decoded state
+/// * `path` - Dot separated field names with optional indices, e.g. `upgradable_to.upgrade_rpc` or `owners[0]`
+///
+/// # Returns
+/// * `Result<Value>` - Selected value
+pub fn select_field(state: &Value, path: &str) -> Result<Value, Box<dyn std::error::Error>> {
+    let mut current: &Value = state;
+    for segment in path.split('.').filter(|segment| !segment.is_empty()) {
+        let (name, indices) = match segment.find('[') {
+            Some(start) => (&segment[..start], &segment[start..]),
+            None => (segment, ""),
+        };
+        if !name.is_empty() {
+            current = current
+                .get(name)
+                .ok_or_else(|| format!("No field {} in {}", name, path))?;
+        }
+        for index in indices.split('[').filter(|index| !index.is_empty()) {
+            let index: usize = index
+                .strip_suffix(']')
+                .and_then(|index| index.parse().ok())
+                .ok_or_else(|| format!("Invalid index in {}", segment))?;
+            current = current
+                .get(index)
+                .ok_or_else(|| format!("Index {} is out of range in {}", index, segment))?;
+        }
+    }
+    Ok(current.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::abi::tests::counter_abi_bytes;
+
+    #[test]
+    fn test_decode_state() {
+        let abi = ContractAbi::from_bytes(&counter_abi_bytes()).unwrap();
+        let mut state_bytes: Vec<u8> = 7u32.to_le_bytes().to_vec();
+        state_bytes.push(0x00);
+        state_bytes.extend([0x11; 20]);
+        let state: Value = decode_state(&abi, &state_bytes, &AvlTrees::new()).unwrap();
+        assert_eq!(state, json!({"counter": 7, "upgrader": format!("00{}", "11".repeat(20))}));
+
+        let mut trailing = state_bytes.clone();
+        trailing.push(0);
+        assert!(decode_state(&abi, &trailing, &AvlTrees::new()).is_err());
+        assert!(decode_state(&abi, &state_bytes[..4], &AvlTrees::new()).is_err());
+
+        // Config { limit: u32, owner: Option<Address> }
+        let config_abi = ContractAbi {
+            state_type: TypeSpec::Named(0),
+            ..abi
+        };
+        let config: Value = decode_state(&config_abi, &[3, 0, 0, 0, 0], &AvlTrees::new()).unwrap();
+        assert_eq!(config, json!({"limit": 3, "owner": null}));
+    }
+
+    #[test]
+    fn test_select_field() {
+        let state = json!({"config": {"limit": 3}, "owners": ["a", "b"]});
+        assert_eq!(select_field(&state, "config.limit").unwrap(), json!(3));
+        assert_eq!(select_field(&state, "owners[1]").unwrap(), json!("b"));
+        assert_eq!(select_field(&state, "").unwrap(), state);
+        assert!(select_field(&state, "config.missing").is_err());
+        assert!(select_field(&state, "owners[2]").is_err());
+    }
+}
@@ -1,14 +1,25 @@
+use crate::client::transaction::TransactionClient;
 use crate::commands::user_profile::Profile;
-use crate::utils::fs_nav::find_workspace_root;
+use crate::utils::constants::{
+    MAINNET_CHAIN_ID, MAINNET_RPC_ENDPOINT, TESTNET_CHAIN_ID, TESTNET_RPC_ENDPOINT,
+};
+use crate::utils::context::ProjectContext;
+use crate::utils::cryptography::address::address_from_private_key_hex;
 use serde::de::DeserializeOwned;
+use serde_json::Value;
 use std::collections::HashMap;
-use std::os::unix::fs::PermissionsExt;
 use std::{
     fs,
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::{Command, Output},
 };
-use tempfile::Builder;
+
+/// Output stream of a tool
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
 
 /// Prints command output to console and attempts to parse as JSON
 /// 
@@ -31,15 +42,18 @@ pub fn print_output<T: DeserializeOwned>(
     }
 }
 
-/// Verifies that current directory is a Partizee project
+/// Verifies that the project directory is a Partizee project
 /// Checks for workspace root with required structure
 /// 
+/// # Arguments
+/// * `context` - Directory the command works on
+/// 
 /// # Returns
 /// * `Result<()>` - Ok if valid project, Error otherwise
-pub fn assert_partizee_project() -> Result<(), Box<dyn std::error::Error>> {
-    let partizee_project: bool = find_workspace_root().is_some();
+pub fn assert_partizee_project(context: &ProjectContext) -> Result<(), Box<dyn std::error::Error>> {
+    let partizee_project: bool = context.workspace_root().is_some();
     if !partizee_project {
-        return Err("Current directory is not a partizee project".into());
+        return Err(format!("{} is not a partizee project", context.project_dir.display()).into());
     }
     Ok(())
 }
@@ -180,7 +194,7 @@ pub fn get_account_address_from_path(path: &PathBuf) -> Result<String, Box<dyn s
 }
 
 /// Derives blockchain address from private key
-/// Uses temporary file to call pbc command
+/// Computes the account address natively from the secp256k1 public key
 /// 
 /// # Arguments
 /// * `private_key` - Private key to derive address from
@@ -190,43 +204,10 @@ pub fn get_account_address_from_path(path: &PathBuf) -> Result<String, Box<dyn s
 pub fn get_address_from_pk(private_key: &str) -> Result<String, Box<dyn std::error::Error>> {
     // validate pk length
     assert_private_key_length(private_key)?;
-    // write temp file with private key
-    let all_read_write = std::fs::Permissions::from_mode(0o666);
-    let temp_pk = Builder::new()
-        .permissions(all_read_write)
-        .tempfile()
-        .unwrap();
-    std::fs::write(&temp_pk.path(), private_key)?;
-
-    let mut command = Command::new("cargo");
-    command.arg("pbc").arg("account").arg("address").arg(
-        temp_pk
-            .path()
-            .canonicalize()
-            .unwrap()
-            .to_str()
-            .ok_or("Invalid UTF-8 in path")?,
-    );
-
-    let output = command.output();
-    std::fs::remove_file(temp_pk.path()).unwrap();
-    if !output.as_ref().unwrap().status.success() {
-        let stderr = String::from_utf8_lossy(&output.as_ref().unwrap().stderr);
-        return Err(format!("Command failed: {}", stderr).into());
-    }
-
-    if output.is_ok() {
-        // get address from command output
-        let mut address: String =
-            String::from_utf8_lossy(&output.as_ref().unwrap().stdout).to_string();
-        // trim non alphanumeric characters
-        address = address.chars().filter(|c| c.is_alphanumeric()).collect();
-        // validate address length
-        assert_address_length(&address)?;
-        Ok(address)
-    } else {
-        return print_error(&output.unwrap());
-    }
+    let address: String = address_from_private_key_hex(private_key)?;
+    // validate address length
+    assert_address_length(&address)?;
+    Ok(address)
 }
 
 /// Validates that address matches private key
@@ -263,12 +244,14 @@ pub fn address_is_valid(
 /// 
 /// # Arguments
 /// * `private_key` - Private key to save
+/// * `context` - Directory the command works on
 /// 
 /// # Returns
 /// * `Result<PathBuf>` - Path to created file if successful
-pub fn create_pk_file(private_key: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
-    let root_path: PathBuf =
-        find_workspace_root().expect("create_pk_file: Failed to find workspace root");
+pub fn create_pk_file(private_key: &str, context: &ProjectContext) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let root_path: PathBuf = context
+        .workspace_root()
+        .expect("create_pk_file: Failed to find workspace root");
     let address: String = get_address_from_pk(private_key)
         .expect("create_pk_file: Failed to get address from private key");
     let pk_file: PathBuf = root_path.join(format!("{}.pk", address));
@@ -297,6 +280,126 @@ pub fn trim_public_key(std_output: &Output) -> String {
     public_key
 }
 
+/// Resolves the node endpoint for a network
+/// Custom networks may be given directly as an http(s) url
+/// 
+/// # Arguments
+/// * `network` - Network name (testnet, mainnet) or node url
+/// 
+/// # Returns
+/// * `Result<String>` - Base url of the node, an error for unknown networks
+pub fn get_rpc_endpoint(network: &str) -> Result<String, Box<dyn std::error::Error>> {
+    match network {
+        "mainnet" => Ok(MAINNET_RPC_ENDPOINT.to_string()),
+        "testnet" => Ok(TESTNET_RPC_ENDPOINT.to_string()),
+        url if url.starts_with("http://") || url.starts_with("https://") => {
+            Ok(url.trim_end_matches('/').to_string())
+        }
+        other => Err(format!("Unknown network {}, use testnet, mainnet or a node url", other).into()),
+    }
+}
+
+/// Chain id of a named network or of the node url it resolves to, without contacting it
+/// 
+/// # Arguments
+/// * `network` - Network name (testnet, mainnet) or node url
+/// 
+/// # Returns
+/// * `Option<&str>` - Chain id of the network, None for other node urls
+pub fn known_chain_id(network: &str) -> Option<&'static str> {
+    match network.trim_end_matches('/') {
+        "mainnet" | MAINNET_RPC_ENDPOINT => Some(MAINNET_CHAIN_ID),
+        "testnet" | TESTNET_RPC_ENDPOINT => Some(TESTNET_CHAIN_ID),
+        _ => None,
+    }
+}
+
+/// Resolves the chain id transactions on a network are signed for
+/// Other node urls are asked for the chain they serve
+/// 
+/// # Arguments
+/// * `network` - Network name (testnet, mainnet) or node url
+/// 
+/// # Returns
+/// * `Result<String>` - Chain id of the network, an error if it cannot be resolved
+pub fn get_chain_id(network: &str) -> Result<String, Box<dyn std::error::Error>> {
+    if let Some(chain_id) = known_chain_id(network) {
+        return Ok(chain_id.to_string());
+    }
+    let client: TransactionClient = TransactionClient::new(&get_rpc_endpoint(network)?, "");
+    let chain: Value = block_on(client.get_chain())?
+        .map_err(|e| format!("Failed to resolve the chain id of {}: {}", network, e))?;
+    chain["chainId"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| format!("{} did not report its chain id", network).into())
+}
+
+/// Runs a tool and returns what it printed to one of its streams
+/// 
+/// # Arguments
+/// * `dir` - Directory to run the tool in
+/// * `program` - Tool to run
+/// * `args` - Arguments of the tool
+/// * `stream` - Stream to read, some tools such as `java -version` print to stderr
+/// 
+/// # Returns
+/// * `Option<String>` - Trimmed output, None if the tool could not be run or failed
+pub fn command_output(dir: &Path, program: &str, args: &[&str], stream: OutputStream) -> Option<String> {
+    let output: Output = Command::new(program).args(args).current_dir(dir).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let printed: &[u8] = match stream {
+        OutputStream::Stdout => &output.stdout,
+        OutputStream::Stderr => &output.stderr,
+    };
+    Some(String::from_utf8_lossy(printed).trim().to_string())
+}
+
+/// Runs a future to completion from synchronous code
+/// 
+/// # Arguments
+/// * `future` - Future to run
+/// 
+/// # Returns
+/// * `Result<T>` - Output of the future
+pub fn block_on<F: std::future::Future>(future: F) -> Result<F::Output, Box<dyn std::error::Error>> {
+    let runtime = tokio::runtime::Runtime::new()
+        .map_err(|e| format!("block_on: Failed to start async runtime: {}", e))?;
+    Ok(runtime.block_on(future))
+}
+
+/// Parses `<contract>=<gas>` pairs into per contract gas limits
+/// 
+/// # Arguments
+/// * `gas_args` - Optional vector of gas arguments
+/// 
+/// # Returns
+/// * `Result<Option<HashMap<String, i64>>>` - Map of lowercase contract names to gas limits
+pub fn parse_gas_limits(
+    gas_args: Option<Vec<String>>,
+) -> Result<Option<HashMap<String, i64>>, Box<dyn std::error::Error>> {
+    let gas_args: Vec<String> = match gas_args {
+        Some(gas_args) => gas_args,
+        None => return Ok(None),
+    };
+    let mut gas_limits: HashMap<String, i64> = HashMap::new();
+    for gas_arg in gas_args {
+        let (name, gas) = gas_arg
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid gas limit {}, expected <contract>=<gas>", gas_arg))?;
+        let gas: i64 = gas
+            .replace('_', "")
+            .parse()
+            .ok()
+            .filter(|gas: &i64| *gas > 0)
+            .ok_or_else(|| format!("Invalid gas limit for {}: {}", name, gas))?;
+        gas_limits.insert(name.to_lowercase(), gas);
+    }
+    Ok(Some(gas_limits))
+}
+
 /// Parses deployment arguments into contract-specific arguments
 /// 
 /// # Arguments
@@ -350,10 +453,12 @@ pub fn parse_deploy_args(
 /// Sets up test environment with mock files and directories
 /// Creates temporary directory with Partizee project structure
 /// 
+/// Tests pass the returned path to the code under test, the working directory is never changed.
+/// 
 /// # Returns
-/// * `(TempDir, PathBuf, PathBuf)` - Temp directory, path, and original directory
+/// * `(TempDir, PathBuf)` - Temp directory and its path
 #[cfg(test)]
-pub fn setup_test_environment() -> (tempfile::TempDir, PathBuf, PathBuf) {
+pub fn setup_test_environment() -> (tempfile::TempDir, PathBuf) {
     let temp_dir = tempfile::tempdir().unwrap();
     // create a mock pk file
     let pk_file = temp_dir
@@ -376,8 +481,7 @@ pub fn setup_test_environment() -> (tempfile::TempDir, PathBuf, PathBuf) {
     let cargo_toml = temp_dir.path().join("Cargo.toml");
     fs::write(cargo_toml, "[workspace]\n[package]").unwrap();
     let temp_path = temp_dir.path().to_path_buf();
-    let original_dir = std::env::current_dir().unwrap();
-    (temp_dir, temp_path, original_dir) // Return temp_dir so it stays alive
+    (temp_dir, temp_path) // Return temp_dir so it stays alive
 }
 
 #[cfg(test)]
@@ -395,6 +499,59 @@ mod tests {
         assert_eq!(valid_address, true, "failed to validate address");
     }
 
+    #[test]
+    fn test_validate_mismatched_address() {
+        let valid_address = address_is_valid(
+            "00d277aa1bf5702ab9fc690b04bd68b5a981095531",
+            "9c1a15a50a4f978f0085bd747b9da360cc0fbf5f1d0744e040873aeba46b37b0",
+        )
+        .unwrap();
+        assert_eq!(valid_address, false, "mismatched address should not validate");
+    }
+
+    #[test]
+    fn test_parse_gas_limits() {
+        let gas_limits = parse_gas_limits(Some(vec![
+            "counterV1=2000000".to_string(),
+            "counterV2=3_000_000".to_string(),
+        ]))
+        .unwrap()
+        .unwrap();
+        assert_eq!(gas_limits["counterv1"], 2_000_000);
+        assert_eq!(gas_limits["counterv2"], 3_000_000);
+        assert!(parse_gas_limits(None).unwrap().is_none());
+        assert!(parse_gas_limits(Some(vec!["2000000".to_string()])).is_err());
+        assert!(parse_gas_limits(Some(vec!["counterV1=-5".to_string()])).is_err());
+    }
+
+    #[test]
+    fn test_command_output() {
+        let dir: PathBuf = std::env::temp_dir();
+        let version: String = command_output(&dir, "cargo", &["--version"], OutputStream::Stdout).unwrap();
+        assert!(version.starts_with("cargo "), "unexpected output: {}", version);
+        assert_eq!(command_output(&dir, "cargo", &["--version"], OutputStream::Stderr).unwrap(), "");
+        assert!(command_output(&dir, "cargo", &["--not-a-cargo-flag"], OutputStream::Stdout).is_none());
+        assert!(command_output(&dir, "not-a-partizee-tool", &[], OutputStream::Stdout).is_none());
+    }
+
+    #[test]
+    fn test_network_resolution() {
+        assert_eq!(get_rpc_endpoint("mainnet").unwrap(), MAINNET_RPC_ENDPOINT);
+        assert_eq!(get_rpc_endpoint("testnet").unwrap(), TESTNET_RPC_ENDPOINT);
+        assert_eq!(get_rpc_endpoint("http://localhost:8080/").unwrap(), "http://localhost:8080");
+        assert_eq!(
+            get_rpc_endpoint("testnett").unwrap_err().to_string(),
+            "Unknown network testnett, use testnet, mainnet or a node url"
+        );
+        assert!(get_rpc_endpoint("httpbin").is_err());
+        assert_eq!(get_chain_id("mainnet").unwrap(), MAINNET_CHAIN_ID);
+        assert_eq!(get_chain_id("testnet").unwrap(), TESTNET_CHAIN_ID);
+        assert_eq!(known_chain_id(&format!("{}/", MAINNET_RPC_ENDPOINT)), Some(MAINNET_CHAIN_ID));
+        // a name merely containing mainnet is not mainnet
+        assert!(get_chain_id("my-mainnet-fork").is_err());
+        assert!(get_chain_id("").is_err());
+    }
+
     #[test]
     fn test_get_address_from_pk() {
         let result =
@@ -3,7 +3,7 @@ import { join } from 'path';
 import { CONTRACT_ADDRESS } from '../src/utils/configs';
 
 if (!CONTRACT_ADDRESS) {
-  console.error('Please provide an address for CONTRACT_ADDRESS in /frontend/src/utils/configs.ts');
+  console.error('No deployed address for CONTRACT_NAME in /frontend/src/utils/configs.ts, run `partizee deploy` first');
   process.exit(1);
 }
 
@@ -0,0 +1,6 @@
+// This file is generated by `partizee deploy`, do not edit it by hand.
+export const NETWORK = "testnet";
+export const CHAIN_ID = "Partisia Blockchain Testnet";
+
+export const DEPLOYMENTS: Record<string, string> = {
+};
@@ -1,16 +1,19 @@
 import { PartisiaSDKConfigs, PermissionTypes } from "@/types/partisia";
+import { CHAIN_ID, DEPLOYMENTS } from "@/generated/deployments";
 
 // Edit these configs to your own needs
 
 // Partisia SDK Configs
 export const PARTISIA_SDK_CONFIGS: PartisiaSDKConfigs = {
-  chainId: "Partisia Blockchain Testnet",
+  chainId: CHAIN_ID,
   permissions: [PermissionTypes.SIGN],
   dappName: "Partisia Dapp Template",
 };
 
 // Contract Configs
-export const CONTRACT_ADDRESS = "02acd7b6a57682122078bf47b8a3fafeca38ddf5a1"
+// Addresses are written to src/generated/deployments.ts by `partizee deploy`
+export const CONTRACT_NAME = "counterV1";
+export const CONTRACT_ADDRESS = DEPLOYMENTS[CONTRACT_NAME] ?? "";
 
 export const TESTNET_URL = "https://node1.testnet.partisiablockchain.com"
 
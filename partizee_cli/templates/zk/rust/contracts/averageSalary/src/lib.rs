@@ -0,0 +1,153 @@
+#![doc = include_str!("../README.md")]
+
+#[macro_use]
+extern crate pbc_contract_codegen;
+
+use create_type_spec_derive::CreateTypeSpec;
+use pbc_contract_common::address::Address;
+use pbc_contract_common::context::ContractContext;
+use pbc_contract_common::events::EventGroup;
+use pbc_contract_common::zk::{CalculationStatus, SecretVarId, ZkInputDef, ZkState, ZkStateChange};
+use pbc_zk::Sbi32;
+use read_write_state_derive::ReadWriteState;
+
+mod zk_compute;
+
+/// Metadata stored with each secret variable.
+#[derive(ReadWriteState, Debug, Clone, CreateTypeSpec)]
+pub struct SecretVarMetadata {
+    variable_type: SecretVarType,
+}
+
+/// Kind of secret variable.
+#[derive(ReadWriteState, Debug, PartialEq, Clone, CreateTypeSpec)]
+#[repr(u8)]
+pub enum SecretVarType {
+    #[discriminant(0)]
+    Salary {},
+    #[discriminant(1)]
+    SumResult {},
+}
+
+/// Contract state.
+#[state]
+pub struct ContractState {
+    /// Account allowed to start the computation.
+    administrator: Address,
+    /// Sum of the salaries, once computed.
+    sum_result: Option<u32>,
+    /// Number of salaries the sum was computed over.
+    num_employees: Option<u32>,
+}
+
+/// Initialize the contract with the deployer as administrator.
+#[init(zk = true)]
+pub fn initialize(ctx: ContractContext, _zk_state: ZkState<SecretVarMetadata>) -> ContractState {
+    ContractState {
+        administrator: ctx.sender,
+        sum_result: None,
+        num_employees: None,
+    }
+}
+
+/// Input a salary as a secret 32-bit integer, one per account.
+#[zk_on_secret_input(shortname = 0x40)]
+pub fn add_salary(
+    context: ContractContext,
+    state: ContractState,
+    zk_state: ZkState<SecretVarMetadata>,
+) -> (
+    ContractState,
+    Vec<EventGroup>,
+    ZkInputDef<SecretVarMetadata, Sbi32>,
+) {
+    assert!(
+        zk_state
+            .secret_variables
+            .iter()
+            .chain(zk_state.pending_inputs.iter())
+            .all(|(_, v)| v.owner != context.sender),
+        "Each address is only allowed to send one salary variable. Sender: {:?}",
+        context.sender
+    );
+    let input_def = ZkInputDef::with_metadata(
+        Some(SHORTNAME_INPUTTED_VARIABLE),
+        SecretVarMetadata {
+            variable_type: SecretVarType::Salary {},
+        },
+    );
+    (state, vec![], input_def)
+}
+
+/// Called once a secret salary has been input.
+#[zk_on_variable_inputted(shortname = 0x41)]
+pub fn inputted_variable(
+    _context: ContractContext,
+    state: ContractState,
+    _zk_state: ZkState<SecretVarMetadata>,
+    _inputted_variable: SecretVarId,
+) -> ContractState {
+    state
+}
+
+/// Start summing the secret salaries.
+#[action(shortname = 0x01, zk = true)]
+pub fn compute_salary(
+    context: ContractContext,
+    mut state: ContractState,
+    zk_state: ZkState<SecretVarMetadata>,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    assert!(
+        context.sender == state.administrator,
+        "Only administrator can start computation"
+    );
+    assert!(
+        zk_state.calculation_state == CalculationStatus::Waiting,
+        "Computation must start from Waiting state, but was {:?}",
+        zk_state.calculation_state,
+    );
+    state.num_employees = Some(zk_state.secret_variables.len() as u32);
+    (
+        state,
+        vec![],
+        vec![zk_compute::sum_everything_start(
+            Some(SHORTNAME_SUM_COMPUTE_COMPLETE),
+            &SecretVarMetadata {
+                variable_type: SecretVarType::SumResult {},
+            },
+        )],
+    )
+}
+
+/// Open the sum once the computation is done.
+#[zk_on_compute_complete(shortname = 0x42)]
+pub fn sum_compute_complete(
+    _context: ContractContext,
+    state: ContractState,
+    _zk_state: ZkState<SecretVarMetadata>,
+    output_variables: Vec<SecretVarId>,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    (
+        state,
+        vec![],
+        vec![ZkStateChange::OpenVariables {
+            variables: output_variables,
+        }],
+    )
+}
+
+/// Store the opened sum in the state.
+#[zk_on_variables_opened]
+pub fn open_sum_variable(
+    _context: ContractContext,
+    mut state: ContractState,
+    zk_state: ZkState<SecretVarMetadata>,
+    opened_variables: Vec<SecretVarId>,
+) -> (ContractState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    assert_eq!(opened_variables.len(), 1, "Unexpected number of output variables");
+    let sum_variable = zk_state.get_variable(opened_variables[0]).unwrap();
+    let mut buffer = [0u8; 4];
+    buffer.copy_from_slice(sum_variable.data.as_ref().unwrap().as_slice());
+    state.sum_result = Some(u32::from_le_bytes(buffer));
+    (state, vec![], vec![ZkStateChange::ContractDone])
+}
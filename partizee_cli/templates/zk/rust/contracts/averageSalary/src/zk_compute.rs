@@ -0,0 +1,15 @@
+//! Computation run by the ZK nodes on the secret salaries.
+
+use pbc_zk::*;
+
+/// Sums every secret salary that has been input.
+///
+/// Only the sum is opened, the individual salaries stay secret.
+#[zk_compute(shortname = 0x61)]
+pub fn sum_everything() -> Sbi32 {
+    let mut sum: Sbi32 = Sbi32::from(0);
+    for variable_id in secret_variable_ids() {
+        sum = sum + load_sbi::<Sbi32>(variable_id);
+    }
+    sum
+}